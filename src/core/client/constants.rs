@@ -26,6 +26,9 @@ pub const DEFAULT_CRUMB_URL: &str = "https://query1.finance.yahoo.com/v1/test/ge
 /// Base URL for the Yahoo Finance v7 quote API.
 pub const DEFAULT_BASE_QUOTE_V7: &str = "https://query1.finance.yahoo.com/v7/finance/quote";
 
+/// Base URL for the Yahoo Finance spark API (batched close series).
+pub const DEFAULT_BASE_SPARK: &str = "https://query1.finance.yahoo.com/v8/finance/spark";
+
 /// Base URL for the Yahoo Finance v7 options API.
 pub const DEFAULT_BASE_OPTIONS_V7: &str = "https://query1.finance.yahoo.com/v7/finance/options/";
 
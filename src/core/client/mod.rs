@@ -73,6 +73,7 @@ pub struct YfClient {
     base_news: Url,
     base_insider_search: Url,
     base_timeseries: Url,
+    base_spark: Url,
     cookie_url: Url,
     crumb_url: Url,
     user_agent: String,
@@ -84,6 +85,7 @@ pub struct YfClient {
     api_preference: ApiPreference,
 
     retry: RetryConfig,
+    normalize_minor_units: bool,
     reporting_currency_cache: Arc<RwLock<HashMap<String, Currency>>>,
     // Cache of resolved instruments by original ticker string
     instrument_cache: Arc<RwLock<HashMap<String, paft::domain::Instrument>>>,
@@ -148,6 +150,10 @@ impl YfClient {
         &self.base_timeseries
     }
 
+    pub(crate) const fn base_spark(&self) -> &Url {
+        &self.base_spark
+    }
+
     #[cfg(feature = "test-mode")]
     pub(crate) const fn api_preference(&self) -> ApiPreference {
         self.api_preference
@@ -197,6 +203,12 @@ impl YfClient {
         guard.insert(key, inst);
     }
 
+    /// Whether prices quoted in minor units (`GBp`, `ZAc`, `ILA`) are converted to the major currency.
+    #[must_use]
+    pub const fn normalize_minor_units(&self) -> bool {
+        self.normalize_minor_units
+    }
+
     /// Clears the entire in-memory cache.
     ///
     /// This is an asynchronous operation that will acquire a write lock on the cache.
@@ -390,6 +402,7 @@ pub struct YfClientBuilder {
     base_news: Option<Url>,
     base_insider_search: Option<Url>,
     base_timeseries: Option<Url>,
+    base_spark: Option<Url>,
     cookie_url: Option<Url>,
     crumb_url: Option<Url>,
 
@@ -404,6 +417,7 @@ pub struct YfClientBuilder {
     connect_timeout: Option<Duration>,
     retry: Option<RetryConfig>,
     cache_ttl: Option<Duration>,
+    normalize_minor_units: bool,
 
     // New fields for custom client and proxy configuration
     custom_client: Option<Client>,
@@ -510,6 +524,14 @@ impl YfClientBuilder {
         self
     }
 
+    /// Sets a custom base URL for the spark endpoint (batched close series).
+    /// Default: `https://query1.finance.yahoo.com/v8/finance/spark`.
+    #[must_use]
+    pub fn base_spark(mut self, url: Url) -> Self {
+        self.base_spark = Some(url);
+        self
+    }
+
     /// Overrides the URL used to acquire an initial cookie.
     #[must_use]
     pub fn cookie_url(mut self, url: Url) -> Self {
@@ -542,6 +564,18 @@ impl YfClientBuilder {
         self
     }
 
+    /// Sets whether prices quoted in minor units are converted to the major currency. (Default: `false`)
+    ///
+    /// London, Johannesburg and Tel Aviv listings are quoted in pence/cents/agorot (`GBp`, `ZAc`,
+    /// `ILA`). When enabled, history, quotes, options and streaming prices for such listings are
+    /// divided by 100 and labelled `GBP`, `ZAR` or `ILS`, matching the currency used for their
+    /// fundamentals.
+    #[must_use]
+    pub const fn normalize_minor_units(mut self, yes: bool) -> Self {
+        self.normalize_minor_units = yes;
+        self
+    }
+
     /// Disables in-memory caching for this client.
     #[must_use]
     pub const fn no_cache(mut self) -> Self {
@@ -794,6 +828,9 @@ impl YfClientBuilder {
         let base_timeseries = self
             .base_timeseries
             .unwrap_or(Url::parse(constants::DEFAULT_BASE_TIMESERIES)?);
+        let base_spark = self
+            .base_spark
+            .unwrap_or(Url::parse(constants::DEFAULT_BASE_SPARK)?);
 
         let cookie_url = self.cookie_url.unwrap_or(Url::parse(DEFAULT_COOKIE_URL)?);
         let crumb_url = self.crumb_url.unwrap_or(Url::parse(DEFAULT_CRUMB_URL)?);
@@ -853,6 +890,7 @@ impl YfClientBuilder {
             base_news,
             base_insider_search,
             base_timeseries,
+            base_spark,
             cookie_url,
             crumb_url,
             user_agent,
@@ -861,6 +899,7 @@ impl YfClientBuilder {
             #[cfg(feature = "test-mode")]
             api_preference: self.api_preference.unwrap_or(ApiPreference::ApiThenScrape),
             retry: self.retry.unwrap_or_default(),
+            normalize_minor_units: self.normalize_minor_units,
             reporting_currency_cache: Arc::new(RwLock::new(HashMap::new())),
             instrument_cache: Arc::new(RwLock::new(HashMap::new())),
             cache: self.cache_ttl.map(|ttl| {
@@ -880,7 +919,7 @@ async fn sleep_backoff(b: &Backoff, attempt: u32) {
 }
 
 #[inline]
-fn compute_backoff_duration(b: &Backoff, attempt: u32) -> Duration {
+pub(crate) fn compute_backoff_duration(b: &Backoff, attempt: u32) -> Duration {
     use std::time::Duration;
     match *b {
         Backoff::Fixed(d) => d,
@@ -1,7 +1,7 @@
 //! Conversion utilities
 
 use chrono::{DateTime, Utc};
-use paft::domain::{Exchange, MarketState, Period};
+use paft::domain::{AssetKind, Exchange, MarketState, Period};
 use paft::fundamentals::analysis::{RecommendationAction, RecommendationGrade};
 use paft::fundamentals::holders::{InsiderPosition, TransactionType};
 use paft::fundamentals::profile::FundKind;
@@ -65,6 +65,71 @@ pub fn f64_to_money_with_currency_str(value: f64, currency_str: Option<&str>) ->
     f64_to_money_with_currency(value, currency)
 }
 
+/// Convert f64 to Money with currency string, converting Yahoo's minor-unit codes
+/// (`GBp`, `ZAc`, `ILA`) to the major ISO currency when `normalize` is set.
+#[must_use]
+pub fn f64_to_money_with_currency_str_normalized(
+    value: f64,
+    currency_str: Option<&str>,
+    normalize: bool,
+) -> Money {
+    let money = f64_to_money_with_currency_str(value, currency_str);
+    if normalize {
+        crate::core::currency::to_major_units(money, currency_str)
+    } else {
+        money
+    }
+}
+
+/// Convert a `Decimal` to Money with specified currency (no precision loss)
+///
+/// # Panics
+/// Panics if currency metadata is not registered for non-ISO currencies.
+#[must_use]
+pub fn decimal_to_money_with_currency(value: rust_decimal::Decimal, currency: Currency) -> Money {
+    Money::new(value, currency).expect("currency metadata available")
+}
+
+/// Convert a `Decimal` to Money with currency string (parses currency string to Currency enum)
+#[must_use]
+pub fn decimal_to_money_with_currency_str(
+    value: rust_decimal::Decimal,
+    currency_str: Option<&str>,
+) -> Money {
+    let currency = currency_str
+        .and_then(|s| Currency::from_str(s).ok())
+        .unwrap_or(Currency::Iso(IsoCurrency::USD));
+    decimal_to_money_with_currency(value, currency)
+}
+
+/// Convert a `Decimal` to Money with currency string, converting Yahoo's minor-unit codes
+/// (`GBp`, `ZAc`, `ILA`) to the major ISO currency when `normalize` is set.
+#[must_use]
+pub fn decimal_to_money_with_currency_str_normalized(
+    value: rust_decimal::Decimal,
+    currency_str: Option<&str>,
+    normalize: bool,
+) -> Money {
+    let money = decimal_to_money_with_currency_str(value, currency_str);
+    if normalize {
+        crate::core::currency::to_major_units(money, currency_str)
+    } else {
+        money
+    }
+}
+
+/// Converts an `f64` to the `Decimal` with the shortest representation that round-trips
+/// to the same float, so `0.1` becomes exactly `0.1` rather than its binary expansion.
+///
+/// Non-finite or out-of-range values return `None`.
+#[must_use]
+pub fn f64_to_decimal_shortest(value: f64) -> Option<rust_decimal::Decimal> {
+    if !value.is_finite() {
+        return None;
+    }
+    rust_decimal::Decimal::from_str(&value.to_string()).ok()
+}
+
 /// Convert Money to f64 (loses currency information)
 #[must_use]
 pub fn money_to_f64(money: &Money) -> f64 {
@@ -147,6 +212,65 @@ pub fn exchange_to_string(exchange: Option<Exchange>) -> Option<String> {
     exchange.map(|e| e.to_string())
 }
 
+/// Quote currencies of Yahoo's `BASE-QUOTE` crypto symbols, e.g. `BTC-USD` or `ETH-BTC`.
+const CRYPTO_QUOTE_CODES: &[&str] = &[
+    "USD", "EUR", "GBP", "JPY", "CAD", "AUD", "CHF", "CNY", "KRW", "INR", "USDT", "USDC", "BTC",
+    "ETH",
+];
+
+/// Convert Yahoo's `quoteType` (e.g. `EQUITY`, `ETF`, `CRYPTOCURRENCY`) to `AssetKind`
+#[must_use]
+pub fn string_to_asset_kind(s: &str) -> Option<AssetKind> {
+    match s.trim().to_ascii_uppercase().as_str() {
+        "EQUITY" => Some(AssetKind::Equity),
+        "ETF" | "MUTUALFUND" | "MONEYMARKET" => Some(AssetKind::Fund),
+        "INDEX" => Some(AssetKind::Index),
+        "CURRENCY" => Some(AssetKind::Forex),
+        "CRYPTOCURRENCY" => Some(AssetKind::Crypto),
+        "FUTURE" => Some(AssetKind::Future),
+        "OPTION" => Some(AssetKind::Option),
+        "BOND" => Some(AssetKind::Bond),
+        _ => s.parse().ok(),
+    }
+}
+
+/// Infer `AssetKind` from Yahoo symbol syntax: `^GSPC` is an index, `EURUSD=X` a currency
+/// pair, `ES=F` a future, `BTC-USD` a cryptocurrency and `AAPL240119C00150000` an option.
+/// Anything else is taken to be an equity.
+#[must_use]
+pub fn symbol_to_asset_kind(symbol: &str) -> AssetKind {
+    let s = symbol.trim().to_ascii_uppercase();
+    if s.starts_with('^') {
+        AssetKind::Index
+    } else if s.ends_with("=X") {
+        AssetKind::Forex
+    } else if s.ends_with("=F") {
+        AssetKind::Future
+    } else if is_occ_option(&s) {
+        AssetKind::Option
+    } else if s
+        .rsplit_once('-')
+        .is_some_and(|(base, quote)| !base.is_empty() && CRYPTO_QUOTE_CODES.contains(&quote))
+    {
+        AssetKind::Crypto
+    } else {
+        AssetKind::Equity
+    }
+}
+
+/// `ROOT` + `YYMMDD` + `C`/`P` + eight-digit strike.
+fn is_occ_option(s: &str) -> bool {
+    let b = s.as_bytes();
+    if b.len() < 16 {
+        return false;
+    }
+    let (root, tail) = b.split_at(b.len() - 15);
+    root.iter().all(u8::is_ascii_alphanumeric)
+        && tail[..6].iter().all(u8::is_ascii_digit)
+        && matches!(tail[6], b'C' | b'P')
+        && tail[7..].iter().all(u8::is_ascii_digit)
+}
+
 /// Convert String to `MarketState` enum
 #[must_use]
 pub fn string_to_market_state(s: Option<String>) -> Option<MarketState> {
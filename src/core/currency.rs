@@ -1,8 +1,39 @@
-//! Helpers for inferring currencies from country information.
+//! Helpers for inferring currencies from country information and normalizing minor units.
 
 use std::{collections::HashMap, sync::LazyLock};
 
-use paft::money::{Currency, IsoCurrency};
+use paft::money::{Currency, IsoCurrency, Money};
+use rust_decimal::Decimal;
+
+/// Yahoo's minor-unit price codes → (major ISO currency, minor units per major unit).
+///
+/// Matching is case-sensitive: `GBp` is pence while `GBP` is pounds.
+const MINOR_UNIT_CODES: &[(&str, &str, u32)] = &[
+    ("GBp", "GBP", 100),
+    ("GBX", "GBP", 100),
+    ("ZAc", "ZAR", 100),
+    ("ZAC", "ZAR", 100),
+    ("ILA", "ILS", 100),
+];
+
+/// Returns the major currency and divisor for one of Yahoo's minor-unit codes.
+pub fn minor_unit(code: &str) -> Option<(Currency, Decimal)> {
+    MINOR_UNIT_CODES
+        .iter()
+        .find(|(minor, _, _)| *minor == code)
+        .and_then(|(_, major, per)| Some((major.parse().ok()?, Decimal::from(*per))))
+}
+
+/// Re-expresses `money` in the major currency if `code` (the currency Yahoo quoted the value
+/// in) is a minor-unit code. Other values are returned unchanged.
+///
+/// The division is done on the decimal amount, so no precision is lost.
+pub fn to_major_units(money: Money, code: Option<&str>) -> Money {
+    match code.and_then(minor_unit) {
+        Some((major, per)) => Money::new(money.amount() / per, major).unwrap_or(money),
+        None => money,
+    }
+}
 
 /// Normalized country → currency code pairs.
 ///
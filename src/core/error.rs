@@ -84,6 +84,10 @@ pub enum YfError {
     /// An error indicating that the provided date range is invalid (e.g., start date after end date).
     #[error("Invalid date range: start date must be before end date")]
     InvalidDates,
+
+    /// Price data failed integrity validation under `ValidationMode::Error`.
+    #[error("Price data failed validation: {0}")]
+    Validation(Box<crate::history::ValidationReport>),
 }
 
 impl From<tokio_tungstenite::tungstenite::Error> for YfError {
@@ -91,3 +95,19 @@ impl From<tokio_tungstenite::tungstenite::Error> for YfError {
         Self::Websocket(Box::new(e))
     }
 }
+
+impl YfError {
+    /// Returns `true` if the error is likely to go away when the request is retried.
+    ///
+    /// Rate limiting, 5xx responses, 408 timeouts and HTTP timeout or connection errors are
+    /// transient; everything else (not found, parse failures, invalid parameters) is not.
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::RateLimited { .. } | Self::ServerError { .. } => true,
+            Self::Status { status, .. } => *status == 408,
+            Self::Http(e) => e.is_timeout() || e.is_connect(),
+            _ => false,
+        }
+    }
+}
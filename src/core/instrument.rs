@@ -0,0 +1,56 @@
+//! Resolving `Instrument`s for returned data, backed by the client's instrument cache.
+
+use paft::domain::{AssetKind, Exchange, IdentifierScheme, Instrument};
+
+use crate::core::YfClient;
+use crate::core::conversions::symbol_to_asset_kind;
+
+/// Builds an instrument for `symbol`, inferring the kind from the symbol syntax if Yahoo did
+/// not report it.
+pub fn build_instrument(
+    symbol: &str,
+    kind: Option<AssetKind>,
+    exchange: Option<Exchange>,
+) -> Option<Instrument> {
+    let kind = kind.unwrap_or_else(|| symbol_to_asset_kind(symbol));
+    exchange
+        .map_or_else(
+            || Instrument::from_symbol(symbol, kind),
+            |ex| Instrument::from_symbol_and_exchange(symbol, ex, kind),
+        )
+        .ok()
+}
+
+/// Returns the instrument for `symbol` and caches it.
+///
+/// When Yahoo reported `kind`, the instrument is built from it and replaces any cached one, so
+/// a kind guessed from the symbol syntax is corrected. Otherwise the cached instrument is
+/// returned, or one is built with [`build_instrument`]. Instruments from v7 quotes are cached
+/// as they arrive, so a symbol seen in a quote keeps Yahoo's own kind and exchange; a caller
+/// without an exchange keeps the cached one.
+pub async fn resolve_instrument(
+    client: &YfClient,
+    symbol: &str,
+    kind: Option<AssetKind>,
+    exchange: Option<Exchange>,
+) -> Option<Instrument> {
+    let cached = client.cached_instrument(symbol).await;
+    if kind.is_none()
+        && let Some(inst) = &cached
+    {
+        return Some(inst.clone());
+    }
+    let exchange = exchange.or_else(|| cached.as_ref().and_then(instrument_exchange));
+    let inst = build_instrument(symbol, kind, exchange)?;
+    client
+        .store_instrument(symbol.to_string(), inst.clone())
+        .await;
+    Some(inst)
+}
+
+fn instrument_exchange(inst: &Instrument) -> Option<Exchange> {
+    match inst.id() {
+        IdentifierScheme::Security(sec) => sec.exchange.clone(),
+        IdentifierScheme::Prediction(_) => None,
+    }
+}
@@ -11,6 +11,7 @@ pub mod client;
 pub(crate) mod currency;
 /// The primary error type (`YfError`) for the crate.
 pub mod error;
+pub(crate) mod instrument;
 /// Shared data models used across multiple API modules (e.g., `Quote`, `Candle`).
 pub mod models;
 pub(crate) mod quotes;
@@ -1,4 +1,9 @@
 // src/core/quotes.rs
+use std::collections::{HashMap, HashSet};
+
+use futures::stream::{self, StreamExt};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 use serde::Deserialize;
 use url::Url;
 
@@ -6,13 +11,18 @@ use crate::{
     YfClient, YfError,
     core::{
         client::{CacheMode, RetryConfig},
-        conversions::f64_to_money_with_currency_str,
+        conversions::{
+            decimal_to_money_with_currency_str_normalized, i64_to_datetime, string_to_asset_kind,
+        },
+        instrument::build_instrument,
         net,
+        wire::de_opt_decimal_lenient,
     },
 };
-use paft::domain::{AssetKind, Instrument};
 use paft::market::quote::Quote;
 
+use crate::quote::{MarketSession, QuoteDetail};
+
 // Centralized wire model for the v7 quote API
 #[derive(Deserialize)]
 pub struct V7Envelope {
@@ -35,10 +45,18 @@ pub struct V7QuoteNode {
     pub(crate) quote_type: Option<String>,
     #[serde(rename = "shortName")]
     pub(crate) short_name: Option<String>,
-    #[serde(rename = "regularMarketPrice")]
-    pub(crate) regular_market_price: Option<f64>,
-    #[serde(rename = "regularMarketPreviousClose")]
-    pub(crate) regular_market_previous_close: Option<f64>,
+    #[serde(
+        default,
+        rename = "regularMarketPrice",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) regular_market_price: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "regularMarketPreviousClose",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) regular_market_previous_close: Option<Decimal>,
     #[serde(rename = "regularMarketVolume")]
     pub(crate) regular_market_volume: Option<u64>,
     pub(crate) currency: Option<String>,
@@ -50,6 +68,210 @@ pub struct V7QuoteNode {
     pub(crate) market_cap_figure_exchange: Option<String>,
     #[serde(rename = "marketState")]
     pub(crate) market_state: Option<String>,
+
+    // --- fields only surfaced through `QuoteDetail` ---
+    #[serde(rename = "longName")]
+    pub(crate) long_name: Option<String>,
+    #[serde(rename = "exchangeTimezoneName")]
+    pub(crate) exchange_timezone_name: Option<String>,
+    #[serde(
+        default,
+        rename = "regularMarketOpen",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) regular_market_open: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "regularMarketDayHigh",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) regular_market_day_high: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "regularMarketDayLow",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) regular_market_day_low: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "regularMarketChange",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) regular_market_change: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "regularMarketChangePercent",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) regular_market_change_percent: Option<Decimal>,
+    #[serde(rename = "regularMarketTime")]
+    pub(crate) regular_market_time: Option<i64>,
+    #[serde(default, deserialize_with = "de_opt_decimal_lenient")]
+    pub(crate) bid: Option<Decimal>,
+    #[serde(default, deserialize_with = "de_opt_decimal_lenient")]
+    pub(crate) ask: Option<Decimal>,
+    // Sizes, volumes and share counts are read as `Decimal` so a float-encoded integer
+    // cannot fail the whole response.
+    #[serde(
+        default,
+        rename = "bidSize",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) bid_size: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "askSize",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) ask_size: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "fiftyTwoWeekHigh",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) fifty_two_week_high: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "fiftyTwoWeekLow",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) fifty_two_week_low: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "fiftyDayAverage",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) fifty_day_average: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "twoHundredDayAverage",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) two_hundred_day_average: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "averageDailyVolume3Month",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) average_daily_volume_3_month: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "averageDailyVolume10Day",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) average_daily_volume_10_day: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "marketCap",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) market_cap: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "sharesOutstanding",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) shares_outstanding: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "trailingPE",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) trailing_pe: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "forwardPE",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) forward_pe: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "epsTrailingTwelveMonths",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) eps_trailing_twelve_months: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "epsForward",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) eps_forward: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "priceToBook",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) price_to_book: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "bookValue",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) book_value: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "dividendRate",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) dividend_rate: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "dividendYield",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) dividend_yield: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "trailingAnnualDividendRate",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) trailing_annual_dividend_rate: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "trailingAnnualDividendYield",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) trailing_annual_dividend_yield: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "preMarketPrice",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) pre_market_price: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "preMarketChange",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) pre_market_change: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "preMarketChangePercent",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) pre_market_change_percent: Option<Decimal>,
+    #[serde(rename = "preMarketTime")]
+    pub(crate) pre_market_time: Option<i64>,
+    #[serde(
+        default,
+        rename = "postMarketPrice",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) post_market_price: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "postMarketChange",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) post_market_change: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "postMarketChangePercent",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) post_market_change_percent: Option<Decimal>,
+    #[serde(rename = "postMarketTime")]
+    pub(crate) post_market_time: Option<i64>,
 }
 
 /// Centralized function to fetch one or more quotes from the v7 API.
@@ -58,6 +280,7 @@ pub struct V7QuoteNode {
 pub async fn fetch_v7_quotes(
     client: &YfClient,
     symbols: &[&str],
+    fields: Option<&str>,
     cache_mode: CacheMode,
     retry_override: Option<&RetryConfig>,
 ) -> Result<Vec<V7QuoteNode>, YfError> {
@@ -65,6 +288,7 @@ pub async fn fetch_v7_quotes(
     async fn attempt_fetch(
         client: &YfClient,
         symbols: &[&str],
+        fields: Option<&str>,
         crumb: Option<&str>,
         cache_mode: CacheMode,
         retry_override: Option<&RetryConfig>,
@@ -73,6 +297,9 @@ pub async fn fetch_v7_quotes(
         {
             let mut qp = url.query_pairs_mut();
             qp.append_pair("symbols", &symbols.join(","));
+            if let Some(f) = fields {
+                qp.append_pair("fields", f);
+            }
             if let Some(c) = crumb {
                 qp.append_pair("crumb", c);
             }
@@ -109,7 +336,7 @@ pub async fn fetch_v7_quotes(
 
     // First attempt, without a crumb.
     let (body, url, maybe_status) =
-        attempt_fetch(client, symbols, None, cache_mode, retry_override).await?;
+        attempt_fetch(client, symbols, fields, None, cache_mode, retry_override).await?;
 
     let body_to_parse = if let Some(status_code) = maybe_status {
         // If unauthorized, get a crumb and retry.
@@ -120,8 +347,15 @@ pub async fn fetch_v7_quotes(
             })?;
 
             // Second attempt, with a crumb.
-            let (body, url, maybe_status) =
-                attempt_fetch(client, symbols, Some(&crumb), cache_mode, retry_override).await?;
+            let (body, url, maybe_status) = attempt_fetch(
+                client,
+                symbols,
+                fields,
+                Some(&crumb),
+                cache_mode,
+                retry_override,
+            )
+            .await?;
 
             if let Some(status_code) = maybe_status {
                 let url_s = url.to_string();
@@ -174,17 +408,8 @@ pub async fn fetch_v7_quotes(
                     .or_else(|| n.market.clone())
                     .or_else(|| n.market_cap_figure_exchange.clone()),
             );
-            let kind = n
-                .quote_type
-                .as_deref()
-                .and_then(|s| s.parse::<AssetKind>().ok())
-                .unwrap_or(AssetKind::Equity);
-
-            let inst = exch.map_or_else(
-                || Instrument::from_symbol(sym, kind),
-                |ex| Instrument::from_symbol_and_exchange(sym, ex, kind),
-            );
-            if let Ok(inst) = inst {
+            let kind = n.quote_type.as_deref().and_then(string_to_asset_kind);
+            if let Some(inst) = build_instrument(sym, kind, exch) {
                 client.store_instrument(sym.to_string(), inst).await;
             }
         }
@@ -193,40 +418,229 @@ pub async fn fetch_v7_quotes(
     Ok(nodes)
 }
 
+/// Default number of symbols per v7 quote request.
+pub const DEFAULT_CHUNK_SIZE: usize = 100;
+/// Default number of v7 quote requests in flight at once.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// How a large symbol list is split across v7 quote requests.
+#[derive(Debug, Clone, Copy)]
+pub struct Batching {
+    pub chunk_size: usize,
+    pub concurrency: usize,
+}
+
+impl Default for Batching {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+}
+
+/// Quote nodes in request order, plus the requested symbols Yahoo returned nothing for and
+/// the chunks whose request failed.
+pub struct BatchedNodes {
+    pub nodes: Vec<V7QuoteNode>,
+    pub missing: Vec<String>,
+    pub failed: Vec<(Vec<String>, YfError)>,
+}
+
+/// Fetches quotes for any number of symbols, `batching.chunk_size` symbols per request and
+/// at most `batching.concurrency` requests at a time.
+///
+/// `fields` is passed through as Yahoo's `fields=` parameter; `None` requests the default payload.
+///
+/// Repeated symbols are requested once. Nodes come back in the order their symbols were first
+/// requested (matched case-insensitively); nodes Yahoo returned under a symbol that was not
+/// requested are appended in response order.
+///
+/// A failed chunk does not fail the others: its symbols and error are reported in
+/// `BatchedNodes::failed` rather than `missing`. An error is returned only if every chunk fails.
+pub async fn fetch_v7_quotes_batched(
+    client: &YfClient,
+    symbols: &[&str],
+    fields: Option<&str>,
+    batching: Batching,
+    cache_mode: CacheMode,
+    retry_override: Option<&RetryConfig>,
+) -> Result<BatchedNodes, YfError> {
+    let mut unique: Vec<&str> = Vec::with_capacity(symbols.len());
+    let mut seen = HashSet::with_capacity(symbols.len());
+    for &sym in symbols {
+        if seen.insert(sym.to_ascii_uppercase()) {
+            unique.push(sym);
+        }
+    }
+
+    let chunks: Vec<&[&str]> = unique.chunks(batching.chunk_size.max(1)).collect();
+    let results: Vec<Result<Vec<V7QuoteNode>, YfError>> = stream::iter(&chunks)
+        .map(|chunk| fetch_v7_quotes(client, chunk, fields, cache_mode, retry_override))
+        .buffered(batching.concurrency.max(1))
+        .collect()
+        .await;
+
+    let mut fetched = Vec::new();
+    let mut failed: Vec<(Vec<String>, YfError)> = Vec::new();
+    for (chunk, result) in chunks.iter().zip(results) {
+        match result {
+            Ok(nodes) => fetched.extend(nodes),
+            Err(e) => failed.push((chunk.iter().map(|s| (*s).to_string()).collect(), e)),
+        }
+    }
+    if failed.len() == chunks.len()
+        && let Some((_, e)) = failed.pop()
+    {
+        return Err(e);
+    }
+    let failed_syms: HashSet<String> = failed
+        .iter()
+        .flat_map(|(syms, _)| syms.iter().map(|s| s.to_ascii_uppercase()))
+        .collect();
+
+    let mut by_symbol: HashMap<String, V7QuoteNode> = HashMap::new();
+    let mut unmatched = Vec::new();
+    for node in fetched {
+        match node.symbol.as_deref().map(str::to_ascii_uppercase) {
+            Some(key) if seen.contains(&key) && !by_symbol.contains_key(&key) => {
+                by_symbol.insert(key, node);
+            }
+            _ => unmatched.push(node),
+        }
+    }
+
+    let mut nodes = Vec::with_capacity(unique.len());
+    let mut missing = Vec::new();
+    for sym in unique {
+        let key = sym.to_ascii_uppercase();
+        match by_symbol.remove(&key) {
+            Some(node) => nodes.push(node),
+            None if failed_syms.contains(&key) => {}
+            None => missing.push(sym.to_string()),
+        }
+    }
+    nodes.extend(unmatched);
+    Ok(BatchedNodes {
+        nodes,
+        missing,
+        failed,
+    })
+}
+
 impl From<V7QuoteNode> for Quote {
     fn from(n: V7QuoteNode) -> Self {
-        let sym = n.symbol.unwrap_or_default();
-        let exchange = crate::core::conversions::string_to_exchange(
-            n.full_exchange_name
-                .or(n.exchange)
-                .or(n.market)
-                .or(n.market_cap_figure_exchange),
-        );
-        let kind = n
-            .quote_type
-            .as_deref()
-            .and_then(|s| s.parse::<AssetKind>().ok())
-            .unwrap_or(AssetKind::Equity);
-        let instrument = exchange
-            .clone()
-            .map_or_else(
-                || Instrument::from_symbol(&sym, kind),
-                |ex| Instrument::from_symbol_and_exchange(&sym, ex, kind),
+        quote_from_node(n, false)
+    }
+}
+
+/// Maps a v7 quote node to a `Quote`, optionally converting minor-unit prices to the major
+/// currency (see `YfClientBuilder::normalize_minor_units`).
+pub fn quote_from_node(n: V7QuoteNode, normalize_minor_units: bool) -> Quote {
+    let sym = n.symbol.unwrap_or_default();
+    let exchange = crate::core::conversions::string_to_exchange(
+        n.full_exchange_name
+            .or(n.exchange)
+            .or(n.market)
+            .or(n.market_cap_figure_exchange),
+    );
+    let kind = n.quote_type.as_deref().and_then(string_to_asset_kind);
+    let instrument = build_instrument(&sym, kind, exchange.clone())
+        .expect("v7 quote node had invalid/missing symbol");
+
+    Quote {
+        instrument,
+        shortname: n.short_name,
+        price: n.regular_market_price.map(|price| {
+            decimal_to_money_with_currency_str_normalized(
+                price,
+                n.currency.as_deref(),
+                normalize_minor_units,
+            )
+        }),
+        previous_close: n.regular_market_previous_close.map(|price| {
+            decimal_to_money_with_currency_str_normalized(
+                price,
+                n.currency.as_deref(),
+                normalize_minor_units,
             )
-            .expect("v7 quote node had invalid/missing symbol");
+        }),
+        day_volume: n.regular_market_volume,
+        exchange,
+        market_state: n.market_state.and_then(|s| s.parse().ok()),
+    }
+}
 
-        Self {
-            instrument,
-            shortname: n.short_name,
-            price: n
-                .regular_market_price
-                .map(|price| f64_to_money_with_currency_str(price, n.currency.as_deref())),
-            previous_close: n
-                .regular_market_previous_close
-                .map(|price| f64_to_money_with_currency_str(price, n.currency.as_deref())),
-            day_volume: n.regular_market_volume,
-            exchange,
-            market_state: n.market_state.and_then(|s| s.parse().ok()),
-        }
+/// Maps a v7 quote node to a [`QuoteDetail`], converting prices like [`quote_from_node`].
+pub fn detail_from_node(n: V7QuoteNode, normalize_minor_units: bool) -> QuoteDetail {
+    let currency = n.currency.clone();
+    let money = |v: Option<Decimal>| {
+        v.map(|d| {
+            decimal_to_money_with_currency_str_normalized(
+                d,
+                currency.as_deref(),
+                normalize_minor_units,
+            )
+        })
+    };
+    let count = |v: Option<Decimal>| v.and_then(|d| d.round().to_u64());
+    let session = |price, change, change_percent, time: Option<i64>| MarketSession {
+        price: money(price),
+        change: money(change),
+        change_percent,
+        time: time.map(i64_to_datetime),
+    };
+
+    let regular = session(
+        n.regular_market_price,
+        n.regular_market_change,
+        n.regular_market_change_percent,
+        n.regular_market_time,
+    );
+    let pre_market = session(
+        n.pre_market_price,
+        n.pre_market_change,
+        n.pre_market_change_percent,
+        n.pre_market_time,
+    );
+    let post_market = session(
+        n.post_market_price,
+        n.post_market_change,
+        n.post_market_change_percent,
+        n.post_market_time,
+    );
+
+    QuoteDetail {
+        long_name: n.long_name.clone(),
+        exchange_timezone: n.exchange_timezone_name.clone(),
+        open: money(n.regular_market_open),
+        day_high: money(n.regular_market_day_high),
+        day_low: money(n.regular_market_day_low),
+        regular,
+        pre_market,
+        post_market,
+        bid: money(n.bid),
+        ask: money(n.ask),
+        bid_size: count(n.bid_size),
+        ask_size: count(n.ask_size),
+        fifty_two_week_high: money(n.fifty_two_week_high),
+        fifty_two_week_low: money(n.fifty_two_week_low),
+        fifty_day_average: money(n.fifty_day_average),
+        two_hundred_day_average: money(n.two_hundred_day_average),
+        average_volume_3_month: count(n.average_daily_volume_3_month),
+        average_volume_10_day: count(n.average_daily_volume_10_day),
+        market_cap: n.market_cap,
+        shares_outstanding: count(n.shares_outstanding),
+        trailing_pe: n.trailing_pe,
+        forward_pe: n.forward_pe,
+        eps_trailing_twelve_months: n.eps_trailing_twelve_months,
+        eps_forward: n.eps_forward,
+        price_to_book: n.price_to_book,
+        book_value: n.book_value,
+        dividend_rate: n.dividend_rate,
+        dividend_yield: n.dividend_yield,
+        trailing_annual_dividend_rate: n.trailing_annual_dividend_rate,
+        trailing_annual_dividend_yield: n.trailing_annual_dividend_yield,
+        quote: quote_from_node(n, normalize_minor_units),
     }
 }
@@ -1,4 +1,6 @@
+use rust_decimal::Decimal;
 use serde::{Deserialize, Deserializer};
+use serde_json::Value;
 
 #[derive(Deserialize, Clone, Copy)]
 pub struct RawNum<T> {
@@ -58,8 +60,52 @@ where
     }
 }
 
+/// A `{raw}` number read through [`de_opt_decimal_lenient`], so a value `Decimal` cannot
+/// represent is `None` instead of failing the surrounding array.
+#[derive(Deserialize, Clone, Copy)]
+pub struct RawDecimal {
+    #[serde(default, deserialize_with = "de_opt_decimal_lenient")]
+    pub(crate) raw: Option<Decimal>,
+}
+
 #[derive(Deserialize, Clone, Copy)]
 pub struct RawNumU64 {
     #[serde(deserialize_with = "de_u64_from_any_number")]
     pub(crate) raw: Option<u64>,
 }
+
+/// Parses a JSON value as a `Decimal`, treating numbers it cannot represent (e.g. `1e300`) as
+/// absent rather than failing the whole payload.
+///
+/// `serde_json` reads JSON floats as `f64`, so a number is converted from the shortest string
+/// that round-trips its `f64`: `0.1` becomes exactly `0.1`, but digits beyond `f64` precision
+/// are not kept.
+fn lenient_decimal<E: serde::de::Error>(v: Value) -> Result<Option<Decimal>, E> {
+    match v {
+        Value::Null => Ok(None),
+        Value::Number(_) => Ok(Decimal::deserialize(v).ok()),
+        other => Decimal::deserialize(other).map(Some).map_err(E::custom),
+    }
+}
+
+/// Deserializes an optional `Decimal` via [`lenient_decimal`], mapping out-of-range numbers
+/// to `None`.
+pub fn de_opt_decimal_lenient<'de, D>(deserializer: D) -> Result<Option<Decimal>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    lenient_decimal(Value::deserialize(deserializer)?)
+}
+
+/// Deserializes a column of optional `Decimal`s via [`lenient_decimal`], mapping out-of-range
+/// numbers to `None`. A `null` column is empty.
+pub fn de_vec_opt_decimal_lenient<'de, D>(deserializer: D) -> Result<Vec<Option<Decimal>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<Vec<Value>>::deserialize(deserializer)?
+        .unwrap_or_default()
+        .into_iter()
+        .map(lenient_decimal)
+        .collect()
+}
@@ -1,16 +1,105 @@
-use futures::future::try_join_all;
+mod overrides;
+mod panel;
+
+pub use overrides::SymbolOverrides;
+pub use panel::{FillPolicy, Panel, PanelBuilder, PanelIndex};
+
+use std::collections::HashMap;
+
+use futures::future::Either;
+use futures::stream::{self, Stream, StreamExt};
 
 use crate::{
     core::client::{CacheMode, RetryConfig},
+    core::instrument::resolve_instrument,
     core::{Candle, HistoryResponse, Interval, Range, YfClient, YfError},
-    history::HistoryBuilder,
+    history::{
+        CandleTransform, HistoryBuilder, RepairConfig, RepairLog, TransformPipeline, ValidationMode,
+    },
+    spark::{SparkBuilder, SparkSeries},
 };
-use paft::domain::{AssetKind, Instrument};
 use paft::market::responses::download::{DownloadEntry, DownloadResponse};
-use paft::money::Money;
-use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::RoundingStrategy;
+use std::sync::Arc;
 type DateRange = (chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>);
 type MaybeDateRange = Option<DateRange>;
+type SymbolOutcome = (String, Result<(HistoryResponse, RepairLog), YfError>);
+type ProgressFn = Arc<dyn Fn(&DownloadProgress) + Send + Sync>;
+
+/// Progress of a download, reported after each symbol finishes.
+///
+/// See [`DownloadBuilder::on_progress`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// The symbol that just finished.
+    pub symbol: String,
+    /// Whether the symbol was fetched successfully.
+    pub succeeded: bool,
+    /// Symbols finished so far, successful or not.
+    pub completed: usize,
+    /// Symbols that failed so far.
+    pub failed: usize,
+    /// Symbols not finished yet.
+    pub remaining: usize,
+    /// Symbols in the download.
+    pub total: usize,
+}
+
+/// Counts finished symbols and reports them to a progress callback.
+struct ProgressTracker {
+    callback: Option<ProgressFn>,
+    total: usize,
+    completed: usize,
+    failed: usize,
+}
+
+impl ProgressTracker {
+    fn new(callback: Option<ProgressFn>, total: usize) -> Self {
+        Self {
+            callback,
+            total,
+            completed: 0,
+            failed: 0,
+        }
+    }
+
+    fn record(&mut self, symbol: &str, succeeded: bool) {
+        let Some(cb) = &self.callback else {
+            return;
+        };
+        self.completed += 1;
+        if !succeeded {
+            self.failed += 1;
+        }
+        cb(&DownloadProgress {
+            symbol: symbol.to_string(),
+            succeeded,
+            completed: self.completed,
+            failed: self.failed,
+            remaining: self.total.saturating_sub(self.completed),
+            total: self.total,
+        });
+    }
+}
+
+/// The outcome of [`DownloadBuilder::run_partial`]: every symbol either succeeded or failed.
+#[derive(Debug)]
+pub struct PartialDownload {
+    /// Entries for the symbols that were fetched successfully, in request order.
+    pub response: DownloadResponse,
+    /// The error of each symbol that could not be fetched.
+    pub failures: HashMap<String, YfError>,
+    /// The repair log of each successful symbol; empty unless repair is enabled.
+    pub repairs: HashMap<String, RepairLog>,
+}
+
+impl PartialDownload {
+    /// Returns `true` if every symbol was fetched successfully.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
 
 /// A builder for downloading historical data for multiple symbols concurrently.
 ///
@@ -30,12 +119,17 @@ pub struct DownloadBuilder {
 
     // behavior flags
     auto_adjust: bool,
-    back_adjust: bool,
     include_prepost: bool,
     include_actions: bool,
     keepna: bool,
-    rounding: bool,
-    repair: bool,
+    closes_only: bool,
+    retry_failed: bool,
+    concurrency: Option<usize>,
+    progress: Option<ProgressFn>,
+    overrides: HashMap<String, SymbolOverrides>,
+
+    // repair, validation, back-adjust, rounding and custom transforms
+    pipeline: TransformPipeline,
 
     cache_mode: CacheMode,
     retry_override: Option<RetryConfig>,
@@ -59,20 +153,21 @@ impl DownloadBuilder {
         }
     }
 
-    fn build_history_for_symbol(
+    /// Builds the request for `sym`, applying any overrides registered for it.
+    fn history_for(
         &self,
         sym: &str,
         period_dt: Option<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)>,
-        need_adjust_in_fetch: bool,
     ) -> HistoryBuilder {
         let mut hb: HistoryBuilder = HistoryBuilder::new(&self.client, sym.to_string())
             .interval(self.interval)
-            .auto_adjust(need_adjust_in_fetch)
+            .auto_adjust(self.auto_adjust)
             .prepost(self.include_prepost)
             .actions(self.include_actions)
             .keepna(self.keepna)
             .cache_mode(self.cache_mode)
             .retry_policy(self.retry_override.clone());
+        hb.pipeline = self.pipeline.clone();
 
         if let Some((start, end)) = period_dt {
             hb = hb.between(start, end);
@@ -81,97 +176,93 @@ impl DownloadBuilder {
         } else {
             hb = hb.range(Range::M6);
         }
-        hb
-    }
-
-    fn apply_back_adjust(&self, rows: &mut [Candle]) {
-        if !self.back_adjust {
-            return;
-        }
-        for c in rows.iter_mut() {
-            if let Some(rc) = c.close_unadj.as_ref()
-                && rc.amount().to_f64().is_some_and(f64::is_finite)
-            {
-                c.close = rc.clone();
-            }
+        match self.overrides.get(sym) {
+            Some(o) => o.apply(hb),
+            None => hb,
         }
     }
 
-    fn apply_rounding_if_enabled(&self, rows: &mut [Candle]) {
-        if !self.rounding {
-            return;
-        }
-        for c in rows {
-            if c.open.amount().to_f64().is_some_and(f64::is_finite) {
-                c.open = Money::new(
-                    rust_decimal::Decimal::from_f64(round2(
-                        c.open.amount().to_f64().unwrap_or(0.0),
-                    ))
-                    .unwrap_or_default(),
-                    c.open.currency().clone(),
-                )
-                .expect("currency metadata available");
-            }
-            if c.high.amount().to_f64().is_some_and(f64::is_finite) {
-                c.high = Money::new(
-                    rust_decimal::Decimal::from_f64(round2(
-                        c.high.amount().to_f64().unwrap_or(0.0),
-                    ))
-                    .unwrap_or_default(),
-                    c.high.currency().clone(),
-                )
-                .expect("currency metadata available");
-            }
-            if c.low.amount().to_f64().is_some_and(f64::is_finite) {
-                c.low = Money::new(
-                    rust_decimal::Decimal::from_f64(round2(c.low.amount().to_f64().unwrap_or(0.0)))
-                        .unwrap_or_default(),
-                    c.low.currency().clone(),
-                )
-                .expect("currency metadata available");
-            }
-            if c.close.amount().to_f64().is_some_and(f64::is_finite) {
-                c.close = Money::new(
-                    rust_decimal::Decimal::from_f64(round2(
-                        c.close.amount().to_f64().unwrap_or(0.0),
-                    ))
-                    .unwrap_or_default(),
-                    c.close.currency().clone(),
-                )
-                .expect("currency metadata available");
-            }
+    async fn fetch_closes_via_spark(
+        &self,
+        period_dt: MaybeDateRange,
+    ) -> Result<Vec<SymbolOutcome>, YfError> {
+        let mut sb = SparkBuilder::new(&self.client)
+            .symbols(self.symbols.iter().cloned())
+            .interval(self.interval)
+            .cache_mode(self.cache_mode)
+            .retry_policy(self.retry_override.clone());
+        if let Some(limit) = self.concurrency {
+            sb = sb.concurrency(limit);
         }
-    }
-
-    fn maybe_repair(&self, rows: &mut [Candle]) {
-        if self.repair {
-            repair_scale_outliers(rows);
+        sb = match (period_dt, self.range) {
+            (Some((start, end)), _) => sb.between(start, end),
+            (None, Some(r)) => sb.range(r),
+            (None, None) => sb.range(Range::M6),
+        };
+
+        let mut by_symbol: HashMap<String, SparkSeries> = sb
+            .fetch()
+            .await?
+            .into_iter()
+            .map(|s| (s.symbol.clone(), s))
+            .collect();
+
+        // Spark series have nothing to repair.
+        let pipeline = TransformPipeline {
+            repair: false,
+            ..self.pipeline.clone()
+        };
+        let mut out = Vec::with_capacity(self.symbols.len());
+        for sym in &self.symbols {
+            let Some(series) = by_symbol.remove(sym) else {
+                // Spark silently leaves out symbols it has no data for.
+                let mut url = self.client.base_spark().clone();
+                url.query_pairs_mut().append_pair("symbols", sym);
+                out.push((
+                    sym.clone(),
+                    Err(YfError::NotFound {
+                        url: url.to_string(),
+                    }),
+                ));
+                continue;
+            };
+            let candles = series
+                .closes
+                .into_iter()
+                .map(|p| Candle {
+                    ts: p.ts,
+                    open: p.close.clone(),
+                    high: p.close.clone(),
+                    low: p.close.clone(),
+                    close: p.close,
+                    close_unadj: None,
+                    volume: None,
+                })
+                .collect();
+            let mut history = HistoryResponse {
+                candles,
+                actions: Vec::new(),
+                adjusted: false,
+                meta: None,
+            };
+            let outcome = pipeline
+                .run(&mut history, &[])
+                .map(|(log, _)| (history, log));
+            out.push((sym.clone(), outcome));
         }
+        Ok(out)
     }
 
     async fn process_joined_results(
         &self,
         joined: Vec<(String, HistoryResponse)>,
-        _need_adjust_in_fetch: bool,
     ) -> DownloadResponse {
         let mut entries: Vec<DownloadEntry> = Vec::with_capacity(joined.len());
-        for (sym, mut resp) in joined {
-            // apply transforms to candles
-            self.apply_back_adjust(&mut resp.candles);
-            self.maybe_repair(&mut resp.candles);
-            self.apply_rounding_if_enabled(&mut resp.candles);
-
+        for (sym, resp) in joined {
             // get instrument from cache or fallback
-            let instrument = if let Some(inst) = self.client.cached_instrument(&sym).await {
-                inst
-            } else {
-                let kind = AssetKind::Equity;
-                let inst = Instrument::from_symbol(&sym, kind).expect("valid symbol");
-                self.client
-                    .store_instrument(sym.clone(), inst.clone())
-                    .await;
-                inst
-            };
+            let instrument = resolve_instrument(&self.client, &sym, None, None)
+                .await
+                .expect("valid symbol");
 
             entries.push(DownloadEntry {
                 instrument,
@@ -191,12 +282,15 @@ impl DownloadBuilder {
             period: None,
             interval: Interval::D1,
             auto_adjust: true,
-            back_adjust: false,
             include_prepost: false,
             include_actions: true,
             keepna: false,
-            rounding: false,
-            repair: false,
+            closes_only: false,
+            retry_failed: false,
+            concurrency: None,
+            progress: None,
+            overrides: HashMap::new(),
+            pipeline: TransformPipeline::default(),
             cache_mode: CacheMode::Use,
             retry_override: None,
         }
@@ -274,7 +368,7 @@ impl DownloadBuilder {
     /// raw, unadjusted close. This forces an internal adjustment even if `auto_adjust` is false.
     #[must_use]
     pub const fn back_adjust(mut self, yes: bool) -> Self {
-        self.back_adjust = yes;
+        self.pipeline.back_adjust = yes;
         self
     }
 
@@ -299,144 +393,321 @@ impl DownloadBuilder {
         self
     }
 
-    /// Sets whether to round prices to 2 decimal places. (Default: `false`)
+    /// Sets whether to round prices. (Default: `false`)
+    ///
+    /// Prices are rounded in `Decimal` to [`rounding_places`](Self::rounding_places) using
+    /// [`rounding_strategy`](Self::rounding_strategy).
     #[must_use]
     pub const fn rounding(mut self, yes: bool) -> Self {
-        self.rounding = yes;
+        self.pipeline.rounding = yes;
         self
     }
 
-    /// Sets whether to attempt to repair obvious price outliers (e.g., 100x errors). (Default: `false`)
+    /// Sets the number of decimal places used when [`rounding`](Self::rounding) is enabled. (Default: `2`)
     #[must_use]
-    pub const fn repair(mut self, yes: bool) -> Self {
-        self.repair = yes;
+    pub const fn rounding_places(mut self, places: u32) -> Self {
+        self.pipeline.rounding_spec.places = places;
         self
     }
 
-    /// Executes the download by fetching data for all specified symbols concurrently.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if any of the underlying history requests fail.
-    pub async fn run(self) -> Result<DownloadResponse, YfError> {
-        if self.symbols.is_empty() {
-            return Err(YfError::InvalidParams("no symbols specified".into()));
-        }
+    /// Sets how midpoints are resolved when [`rounding`](Self::rounding) is enabled.
+    /// (Default: [`RoundingStrategy::MidpointAwayFromZero`])
+    #[must_use]
+    pub const fn rounding_strategy(mut self, strategy: RoundingStrategy) -> Self {
+        self.pipeline.rounding_spec.strategy = strategy;
+        self
+    }
 
-        let need_adjust_in_fetch = self.auto_adjust || self.back_adjust;
-        let period_dt = self.precompute_period_dt()?;
+    /// Sets whether to detect and fix common price defects in each symbol's series. (Default: `false`)
+    ///
+    /// Repairs run on the fetched data before back-adjustment and rounding. See
+    /// [`repair_history`](crate::history::repair_history) for the repairs performed, and
+    /// [`run_with_repairs`](Self::run_with_repairs) to see what was changed.
+    #[must_use]
+    pub const fn repair(mut self, yes: bool) -> Self {
+        self.pipeline.repair = yes;
+        self
+    }
 
-        let futures = self.symbols.iter().map(|sym| {
-            let sym = sym.clone();
-            let hb = self.build_history_for_symbol(&sym, period_dt, need_adjust_in_fetch);
+    /// Sets the thresholds used when [`repair`](Self::repair) is enabled, such as whether stray
+    /// zero-volume prints are removed or only flagged. (Default: [`RepairConfig::default`])
+    #[must_use]
+    pub const fn repair_config(mut self, cfg: RepairConfig) -> Self {
+        self.pipeline.repair_config = cfg;
+        self
+    }
 
-            async move {
-                let full: HistoryResponse = hb.fetch_full().await?;
-                Ok::<(String, HistoryResponse), YfError>((sym, full))
-            }
-        });
+    /// Sets how bars that fail OHLCV integrity checks are handled for each symbol.
+    /// (Default: [`ValidationMode::Off`])
+    ///
+    /// See [`HistoryBuilder::validation`]. With [`ValidationMode::Error`], the whole download
+    /// fails if any symbol's series fails validation.
+    #[must_use]
+    pub const fn validation(mut self, mode: ValidationMode) -> Self {
+        self.pipeline.validation = mode;
+        self
+    }
 
-        let joined: Vec<(String, HistoryResponse)> = try_join_all(futures).await?;
-        Ok(self
-            .process_joined_results(joined, need_adjust_in_fetch)
-            .await)
+    /// Appends a custom transform to run on each symbol's series.
+    ///
+    /// See [`HistoryBuilder::transform`].
+    #[must_use]
+    pub fn transform(mut self, t: impl CandleTransform + 'static) -> Self {
+        self.pipeline.custom.push(Arc::new(t));
+        self
     }
-}
 
-/* ---------------- internal helpers ---------------- */
+    /// Sets whether to fetch only close prices through the spark endpoint. (Default: `false`)
+    ///
+    /// Spark returns closes for many symbols per request, which is far cheaper than one chart
+    /// call per symbol. Each candle's open, high and low are set to its close and volume is
+    /// `None`. The closes are split-adjusted only, so the histories are reported as not
+    /// adjusted; `auto_adjust`, `back_adjust`, `prepost`, `actions`, `keepna` and `repair` do
+    /// not apply. Symbols Yahoo returns no data for fail with [`YfError::NotFound`].
+    #[must_use]
+    pub const fn closes_only(mut self, yes: bool) -> Self {
+        self.closes_only = yes;
+        self
+    }
 
-fn round2(x: f64) -> f64 {
-    (x * 100.0).round() / 100.0
-}
+    /// Replaces the builder defaults for one symbol. (Default: none)
+    ///
+    /// Unset fields in `overrides` fall back to the builder's configuration, and calling this
+    /// again for the same symbol replaces its previous overrides. Overrides for symbols not in
+    /// the download are ignored, and none apply with [`closes_only`](Self::closes_only).
+    #[must_use]
+    pub fn override_symbol(mut self, sym: impl Into<String>, overrides: SymbolOverrides) -> Self {
+        self.overrides.insert(sym.into(), overrides);
+        self
+    }
 
-/// Very lightweight "repair" pass:
-/// If a bar's close is ~100× the average of its neighbors (or ~1/100),
-/// scale that entire bar's OHLC accordingly. Volumes are left unchanged.
-fn repair_scale_outliers(rows: &mut [Candle]) {
-    if rows.len() < 3 {
-        return;
+    /// Limits how many chart requests run at the same time. (Default: unlimited)
+    ///
+    /// Large universes otherwise fire every request at once and tend to trip Yahoo's rate
+    /// limiting. A limit of `0` is treated as `1`. With [`closes_only`](Self::closes_only) it
+    /// limits how many spark batches run at once instead.
+    #[must_use]
+    pub const fn concurrency(mut self, limit: usize) -> Self {
+        self.concurrency = Some(limit);
+        self
     }
 
-    for i in 1..rows.len() - 1 {
-        // Split rows at i, so left[..i] and right[i..] don't overlap.
-        let (left, right) = rows.split_at_mut(i);
+    /// Sets a callback invoked each time a symbol finishes, successfully or not.
+    ///
+    /// The callback receives running completed, failed and remaining counts. It runs on the
+    /// task driving the download, so it should be quick; forward to a channel for heavier
+    /// work. Transient failures re-attempted by [`retry_failed`](Self::retry_failed) are not
+    /// reported again.
+    #[must_use]
+    pub fn on_progress<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&DownloadProgress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(f));
+        self
+    }
 
-        // prev is in the left side (immutable is fine)
-        let prev = &left[i - 1];
+    /// Sets whether [`run_partial`](Self::run_partial) retries transient failures once more
+    /// after every symbol has been attempted. (Default: `false`)
+    ///
+    /// A failure is transient if [`YfError::is_transient`] says so, e.g. rate limiting or a 5xx
+    /// response that outlasted the client's own retries. Does not apply with
+    /// [`closes_only`](Self::closes_only).
+    #[must_use]
+    pub const fn retry_failed(mut self, yes: bool) -> Self {
+        self.retry_failed = yes;
+        self
+    }
 
-        // Now split the right side so we can mutably borrow the “current” bar
-        // and (immutably) the remainder where “next” lives, without overlap.
-        let (cur, rem) = right.split_first_mut().expect("right has at least 1");
-        let next = &rem[0]; // safe because len >= 2 overall ⇒ rem has at least one
+    /// Executes the download by fetching data for all specified symbols concurrently.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the underlying history requests fail.
+    pub async fn run(self) -> Result<DownloadResponse, YfError> {
+        let (resp, _logs) = self.run_with_repairs().await?;
+        Ok(resp)
+    }
 
-        let p = &prev.close;
-        let n = &next.close;
-        let c = &cur.close;
+    /// Executes the download and also returns the repair log of each symbol.
+    ///
+    /// The logs are keyed by symbol and are empty unless [`repair`](Self::repair) is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the underlying history requests fail.
+    pub async fn run_with_repairs(
+        self,
+    ) -> Result<(DownloadResponse, HashMap<String, RepairLog>), YfError> {
+        let outcomes = self.fetch_outcomes().await?;
+
+        let mut logs: HashMap<String, RepairLog> = HashMap::with_capacity(outcomes.len());
+        let mut joined: Vec<(String, HistoryResponse)> = Vec::with_capacity(outcomes.len());
+        for (sym, outcome) in outcomes {
+            let (full, log) = outcome?;
+            logs.insert(sym.clone(), log);
+            joined.push((sym, full));
+        }
+        let resp = self.process_joined_results(joined).await;
+        Ok((resp, logs))
+    }
 
-        if !(p.amount().to_f64().is_some_and(f64::is_finite)
-            && n.amount().to_f64().is_some_and(f64::is_finite)
-            && c.amount().to_f64().is_some_and(f64::is_finite))
-        {
-            continue;
+    /// Executes the download without letting one symbol fail the others.
+    ///
+    /// Successful symbols are returned in [`PartialDownload::response`] and failed ones in
+    /// [`PartialDownload::failures`]. With [`retry_failed`](Self::retry_failed), transient
+    /// failures are attempted once more at the end. With [`closes_only`](Self::closes_only),
+    /// a failed spark request still fails the whole call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no symbols are specified, the period is invalid, or the spark
+    /// request fails.
+    pub async fn run_partial(self) -> Result<PartialDownload, YfError> {
+        let mut outcomes = self.fetch_outcomes().await?;
+
+        if self.retry_failed && !self.closes_only {
+            let retry: Vec<String> = outcomes
+                .iter()
+                .filter(|(_, o)| o.as_ref().is_err_and(YfError::is_transient))
+                .map(|(sym, _)| sym.clone())
+                .collect();
+            if !retry.is_empty() {
+                let period_dt = self.precompute_period_dt()?;
+                let mut retried: HashMap<String, _> = self
+                    .fetch_symbols(retry, period_dt, None)
+                    .await
+                    .into_iter()
+                    .collect();
+                for (sym, outcome) in &mut outcomes {
+                    if let Some(again) = retried.remove(sym.as_str()) {
+                        *outcome = again;
+                    }
+                }
+            }
         }
 
-        let p_val = p.amount().to_f64().unwrap_or(0.0);
-        let n_val = n.amount().to_f64().unwrap_or(0.0);
-        let c_val = c.amount().to_f64().unwrap_or(0.0);
+        let mut failures = HashMap::new();
+        let mut repairs = HashMap::new();
+        let mut joined = Vec::with_capacity(outcomes.len());
+        for (sym, outcome) in outcomes {
+            match outcome {
+                Ok((full, log)) => {
+                    repairs.insert(sym.clone(), log);
+                    joined.push((sym, full));
+                }
+                Err(e) => {
+                    failures.insert(sym, e);
+                }
+            }
+        }
+        let response = self.process_joined_results(joined).await;
+        Ok(PartialDownload {
+            response,
+            failures,
+            repairs,
+        })
+    }
 
-        let baseline = f64::midpoint(p_val, n_val);
-        if baseline <= 0.0 {
-            continue;
+    /// Executes the download and yields each symbol's result as soon as it finishes.
+    ///
+    /// Results arrive in completion order, at most [`concurrency`](Self::concurrency) requests
+    /// at a time, and [`on_progress`](Self::on_progress) is called as each one is yielded.
+    /// With [`closes_only`](Self::closes_only) the spark request is made up front and its
+    /// results are yielded in request order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no symbols are specified, the period is invalid, or the spark
+    /// request fails.
+    pub async fn stream(
+        self,
+    ) -> Result<impl Stream<Item = (String, Result<HistoryResponse, YfError>)>, YfError> {
+        if self.symbols.is_empty() {
+            return Err(YfError::InvalidParams("no symbols specified".into()));
         }
+        let period_dt = self.precompute_period_dt()?;
 
-        let ratio = c_val / baseline;
+        let results = if self.closes_only {
+            let outcomes = self.fetch_closes_via_spark(period_dt).await?;
+            let mut tracker = ProgressTracker::new(self.progress.clone(), outcomes.len());
+            Either::Left(stream::iter(outcomes).inspect(move |(sym, outcome)| {
+                tracker.record(sym, outcome.is_ok());
+            }))
+        } else {
+            Either::Right(
+                Self::outcome_stream(
+                    self.requests(&self.symbols, period_dt),
+                    self.concurrency,
+                    self.progress.clone(),
+                )
+                .map(|(_, outcome)| outcome),
+            )
+        };
+        Ok(results.map(|(sym, outcome)| (sym, outcome.map(|(full, _log)| full))))
+    }
 
-        // ~100× high
-        if ratio > 50.0 && ratio < 200.0 {
-            let scale = if (80.0..125.0).contains(&ratio) {
-                0.01
-            } else {
-                1.0 / ratio
-            };
-            scale_row_prices(cur, scale);
-            continue;
+    /// Fetches every symbol, keeping each symbol's result separate.
+    async fn fetch_outcomes(&self) -> Result<Vec<SymbolOutcome>, YfError> {
+        if self.symbols.is_empty() {
+            return Err(YfError::InvalidParams("no symbols specified".into()));
         }
 
-        // ~100× low
-        if ratio > 0.0 && ratio < 0.02 {
-            let scale = if (0.008..0.0125).contains(&ratio) {
-                100.0
-            } else {
-                1.0 / ratio
-            };
-            scale_row_prices(cur, scale);
+        let period_dt = self.precompute_period_dt()?;
+        if self.closes_only {
+            let outcomes = self.fetch_closes_via_spark(period_dt).await?;
+            let mut tracker = ProgressTracker::new(self.progress.clone(), outcomes.len());
+            for (sym, outcome) in &outcomes {
+                tracker.record(sym, outcome.is_ok());
+            }
+            return Ok(outcomes);
         }
+        Ok(self
+            .fetch_symbols(self.symbols.clone(), period_dt, self.progress.clone())
+            .await)
+    }
+
+    /// Fetches `symbols` and returns their outcomes in request order.
+    async fn fetch_symbols(
+        &self,
+        symbols: Vec<String>,
+        period_dt: MaybeDateRange,
+        progress: Option<ProgressFn>,
+    ) -> Vec<SymbolOutcome> {
+        let mut indexed: Vec<(usize, SymbolOutcome)> = Self::outcome_stream(
+            self.requests(&symbols, period_dt),
+            self.concurrency,
+            progress,
+        )
+        .collect()
+        .await;
+        indexed.sort_by_key(|(i, _)| *i);
+        indexed.into_iter().map(|(_, outcome)| outcome).collect()
+    }
+
+    fn requests(&self, symbols: &[String], period_dt: MaybeDateRange) -> Vec<HistoryBuilder> {
+        symbols
+            .iter()
+            .map(|sym| self.history_for(sym, period_dt))
+            .collect()
     }
-}
 
-fn scale_row_prices(c: &mut Candle, scale: f64) {
-    if c.open.amount().to_f64().is_some_and(f64::is_finite) {
-        c.open = c
-            .open
-            .try_mul(rust_decimal::Decimal::from_f64_retain(scale).unwrap_or_default())
-            .expect("currency metadata available");
-    }
-    if c.high.amount().to_f64().is_some_and(f64::is_finite) {
-        c.high = c
-            .high
-            .try_mul(rust_decimal::Decimal::from_f64_retain(scale).unwrap_or_default())
-            .expect("currency metadata available");
-    }
-    if c.low.amount().to_f64().is_some_and(f64::is_finite) {
-        c.low = c
-            .low
-            .try_mul(rust_decimal::Decimal::from_f64_retain(scale).unwrap_or_default())
-            .expect("currency metadata available");
-    }
-    if c.close.amount().to_f64().is_some_and(f64::is_finite) {
-        c.close = c
-            .close
-            .try_mul(rust_decimal::Decimal::from_f64_retain(scale).unwrap_or_default())
-            .expect("currency metadata available");
+    /// Runs `requests` with at most `limit` in flight, yielding each outcome with its index in
+    /// `requests` as it completes.
+    fn outcome_stream(
+        requests: Vec<HistoryBuilder>,
+        limit: Option<usize>,
+        progress: Option<ProgressFn>,
+    ) -> impl Stream<Item = (usize, SymbolOutcome)> {
+        let limit = limit.unwrap_or(requests.len()).max(1);
+        let mut tracker = ProgressTracker::new(progress, requests.len());
+        stream::iter(requests.into_iter().enumerate())
+            .map(|(i, hb)| async move {
+                let sym = hb.symbol.clone();
+                let outcome = hb.fetch_full_with_repairs().await;
+                (i, (sym, outcome))
+            })
+            .buffer_unordered(limit)
+            .inspect(move |(_, (sym, outcome))| tracker.record(sym, outcome.is_ok()))
     }
 }
@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+
+use crate::core::{Interval, Range};
+use crate::history::HistoryBuilder;
+
+/// Settings that replace the [`DownloadBuilder`](crate::DownloadBuilder) defaults for one
+/// symbol.
+///
+/// Anything left unset falls back to the builder's configuration.
+///
+/// ```
+/// use yfinance_rs::download::SymbolOverrides;
+/// use yfinance_rs::Range;
+///
+/// let recent_listing = SymbolOverrides::new().range(Range::Max);
+/// let crypto = SymbolOverrides::new().prepost(false);
+/// let fund = SymbolOverrides::new().adjust_capital_gains(true);
+/// # let _ = (recent_listing, crypto, fund);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SymbolOverrides {
+    range: Option<Range>,
+    period: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    interval: Option<Interval>,
+    auto_adjust: Option<bool>,
+    include_prepost: Option<bool>,
+    include_actions: Option<bool>,
+    adjust_capital_gains: Option<bool>,
+}
+
+impl SymbolOverrides {
+    /// Creates an empty set of overrides.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fetches this symbol over a relative range, replacing any builder range or period.
+    #[must_use]
+    pub const fn range(mut self, range: Range) -> Self {
+        self.period = None;
+        self.range = Some(range);
+        self
+    }
+
+    /// Fetches this symbol over an absolute period, replacing any builder range or period.
+    #[must_use]
+    pub const fn between(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.range = None;
+        self.period = Some((start, end));
+        self
+    }
+
+    /// Sets the candle interval for this symbol.
+    #[must_use]
+    pub const fn interval(mut self, interval: Interval) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Sets whether this symbol's prices are adjusted for splits and dividends.
+    #[must_use]
+    pub const fn auto_adjust(mut self, yes: bool) -> Self {
+        self.auto_adjust = Some(yes);
+        self
+    }
+
+    /// Sets whether pre-market and post-market data is included for this symbol.
+    #[must_use]
+    pub const fn prepost(mut self, yes: bool) -> Self {
+        self.include_prepost = Some(yes);
+        self
+    }
+
+    /// Sets whether corporate actions are included for this symbol.
+    #[must_use]
+    pub const fn actions(mut self, yes: bool) -> Self {
+        self.include_actions = Some(yes);
+        self
+    }
+
+    /// Sets whether capital-gain distributions are folded into this symbol's adjustment.
+    ///
+    /// See [`HistoryBuilder::adjust_capital_gains`].
+    #[must_use]
+    pub const fn adjust_capital_gains(mut self, yes: bool) -> Self {
+        self.adjust_capital_gains = Some(yes);
+        self
+    }
+
+    pub(crate) fn apply(&self, mut hb: HistoryBuilder) -> HistoryBuilder {
+        if let Some((start, end)) = self.period {
+            hb = hb.between(start, end);
+        } else if let Some(r) = self.range {
+            hb = hb.range(r);
+        }
+        if let Some(i) = self.interval {
+            hb = hb.interval(i);
+        }
+        if let Some(yes) = self.auto_adjust {
+            hb = hb.auto_adjust(yes);
+        }
+        if let Some(yes) = self.include_prepost {
+            hb = hb.prepost(yes);
+        }
+        if let Some(yes) = self.include_actions {
+            hb = hb.actions(yes);
+        }
+        if let Some(yes) = self.adjust_capital_gains {
+            hb = hb.adjust_capital_gains(yes);
+        }
+        hb
+    }
+}
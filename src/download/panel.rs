@@ -0,0 +1,289 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, NaiveTime, Utc};
+use paft::domain::IdentifierScheme;
+use paft::market::responses::download::DownloadResponse;
+use paft::market::responses::history::{Candle, HistoryResponse};
+use paft::money::Money;
+
+use crate::history::{PriceField, local_dates};
+
+/// Which timestamps a [`Panel`] is indexed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PanelIndex {
+    /// Every timestamp any symbol has a bar for. (Default)
+    #[default]
+    Union,
+    /// Only timestamps every symbol has a bar for.
+    Intersection,
+}
+
+/// How a [`Panel`] fills timestamps a symbol has no bar for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillPolicy {
+    /// Leave gaps empty. (Default)
+    #[default]
+    None,
+    /// Fill each gap with a flat bar at the last close.
+    ForwardFill,
+    /// Like [`ForwardFill`](Self::ForwardFill), but fill at most this many consecutive gaps.
+    ForwardFillLimit(usize),
+}
+
+/// Histories of several symbols aligned on one timestamp index, built by [`PanelBuilder`].
+///
+/// Each symbol has one slot per index entry, `None` where it has no bar (and none was filled).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Panel {
+    index: Vec<DateTime<Utc>>,
+    symbols: Vec<String>,
+    bars: Vec<Vec<Option<Candle>>>,
+    filled: Vec<Vec<bool>>,
+}
+
+impl Panel {
+    /// The shared timestamp index, in ascending order.
+    #[must_use]
+    pub fn index(&self) -> &[DateTime<Utc>] {
+        &self.index
+    }
+
+    /// The symbols in the panel, in download order.
+    #[must_use]
+    pub fn symbols(&self) -> &[String] {
+        &self.symbols
+    }
+
+    /// Number of index entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns `true` if the index is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// The aligned bars of `symbol`, or `None` if it is not in the panel.
+    #[must_use]
+    pub fn candles(&self, symbol: &str) -> Option<&[Option<Candle>]> {
+        self.position(symbol).map(|i| self.bars[i].as_slice())
+    }
+
+    /// One price column of `symbol`, or `None` if it is not in the panel.
+    #[must_use]
+    pub fn prices(&self, symbol: &str, field: PriceField) -> Option<Vec<Option<Money>>> {
+        let pick = |c: &Candle| match field {
+            PriceField::Open => c.open.clone(),
+            PriceField::High => c.high.clone(),
+            PriceField::Low => c.low.clone(),
+            PriceField::Close => c.close.clone(),
+        };
+        self.candles(symbol)
+            .map(|bars| bars.iter().map(|b| b.as_ref().map(pick)).collect())
+    }
+
+    /// The volume column of `symbol`, or `None` if it is not in the panel.
+    #[must_use]
+    pub fn volumes(&self, symbol: &str) -> Option<Vec<Option<u64>>> {
+        self.candles(symbol).map(|bars| {
+            bars.iter()
+                .map(|b| b.as_ref().and_then(|c| c.volume))
+                .collect()
+        })
+    }
+
+    /// Returns `true` if the bar of `symbol` at index position `row` was filled rather than
+    /// fetched.
+    #[must_use]
+    pub fn is_filled(&self, symbol: &str, row: usize) -> bool {
+        self.position(symbol)
+            .and_then(|i| self.filled[i].get(row).copied())
+            .unwrap_or(false)
+    }
+
+    fn position(&self, symbol: &str) -> Option<usize> {
+        self.symbols.iter().position(|s| s == symbol)
+    }
+}
+
+/// Aligns the histories of a [`DownloadResponse`] into a [`Panel`].
+///
+/// Bars are matched on their exact timestamp. Exchanges in different timezones stamp daily
+/// bars at different times of day, so for daily and longer intervals use
+/// [`align_on_dates`](Self::align_on_dates) to match bars by trading date instead.
+///
+/// Filled bars are flat: open, high, low and close are the last fetched close, and volume is
+/// zero. Gaps before a symbol's first bar are never filled.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), yfinance_rs::YfError> {
+/// use yfinance_rs::download::{FillPolicy, PanelBuilder};
+/// use yfinance_rs::history::PriceField;
+/// use yfinance_rs::{DownloadBuilder, YfClient};
+///
+/// let client = YfClient::default();
+/// let dl = DownloadBuilder::new(&client).symbols(["SPY", "EWG"]).run().await?;
+/// let panel = PanelBuilder::new(&dl)
+///     .align_on_dates(true)
+///     .fill(FillPolicy::ForwardFillLimit(3))
+///     .build();
+/// let closes = panel.prices("EWG", PriceField::Close);
+/// # Ok(())
+/// # }
+/// ```
+pub struct PanelBuilder<'a> {
+    download: &'a DownloadResponse,
+    index: PanelIndex,
+    fill: FillPolicy,
+    align_on_dates: bool,
+}
+
+impl<'a> PanelBuilder<'a> {
+    /// Creates a builder over the histories in `download`.
+    #[must_use]
+    pub const fn new(download: &'a DownloadResponse) -> Self {
+        Self {
+            download,
+            index: PanelIndex::Union,
+            fill: FillPolicy::None,
+            align_on_dates: false,
+        }
+    }
+
+    /// Sets which timestamps the panel is indexed on. (Default: [`PanelIndex::Union`])
+    #[must_use]
+    pub const fn index(mut self, index: PanelIndex) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// Sets how gaps are filled. (Default: [`FillPolicy::None`])
+    #[must_use]
+    pub const fn fill(mut self, fill: FillPolicy) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    /// Sets whether bars are matched by exchange-local trading date instead of exact
+    /// timestamp. (Default: `false`)
+    ///
+    /// Each index entry is then midnight UTC of the trading date, and the candles keep their
+    /// original timestamps.
+    #[must_use]
+    pub const fn align_on_dates(mut self, yes: bool) -> Self {
+        self.align_on_dates = yes;
+        self
+    }
+
+    /// Builds the panel.
+    ///
+    /// Entries whose instrument has no symbol are skipped, and only the first entry of a
+    /// repeated symbol is used. Within a symbol, the first bar of a repeated key wins.
+    #[must_use]
+    pub fn build(self) -> Panel {
+        let mut symbols: Vec<String> = Vec::new();
+        let mut keyed: Vec<HashMap<DateTime<Utc>, &Candle>> = Vec::new();
+        for entry in &self.download.entries {
+            let IdentifierScheme::Security(sec) = entry.instrument.id() else {
+                continue;
+            };
+            let symbol = sec.symbol.as_str().to_string();
+            if symbols.contains(&symbol) {
+                continue;
+            }
+            symbols.push(symbol);
+            keyed.push(self.keyed_bars(&entry.history));
+        }
+
+        let mut counts: BTreeMap<DateTime<Utc>, usize> = BTreeMap::new();
+        for bars in &keyed {
+            for key in bars.keys() {
+                *counts.entry(*key).or_default() += 1;
+            }
+        }
+        let index: Vec<DateTime<Utc>> = counts
+            .into_iter()
+            .filter(|(_, n)| self.index == PanelIndex::Union || *n == keyed.len())
+            .map(|(key, _)| key)
+            .collect();
+
+        let limit = match self.fill {
+            FillPolicy::None => 0,
+            FillPolicy::ForwardFill => usize::MAX,
+            FillPolicy::ForwardFillLimit(n) => n,
+        };
+        let mut bars = Vec::with_capacity(keyed.len());
+        let mut filled = Vec::with_capacity(keyed.len());
+        for by_key in &keyed {
+            let (column, flags) = align(&index, by_key, limit);
+            bars.push(column);
+            filled.push(flags);
+        }
+
+        Panel {
+            index,
+            symbols,
+            bars,
+            filled,
+        }
+    }
+
+    fn keyed_bars<'h>(&self, history: &'h HistoryResponse) -> HashMap<DateTime<Utc>, &'h Candle> {
+        let local_date = local_dates(history);
+        let mut out = HashMap::with_capacity(history.candles.len());
+        for c in &history.candles {
+            let key = if self.align_on_dates {
+                local_date(c.ts).and_time(NaiveTime::MIN).and_utc()
+            } else {
+                c.ts
+            };
+            out.entry(key).or_insert(c);
+        }
+        out
+    }
+}
+
+/// Lays out one symbol's bars on `index`, forward-filling at most `limit` consecutive gaps.
+fn align(
+    index: &[DateTime<Utc>],
+    by_key: &HashMap<DateTime<Utc>, &Candle>,
+    limit: usize,
+) -> (Vec<Option<Candle>>, Vec<bool>) {
+    let mut column = Vec::with_capacity(index.len());
+    let mut flags = Vec::with_capacity(index.len());
+    let mut last: Option<&Candle> = None;
+    let mut run = 0;
+
+    for key in index {
+        if let Some(c) = by_key.get(key) {
+            column.push(Some((*c).clone()));
+            flags.push(false);
+            last = Some(*c);
+            run = 0;
+            continue;
+        }
+        match last {
+            Some(prev) if run < limit => {
+                column.push(Some(Candle {
+                    ts: *key,
+                    open: prev.close.clone(),
+                    high: prev.close.clone(),
+                    low: prev.close.clone(),
+                    close: prev.close.clone(),
+                    close_unadj: prev.close_unadj.clone(),
+                    volume: Some(0),
+                }));
+                flags.push(true);
+                run += 1;
+            }
+            _ => {
+                column.push(None);
+                flags.push(false);
+            }
+        }
+    }
+    (column, flags)
+}
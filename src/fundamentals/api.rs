@@ -5,12 +5,16 @@ use crate::{
     core::{
         YfClient, YfError,
         client::{CacheMode, RetryConfig},
-        conversions::{f64_to_money_with_currency, i64_to_datetime, string_to_period},
+        conversions::{
+            decimal_to_money_with_currency, f64_to_money_with_currency, i64_to_datetime,
+            string_to_period,
+        },
     },
     fundamentals::wire::{TimeseriesData, TimeseriesEnvelope},
 };
 use paft::fundamentals::profile::ShareCount;
 use paft::money::Currency;
+use rust_decimal::Decimal;
 
 use super::fetch::fetch_modules;
 use super::{
@@ -125,12 +129,12 @@ pub(super) async fn income_statement(
 ) -> Result<Vec<IncomeStatementRow>, YfError> {
     use serde::Deserialize;
 
-    use crate::core::wire::RawNum;
+    use crate::core::wire::RawDecimal;
 
     #[derive(Deserialize)]
-    struct TimeseriesValueF64 {
+    struct TimeseriesValueDecimal {
         #[serde(rename = "reportedValue")]
-        reported_value: Option<RawNum<f64>>,
+        reported_value: Option<RawDecimal>,
     }
 
     let keys = [
@@ -155,7 +159,9 @@ pub(super) async fn income_statement(
                         timestamps: &[i64],
                         prefix: &str|
      -> Result<(), YfError> {
-        if let Ok(values) = serde_json::from_value::<Vec<TimeseriesValueF64>>(values_json.clone()) {
+        if let Ok(values) =
+            serde_json::from_value::<Vec<TimeseriesValueDecimal>>(values_json.clone())
+        {
             for (i, ts) in timestamps.iter().enumerate() {
                 let row = rows_map
                     .entry(*ts)
@@ -167,15 +173,16 @@ pub(super) async fn income_statement(
 
                 if key == format!("{prefix}TotalRevenue") {
                     row.total_revenue =
-                        value.map(|v| f64_to_money_with_currency(v, currency.clone()));
+                        value.map(|v| decimal_to_money_with_currency(v, currency.clone()));
                 } else if key == format!("{prefix}GrossProfit") {
                     row.gross_profit =
-                        value.map(|v| f64_to_money_with_currency(v, currency.clone()));
+                        value.map(|v| decimal_to_money_with_currency(v, currency.clone()));
                 } else if key == format!("{prefix}OperatingIncome") {
                     row.operating_income =
-                        value.map(|v| f64_to_money_with_currency(v, currency.clone()));
+                        value.map(|v| decimal_to_money_with_currency(v, currency.clone()));
                 } else if key == format!("{prefix}NetIncome") {
-                    row.net_income = value.map(|v| f64_to_money_with_currency(v, currency.clone()));
+                    row.net_income =
+                        value.map(|v| decimal_to_money_with_currency(v, currency.clone()));
                 }
             }
         }
@@ -210,12 +217,12 @@ pub(super) async fn balance_sheet(
 ) -> Result<Vec<BalanceSheetRow>, YfError> {
     use serde::Deserialize;
 
-    use crate::core::wire::{RawNum, RawNumU64};
+    use crate::core::wire::{RawDecimal, RawNumU64};
 
     #[derive(Deserialize)]
-    struct TimeseriesValueF64 {
+    struct TimeseriesValueDecimal {
         #[serde(rename = "reportedValue")]
-        reported_value: Option<RawNum<f64>>,
+        reported_value: Option<RawDecimal>,
     }
     #[derive(Deserialize)]
     struct TimeseriesValueU64 {
@@ -263,7 +270,7 @@ pub(super) async fn balance_sheet(
                 }
             }
         } else if let Ok(values) =
-            serde_json::from_value::<Vec<TimeseriesValueF64>>(values_json.clone())
+            serde_json::from_value::<Vec<TimeseriesValueDecimal>>(values_json.clone())
         {
             for (i, ts) in timestamps.iter().enumerate() {
                 let row = rows_map
@@ -276,18 +283,18 @@ pub(super) async fn balance_sheet(
 
                 if key == format!("{prefix}TotalAssets") {
                     row.total_assets =
-                        value.map(|v| f64_to_money_with_currency(v, currency.clone()));
+                        value.map(|v| decimal_to_money_with_currency(v, currency.clone()));
                 } else if key == format!("{prefix}TotalLiabilitiesNetMinorityInterest") {
                     row.total_liabilities =
-                        value.map(|v| f64_to_money_with_currency(v, currency.clone()));
+                        value.map(|v| decimal_to_money_with_currency(v, currency.clone()));
                 } else if key == format!("{prefix}StockholdersEquity") {
                     row.total_equity =
-                        value.map(|v| f64_to_money_with_currency(v, currency.clone()));
+                        value.map(|v| decimal_to_money_with_currency(v, currency.clone()));
                 } else if key == format!("{prefix}CashAndCashEquivalents") {
-                    row.cash = value.map(|v| f64_to_money_with_currency(v, currency.clone()));
+                    row.cash = value.map(|v| decimal_to_money_with_currency(v, currency.clone()));
                 } else if key == format!("{prefix}LongTermDebt") {
                     row.long_term_debt =
-                        value.map(|v| f64_to_money_with_currency(v, currency.clone()));
+                        value.map(|v| decimal_to_money_with_currency(v, currency.clone()));
                 }
             }
         }
@@ -319,12 +326,12 @@ pub(super) async fn cashflow(
 ) -> Result<Vec<CashflowRow>, YfError> {
     use serde::Deserialize;
 
-    use crate::core::wire::RawNum;
+    use crate::core::wire::RawDecimal;
 
     #[derive(Deserialize)]
-    struct TimeseriesValueF64 {
+    struct TimeseriesValueDecimal {
         #[serde(rename = "reportedValue")]
-        reported_value: Option<RawNum<f64>>,
+        reported_value: Option<RawDecimal>,
     }
 
     let keys = [
@@ -349,7 +356,9 @@ pub(super) async fn cashflow(
                         timestamps: &[i64],
                         prefix: &str|
      -> Result<(), YfError> {
-        if let Ok(values) = serde_json::from_value::<Vec<TimeseriesValueF64>>(values_json.clone()) {
+        if let Ok(values) =
+            serde_json::from_value::<Vec<TimeseriesValueDecimal>>(values_json.clone())
+        {
             for (i, ts) in timestamps.iter().enumerate() {
                 let row = rows_map
                     .entry(*ts)
@@ -361,15 +370,16 @@ pub(super) async fn cashflow(
 
                 if key == format!("{prefix}OperatingCashFlow") {
                     row.operating_cashflow =
-                        value.map(|v| f64_to_money_with_currency(v, currency.clone()));
+                        value.map(|v| decimal_to_money_with_currency(v, currency.clone()));
                 } else if key == format!("{prefix}CapitalExpenditure") {
                     row.capital_expenditures =
-                        value.map(|v| f64_to_money_with_currency(v, currency.clone()));
+                        value.map(|v| decimal_to_money_with_currency(v, currency.clone()));
                 } else if key == format!("{prefix}FreeCashFlow") {
                     row.free_cash_flow =
-                        value.map(|v| f64_to_money_with_currency(v, currency.clone()));
+                        value.map(|v| decimal_to_money_with_currency(v, currency.clone()));
                 } else if key == format!("{prefix}NetIncome") {
-                    row.net_income = value.map(|v| f64_to_money_with_currency(v, currency.clone()));
+                    row.net_income =
+                        value.map(|v| decimal_to_money_with_currency(v, currency.clone()));
                 }
             }
         }
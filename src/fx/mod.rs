@@ -0,0 +1,554 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, Utc};
+use paft::fundamentals::statements::{BalanceSheetRow, CashflowRow, IncomeStatementRow};
+use paft::market::action::Action;
+use paft::market::options::OptionChain;
+use paft::market::responses::history::HistoryResponse;
+use paft::money::{Currency, Money};
+use rust_decimal::Decimal;
+use tokio::sync::RwLock;
+
+use crate::core::client::{CacheMode, RetryConfig};
+use crate::core::currency::minor_unit;
+use crate::core::{Interval, YfClient, YfError, quotes as core_quotes};
+use crate::history::{HistoryBuilder, action_ts, local_dates};
+
+/// Default number of calendar days searched back from a date for an FX close.
+const DEFAULT_LOOKBACK_DAYS: u32 = 10;
+
+/// Default time a spot rate is reused before it is fetched again.
+const DEFAULT_SPOT_TTL: Duration = Duration::from_secs(60);
+
+/// Default time daily closes that reach today are reused before they are fetched again.
+const DEFAULT_DAILY_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// `(from, to)` major ISO codes.
+type Pair = (String, String);
+
+/// Daily closes of one pair over the span they were fetched for.
+struct DailyRates {
+    first: NaiveDate,
+    last: NaiveDate,
+    closes: BTreeMap<NaiveDate, Decimal>,
+    fetched_at: Instant,
+    /// Whether the fetched span reached the day of the fetch, so its last close may still move.
+    open: bool,
+}
+
+#[derive(Default)]
+struct RateCache {
+    spot: HashMap<Pair, (Decimal, Instant)>,
+    daily: HashMap<Pair, DailyRates>,
+}
+
+/// Converts `Money` values between currencies using Yahoo FX pairs such as `EURUSD=X`.
+///
+/// Spot rates come from the v7 quote endpoint and historical rates from daily chart closes.
+/// Both are cached by the converter: spot rates for [`spot_ttl`](Self::spot_ttl), daily
+/// closes for its lifetime, with the fetched span widened as needed. Closes fetched while
+/// the span still reached today are refreshed after [`daily_ttl`](Self::daily_ttl). Clones
+/// share the cache.
+///
+/// Historical conversions use the last close on or before the date, so weekends and holidays
+/// fall back to the previous session. Yahoo's minor-unit codes (`GBp`, `ZAc`, `ILA`) are
+/// converted through their major currency.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), yfinance_rs::YfError> {
+/// use paft::money::{Currency, IsoCurrency};
+/// use yfinance_rs::{FxConverter, Ticker, YfClient};
+///
+/// let client = YfClient::default();
+/// let fx = FxConverter::new(&client);
+/// let history = Ticker::new(&client, "SAP.DE").history(None, None, false).await?;
+/// let usd = Currency::Iso(IsoCurrency::USD);
+/// let close = fx.convert(&history[0].close, &usd).await?;
+/// println!("{}", close.amount());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FxConverter {
+    client: YfClient,
+    lookback_days: u32,
+    spot_ttl: Duration,
+    daily_ttl: Duration,
+    cache_mode: CacheMode,
+    retry_override: Option<RetryConfig>,
+    rates: Arc<RwLock<RateCache>>,
+}
+
+impl FxConverter {
+    /// Creates a new `FxConverter` with an empty rate cache.
+    #[must_use]
+    pub fn new(client: &YfClient) -> Self {
+        Self {
+            client: client.clone(),
+            lookback_days: DEFAULT_LOOKBACK_DAYS,
+            spot_ttl: DEFAULT_SPOT_TTL,
+            daily_ttl: DEFAULT_DAILY_TTL,
+            cache_mode: CacheMode::Use,
+            retry_override: None,
+            rates: Arc::new(RwLock::new(RateCache::default())),
+        }
+    }
+
+    /// Sets the cache mode for the underlying quote and chart requests.
+    #[must_use]
+    pub const fn cache_mode(mut self, mode: CacheMode) -> Self {
+        self.cache_mode = mode;
+        self
+    }
+
+    /// Overrides the default retry policy for the underlying requests.
+    #[must_use]
+    pub fn retry_policy(mut self, cfg: Option<RetryConfig>) -> Self {
+        self.retry_override = cfg;
+        self
+    }
+
+    /// Sets how many calendar days before a date are searched for a close. (Default: `10`)
+    #[must_use]
+    pub const fn lookback_days(mut self, days: u32) -> Self {
+        self.lookback_days = days;
+        self
+    }
+
+    /// Sets how long a spot rate is reused before it is fetched again. (Default: 60 seconds)
+    #[must_use]
+    pub const fn spot_ttl(mut self, ttl: Duration) -> Self {
+        self.spot_ttl = ttl;
+        self
+    }
+
+    /// Sets how long daily closes fetched up to today are reused before they are fetched
+    /// again, as the latest close is still moving. Closes of past spans are kept.
+    /// (Default: 15 minutes)
+    #[must_use]
+    pub const fn daily_ttl(mut self, ttl: Duration) -> Self {
+        self.daily_ttl = ttl;
+        self
+    }
+
+    /// Returns the current rate: one unit of `from` in `to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YfError::MissingData` if Yahoo has no price for the pair, or any error from
+    /// the quote request.
+    pub async fn spot_rate(&self, from: &Currency, to: &Currency) -> Result<Decimal, YfError> {
+        let (pair, scale) = pair_of(from, to);
+        if pair.0 == pair.1 {
+            return Ok(scale);
+        }
+        Ok(self.major_spot(pair).await? * scale)
+    }
+
+    /// Returns the rate on `date`: one unit of `from` in `to`, at the last daily close on or
+    /// before `date`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YfError::MissingData` if there is no close within
+    /// [`lookback_days`](Self::lookback_days) of `date`, or any error from the chart request.
+    pub async fn rate_on(
+        &self,
+        from: &Currency,
+        to: &Currency,
+        date: NaiveDate,
+    ) -> Result<Decimal, YfError> {
+        let (pair, scale) = pair_of(from, to);
+        if pair.0 == pair.1 {
+            return Ok(scale);
+        }
+        self.ensure_daily(&pair, date, date).await?;
+        Ok(self.cached_close(&pair, date).await? * scale)
+    }
+
+    /// Converts `money` to `to` at the spot rate.
+    ///
+    /// # Errors
+    ///
+    /// See [`spot_rate`](Self::spot_rate).
+    pub async fn convert(&self, money: &Money, to: &Currency) -> Result<Money, YfError> {
+        let rate = self.spot_rate(money.currency(), to).await?;
+        scaled(money, rate, to)
+    }
+
+    /// Converts `money` to `to` at the rate on `date`.
+    ///
+    /// # Errors
+    ///
+    /// See [`rate_on`](Self::rate_on).
+    pub async fn convert_on(
+        &self,
+        money: &Money,
+        to: &Currency,
+        date: NaiveDate,
+    ) -> Result<Money, YfError> {
+        let rate = self.rate_on(money.currency(), to, date).await?;
+        scaled(money, rate, to)
+    }
+
+    /// Converts every price of `history`, and its dividend and capital-gain amounts, to `to`
+    /// at the rate on each bar's or event's exchange-local date.
+    ///
+    /// Volumes and split ratios are unchanged. Rates for the whole span are fetched with one
+    /// chart request per source currency.
+    ///
+    /// # Errors
+    ///
+    /// See [`rate_on`](Self::rate_on).
+    pub async fn convert_history(
+        &self,
+        mut history: HistoryResponse,
+        to: &Currency,
+    ) -> Result<HistoryResponse, YfError> {
+        let (bar_dates, action_dates): (Vec<NaiveDate>, Vec<NaiveDate>) = {
+            let local_date = local_dates(&history);
+            (
+                history.candles.iter().map(|c| local_date(c.ts)).collect(),
+                history
+                    .actions
+                    .iter()
+                    .map(|a| local_date(action_ts(a)))
+                    .collect(),
+            )
+        };
+
+        let mut values: Vec<(&Money, NaiveDate)> = Vec::new();
+        for (c, &d) in history.candles.iter().zip(&bar_dates) {
+            values.extend([(&c.open, d), (&c.high, d), (&c.low, d), (&c.close, d)]);
+            if let Some(raw) = &c.close_unadj {
+                values.push((raw, d));
+            }
+        }
+        for (a, &d) in history.actions.iter().zip(&action_dates) {
+            match a {
+                Action::Dividend { amount, .. } => values.push((amount, d)),
+                Action::CapitalGain { gain, .. } => values.push((gain, d)),
+                Action::Split { .. } => {}
+            }
+        }
+        self.prefetch(values, to).await?;
+
+        for (c, d) in history.candles.iter_mut().zip(bar_dates) {
+            c.open = self.convert_on(&c.open, to, d).await?;
+            c.high = self.convert_on(&c.high, to, d).await?;
+            c.low = self.convert_on(&c.low, to, d).await?;
+            c.close = self.convert_on(&c.close, to, d).await?;
+            if let Some(raw) = &c.close_unadj {
+                c.close_unadj = Some(self.convert_on(raw, to, d).await?);
+            }
+        }
+        for (a, d) in history.actions.iter_mut().zip(action_dates) {
+            match a {
+                Action::Dividend { amount, .. } => {
+                    *amount = self.convert_on(amount, to, d).await?;
+                }
+                Action::CapitalGain { gain, .. } => {
+                    *gain = self.convert_on(gain, to, d).await?;
+                }
+                Action::Split { .. } => {}
+            }
+        }
+        Ok(history)
+    }
+
+    /// Converts the strikes, prices, bids and asks of `chain` to `to` at the spot rate.
+    ///
+    /// # Errors
+    ///
+    /// See [`spot_rate`](Self::spot_rate).
+    pub async fn convert_option_chain(
+        &self,
+        mut chain: OptionChain,
+        to: &Currency,
+    ) -> Result<OptionChain, YfError> {
+        for c in chain.calls.iter_mut().chain(chain.puts.iter_mut()) {
+            c.strike = self.convert(&c.strike, to).await?;
+            for m in [&mut c.price, &mut c.bid, &mut c.ask] {
+                if let Some(v) = m.as_ref() {
+                    *m = Some(self.convert(v, to).await?);
+                }
+            }
+        }
+        Ok(chain)
+    }
+
+    /// Converts income statement rows to `to` at the rate on each row's period end.
+    ///
+    /// Rows whose period is not a calendar date are converted at the spot rate.
+    ///
+    /// # Errors
+    ///
+    /// See [`rate_on`](Self::rate_on) and [`spot_rate`](Self::spot_rate).
+    pub async fn convert_income_statement(
+        &self,
+        rows: Vec<IncomeStatementRow>,
+        to: &Currency,
+    ) -> Result<Vec<IncomeStatementRow>, YfError> {
+        self.convert_rows(
+            rows,
+            to,
+            |r| r.period.to_string(),
+            |r| {
+                vec![
+                    &mut r.total_revenue,
+                    &mut r.gross_profit,
+                    &mut r.operating_income,
+                    &mut r.net_income,
+                ]
+            },
+        )
+        .await
+    }
+
+    /// Converts balance sheet rows to `to` at the rate on each row's period end.
+    ///
+    /// Share counts are unchanged. Rows whose period is not a calendar date are converted at
+    /// the spot rate.
+    ///
+    /// # Errors
+    ///
+    /// See [`rate_on`](Self::rate_on) and [`spot_rate`](Self::spot_rate).
+    pub async fn convert_balance_sheet(
+        &self,
+        rows: Vec<BalanceSheetRow>,
+        to: &Currency,
+    ) -> Result<Vec<BalanceSheetRow>, YfError> {
+        self.convert_rows(
+            rows,
+            to,
+            |r| r.period.to_string(),
+            |r| {
+                vec![
+                    &mut r.total_assets,
+                    &mut r.total_liabilities,
+                    &mut r.total_equity,
+                    &mut r.cash,
+                    &mut r.long_term_debt,
+                ]
+            },
+        )
+        .await
+    }
+
+    /// Converts cash flow rows to `to` at the rate on each row's period end.
+    ///
+    /// Rows whose period is not a calendar date are converted at the spot rate.
+    ///
+    /// # Errors
+    ///
+    /// See [`rate_on`](Self::rate_on) and [`spot_rate`](Self::spot_rate).
+    pub async fn convert_cashflow(
+        &self,
+        rows: Vec<CashflowRow>,
+        to: &Currency,
+    ) -> Result<Vec<CashflowRow>, YfError> {
+        self.convert_rows(
+            rows,
+            to,
+            |r| r.period.to_string(),
+            |r| {
+                vec![
+                    &mut r.operating_cashflow,
+                    &mut r.capital_expenditures,
+                    &mut r.free_cash_flow,
+                    &mut r.net_income,
+                ]
+            },
+        )
+        .await
+    }
+
+    async fn convert_rows<R>(
+        &self,
+        mut rows: Vec<R>,
+        to: &Currency,
+        period: fn(&R) -> String,
+        fields: fn(&mut R) -> Vec<&mut Option<Money>>,
+    ) -> Result<Vec<R>, YfError> {
+        let dates: Vec<Option<NaiveDate>> = rows
+            .iter()
+            .map(|r| NaiveDate::parse_from_str(&period(r), "%Y-%m-%d").ok())
+            .collect();
+
+        let mut dated = Vec::new();
+        for (r, d) in rows.iter_mut().zip(&dates) {
+            if let Some(d) = *d {
+                dated.extend(fields(r).into_iter().flatten().map(|m| (m.clone(), d)));
+            }
+        }
+        self.prefetch(dated.iter().map(|(m, d)| (m, *d)).collect(), to)
+            .await?;
+
+        for (r, d) in rows.iter_mut().zip(dates) {
+            for m in fields(r) {
+                if let Some(v) = m.as_ref() {
+                    *m = Some(match d {
+                        Some(d) => self.convert_on(v, to, d).await?,
+                        None => self.convert(v, to).await?,
+                    });
+                }
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Fetches daily closes covering every `(value, date)` so the conversions that follow hit
+    /// the cache: one chart request per source currency.
+    async fn prefetch(
+        &self,
+        values: Vec<(&Money, NaiveDate)>,
+        to: &Currency,
+    ) -> Result<(), YfError> {
+        let mut spans: HashMap<Pair, (NaiveDate, NaiveDate)> = HashMap::new();
+        for (m, d) in values {
+            let (pair, _) = pair_of(m.currency(), to);
+            if pair.0 == pair.1 {
+                continue;
+            }
+            spans
+                .entry(pair)
+                .and_modify(|(first, last)| {
+                    *first = (*first).min(d);
+                    *last = (*last).max(d);
+                })
+                .or_insert((d, d));
+        }
+        for (pair, (first, last)) in spans {
+            self.ensure_daily(&pair, first, last).await?;
+        }
+        Ok(())
+    }
+
+    async fn major_spot(&self, pair: Pair) -> Result<Decimal, YfError> {
+        if let Some((rate, at)) = self.rates.read().await.spot.get(&pair)
+            && at.elapsed() <= self.spot_ttl
+        {
+            return Ok(*rate);
+        }
+
+        let symbol = fx_symbol(&pair);
+        let nodes = core_quotes::fetch_v7_quotes(
+            &self.client,
+            &[symbol.as_str()],
+            None,
+            self.cache_mode,
+            self.retry_override.as_ref(),
+        )
+        .await?;
+        let rate = nodes
+            .into_iter()
+            .find_map(|n| n.regular_market_price)
+            .filter(|r| !r.is_zero())
+            .ok_or_else(|| YfError::MissingData(format!("no spot rate for {symbol}")))?;
+
+        self.rates
+            .write()
+            .await
+            .spot
+            .insert(pair, (rate, Instant::now()));
+        Ok(rate)
+    }
+
+    /// Makes sure the cached closes of `pair` cover `first` (less the lookback) to `last`,
+    /// widening the cached span with a single chart request if not.
+    async fn ensure_daily(
+        &self,
+        pair: &Pair,
+        first: NaiveDate,
+        last: NaiveDate,
+    ) -> Result<(), YfError> {
+        let first = first
+            .checked_sub_days(Days::new(u64::from(self.lookback_days)))
+            .unwrap_or(first);
+        let (first, last) = match self.rates.read().await.daily.get(pair) {
+            Some(cached)
+                if cached.first <= first
+                    && last <= cached.last
+                    && (!cached.open || cached.fetched_at.elapsed() <= self.daily_ttl) =>
+            {
+                return Ok(());
+            }
+            Some(cached) => (cached.first.min(first), cached.last.max(last)),
+            None => (first, last),
+        };
+
+        // West of Greenwich a bar's UTC timestamp can fall on the day after its trading date.
+        let end = last.checked_add_days(Days::new(2)).unwrap_or(last);
+        let history = HistoryBuilder::new(&self.client, fx_symbol(pair))
+            .between(midnight(first), midnight(end))
+            .interval(Interval::D1)
+            .actions(false)
+            .cache_mode(self.cache_mode)
+            .retry_policy(self.retry_override.clone())
+            .fetch_full()
+            .await?;
+
+        let local_date = local_dates(&history);
+        let closes = history
+            .candles
+            .iter()
+            .filter(|c| !c.close.amount().is_zero())
+            .map(|c| (local_date(c.ts), c.close.amount()))
+            .collect();
+        self.rates.write().await.daily.insert(
+            pair.clone(),
+            DailyRates {
+                first,
+                last,
+                closes,
+                fetched_at: Instant::now(),
+                open: end >= Utc::now().date_naive(),
+            },
+        );
+        Ok(())
+    }
+
+    async fn cached_close(&self, pair: &Pair, date: NaiveDate) -> Result<Decimal, YfError> {
+        let rates = self.rates.read().await;
+        rates
+            .daily
+            .get(pair)
+            .and_then(|r| r.closes.range(..=date).next_back())
+            .filter(|(d, _)| (date - **d).num_days() <= i64::from(self.lookback_days))
+            .map(|(_, rate)| *rate)
+            .ok_or_else(|| {
+                YfError::MissingData(format!("no {} rate on or before {date}", fx_symbol(pair)))
+            })
+    }
+}
+
+/// The major-currency pair behind a conversion, and the factor that turns its rate into a
+/// rate between the (possibly minor-unit) currencies given.
+fn pair_of(from: &Currency, to: &Currency) -> (Pair, Decimal) {
+    let (from, from_per) = major(from);
+    let (to, to_per) = major(to);
+    ((from, to), to_per / from_per)
+}
+
+fn major(currency: &Currency) -> (String, Decimal) {
+    let code = currency.to_string();
+    match minor_unit(&code) {
+        Some((major, per)) => (major.to_string(), per),
+        None => (code, Decimal::ONE),
+    }
+}
+
+fn fx_symbol((from, to): &Pair) -> String {
+    format!("{from}{to}=X")
+}
+
+fn midnight(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN).and_utc()
+}
+
+fn scaled(money: &Money, rate: Decimal, to: &Currency) -> Result<Money, YfError> {
+    Money::new(money.amount() * rate, to.clone())
+        .map_err(|_| YfError::InvalidParams(format!("cannot express amounts in {to}")))
+}
@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Days, FixedOffset, NaiveDate, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use paft::market::responses::history::HistoryResponse;
+use paft::money::Money;
+
+use crate::core::client::{CacheMode, RetryConfig};
+use crate::core::{Interval, YfClient, YfError};
+use crate::history::HistoryBuilder;
+
+/// Default number of calendar days searched back from each date for a trading day.
+const DEFAULT_LOOKBACK_DAYS: u32 = 10;
+/// Default number of chart requests in flight at once.
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// A close keyed by its exchange-local trading date.
+type DatedClose = (NaiveDate, DateTime<Utc>, Money);
+
+/// The bar an as-of lookup resolved to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsOfBar {
+    /// Trading date of the bar in the exchange's time zone (on or before the requested date).
+    pub date: NaiveDate,
+    /// Timestamp of the bar.
+    pub ts: DateTime<Utc>,
+    /// Close of the bar, adjusted or not depending on [`AsOfBuilder::adjusted`].
+    pub close: Money,
+}
+
+/// Why an as-of lookup found no close.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsOfMiss {
+    /// Yahoo returned no bars for the symbol over the requested span, or does not know it.
+    NoData,
+    /// No bar falls within the lookback window ending on the requested date, e.g. because the
+    /// date precedes the listing or the symbol stopped trading.
+    NoBarInWindow,
+}
+
+/// The result of one `(symbol, date)` lookup.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsOfLookup {
+    /// The symbol, as requested.
+    pub symbol: String,
+    /// The requested date.
+    pub date: NaiveDate,
+    /// The bar used for the lookup, or why there is none.
+    pub result: Result<AsOfBar, AsOfMiss>,
+}
+
+/// A builder for looking up the close of many symbols as of given dates.
+///
+/// Each lookup resolves to the last daily bar whose exchange-local trading date is on or before
+/// the requested date, so weekends and holidays fall back to the previous session. Lookups are
+/// grouped by symbol: every symbol costs one chart request spanning its earliest date (less the
+/// [`lookback_days`](Self::lookback_days) window) to its latest, and up to
+/// [`concurrency`](Self::concurrency) symbols are fetched at a time.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), yfinance_rs::YfError> {
+/// use chrono::NaiveDate;
+/// use yfinance_rs::{AsOfBuilder, YfClient};
+///
+/// let client = YfClient::default();
+/// let d = NaiveDate::from_ymd_opt(2024, 3, 30).unwrap(); // a Saturday
+/// for lookup in AsOfBuilder::new(&client).add("AAPL", d).add("MSFT", d).fetch().await? {
+///     match lookup.result {
+///         Ok(bar) => println!("{}: {} (bar of {})", lookup.symbol, bar.close.amount(), bar.date),
+///         Err(miss) => println!("{}: {miss:?}", lookup.symbol),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsOfBuilder {
+    client: YfClient,
+    lookups: Vec<(String, NaiveDate)>,
+    adjusted: bool,
+    lookback_days: u32,
+    concurrency: usize,
+    cache_mode: CacheMode,
+    retry_override: Option<RetryConfig>,
+}
+
+impl AsOfBuilder {
+    /// Creates a new `AsOfBuilder`.
+    #[must_use]
+    pub fn new(client: &YfClient) -> Self {
+        Self {
+            client: client.clone(),
+            lookups: Vec::new(),
+            adjusted: false,
+            lookback_days: DEFAULT_LOOKBACK_DAYS,
+            concurrency: DEFAULT_CONCURRENCY,
+            cache_mode: CacheMode::Use,
+            retry_override: None,
+        }
+    }
+
+    /// Sets the cache mode for this specific API call.
+    #[must_use]
+    pub const fn cache_mode(mut self, mode: CacheMode) -> Self {
+        self.cache_mode = mode;
+        self
+    }
+
+    /// Overrides the default retry policy for this specific API call.
+    #[must_use]
+    pub fn retry_policy(mut self, cfg: Option<RetryConfig>) -> Self {
+        self.retry_override = cfg;
+        self
+    }
+
+    /// Replaces the current list of lookups with a new list of `(symbol, date)` pairs.
+    #[must_use]
+    pub fn lookups<I, S>(mut self, pairs: I) -> Self
+    where
+        I: IntoIterator<Item = (S, NaiveDate)>,
+        S: Into<String>,
+    {
+        self.lookups = pairs.into_iter().map(|(s, d)| (s.into(), d)).collect();
+        self
+    }
+
+    /// Adds a single `(symbol, date)` lookup.
+    #[must_use]
+    pub fn add(mut self, symbol: impl Into<String>, date: NaiveDate) -> Self {
+        self.lookups.push((symbol.into(), date));
+        self
+    }
+
+    /// Sets whether closes are split- and dividend-adjusted. (Default: `false`)
+    #[must_use]
+    pub const fn adjusted(mut self, yes: bool) -> Self {
+        self.adjusted = yes;
+        self
+    }
+
+    /// Sets how many calendar days before each date are searched for a trading day.
+    /// (Default: `10`)
+    ///
+    /// A lookup with no bar in the window is reported as [`AsOfMiss::NoBarInWindow`].
+    #[must_use]
+    pub const fn lookback_days(mut self, days: u32) -> Self {
+        self.lookback_days = days;
+        self
+    }
+
+    /// Limits how many chart requests run at the same time. (Default: `4`)
+    ///
+    /// A limit of `0` is treated as `1`.
+    #[must_use]
+    pub const fn concurrency(mut self, limit: usize) -> Self {
+        self.concurrency = limit;
+        self
+    }
+
+    /// Resolves every lookup.
+    ///
+    /// Results are returned in the order the lookups were given, with misses reported per
+    /// lookup rather than as errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YfError::InvalidParams` if no lookups were provided, or any error other than
+    /// `YfError::NotFound` from the underlying chart requests.
+    pub async fn fetch(self) -> Result<Vec<AsOfLookup>, YfError> {
+        if self.lookups.is_empty() {
+            return Err(YfError::InvalidParams(
+                "lookups list cannot be empty".into(),
+            ));
+        }
+
+        let mut spans: Vec<(String, NaiveDate, NaiveDate)> = Vec::new();
+        let mut span_of: HashMap<&str, usize> = HashMap::new();
+        for (sym, date) in &self.lookups {
+            if let Some(&i) = span_of.get(sym.as_str()) {
+                let span = &mut spans[i];
+                span.1 = span.1.min(*date);
+                span.2 = span.2.max(*date);
+            } else {
+                span_of.insert(sym, spans.len());
+                spans.push((sym.clone(), *date, *date));
+            }
+        }
+
+        let fetched: Vec<Vec<DatedClose>> = stream::iter(&spans)
+            .map(|(sym, first, last)| self.fetch_span(sym, *first, *last))
+            .buffered(self.concurrency.max(1))
+            .try_collect()
+            .await?;
+        let bars: HashMap<&str, Vec<DatedClose>> = spans
+            .iter()
+            .map(|(sym, _, _)| sym.as_str())
+            .zip(fetched)
+            .collect();
+
+        Ok(self
+            .lookups
+            .iter()
+            .map(|(sym, date)| AsOfLookup {
+                symbol: sym.clone(),
+                date: *date,
+                result: self.resolve(&bars[sym.as_str()], *date),
+            })
+            .collect())
+    }
+
+    async fn fetch_span(
+        &self,
+        symbol: &str,
+        first: NaiveDate,
+        last: NaiveDate,
+    ) -> Result<Vec<DatedClose>, YfError> {
+        let start = first
+            .checked_sub_days(Days::new(u64::from(self.lookback_days) + 1))
+            .unwrap_or(first);
+        // West of Greenwich a bar's UTC timestamp can fall on the day after its trading date.
+        let end = last.checked_add_days(Days::new(2)).unwrap_or(last);
+
+        let history = HistoryBuilder::new(&self.client, symbol)
+            .between(
+                start.and_time(chrono::NaiveTime::MIN).and_utc(),
+                end.and_time(chrono::NaiveTime::MIN).and_utc(),
+            )
+            .interval(Interval::D1)
+            .auto_adjust(self.adjusted)
+            // Actions only matter for the fallback adjustment when Yahoo omits `adjclose`.
+            .actions(self.adjusted)
+            .cache_mode(self.cache_mode)
+            .retry_policy(self.retry_override.clone())
+            .fetch_full()
+            .await;
+
+        match history {
+            Ok(history) => Ok(dated_closes(&history)),
+            // Unknown symbols are reported per lookup as `AsOfMiss::NoData`.
+            Err(YfError::NotFound { .. }) => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn resolve(&self, bars: &[DatedClose], date: NaiveDate) -> Result<AsOfBar, AsOfMiss> {
+        if bars.is_empty() {
+            return Err(AsOfMiss::NoData);
+        }
+        let idx = bars.partition_point(|(d, _, _)| *d <= date);
+        let (bar_date, ts, close) = idx
+            .checked_sub(1)
+            .map(|i| &bars[i])
+            .ok_or(AsOfMiss::NoBarInWindow)?;
+        if (date - *bar_date).num_days() > i64::from(self.lookback_days) {
+            return Err(AsOfMiss::NoBarInWindow);
+        }
+        Ok(AsOfBar {
+            date: *bar_date,
+            ts: *ts,
+            close: close.clone(),
+        })
+    }
+}
+
+/// Closes of `history` keyed by exchange-local trading date, in timestamp order.
+fn dated_closes(history: &HistoryResponse) -> Vec<DatedClose> {
+    let local_date = local_dates(history);
+    let mut out: Vec<DatedClose> = history
+        .candles
+        .iter()
+        .map(|c| (local_date(c.ts), c.ts, c.close.clone()))
+        .collect();
+    out.sort_by_key(|(_, ts, _)| *ts);
+    out
+}
+
+/// Maps timestamps of `history` to exchange-local calendar dates, using the history's
+/// timezone, else its UTC offset, else UTC.
+pub(crate) fn local_dates(history: &HistoryResponse) -> impl Fn(DateTime<Utc>) -> NaiveDate {
+    let tz = history.meta.as_ref().and_then(|m| m.timezone);
+    let offset = history
+        .meta
+        .as_ref()
+        .and_then(|m| m.utc_offset_seconds)
+        .and_then(|s| i32::try_from(s).ok())
+        .and_then(FixedOffset::east_opt);
+
+    move |ts| match (tz, offset) {
+        (Some(tz), _) => ts.with_timezone(&tz).date_naive(),
+        (None, Some(off)) => ts.with_timezone(&off).date_naive(),
+        (None, None) => ts.date_naive(),
+    }
+}
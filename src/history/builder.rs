@@ -2,20 +2,35 @@ mod actions;
 mod adjust;
 mod assemble;
 mod fetch;
+mod update;
 
 use crate::core::client::{CacheMode, RetryConfig};
 // use crate::core::conversions::f64_to_money_with_currency_str;
+use crate::core::conversions::{string_to_asset_kind, string_to_exchange};
+use crate::core::currency::{minor_unit, to_major_units};
+use crate::core::instrument::resolve_instrument;
 use crate::core::{YfClient, YfError};
+use crate::history::earnings::{EarningsEvent, extract_earnings};
+use crate::history::repair::{RepairConfig, RepairLog};
+use crate::history::transform::{CandleTransform, TransformPipeline};
+use crate::history::validate::{ValidationMode, ValidationReport};
 use crate::history::wire::MetaNode;
 use chrono_tz::Tz;
 use paft::market::action::Action;
 use paft::market::requests::history::{Interval, Range};
 use paft::market::responses::history::{Candle, HistoryMeta, HistoryResponse};
+use paft::money::Money;
+use rust_decimal::RoundingStrategy;
+use rust_decimal::prelude::ToPrimitive;
+use std::sync::Arc;
 
 use actions::extract_actions;
-use adjust::cumulative_split_after;
+use adjust::{cumulative_dividend_after, cumulative_split_after, distribution_events};
 use assemble::assemble_candles;
 use fetch::fetch_chart;
+use update::{detect_restatement, merge, overlap_start};
+
+pub use update::{HistoryUpdate, Restatement};
 
 /// A builder for fetching historical price data for a single symbol.
 ///
@@ -43,6 +58,14 @@ pub struct HistoryBuilder {
     #[doc(hidden)]
     pub(crate) keepna: bool,
     #[doc(hidden)]
+    pub(crate) adjust_capital_gains: bool,
+    #[doc(hidden)]
+    pub(crate) pipeline: TransformPipeline,
+    #[doc(hidden)]
+    pub(crate) update_overlap: usize,
+    #[doc(hidden)]
+    pub(crate) restate_on_update: bool,
+    #[doc(hidden)]
     pub(crate) cache_mode: CacheMode,
     #[doc(hidden)]
     pub(crate) retry_override: Option<RetryConfig>,
@@ -61,6 +84,10 @@ impl HistoryBuilder {
             include_prepost: false,
             include_actions: true,
             keepna: false,
+            adjust_capital_gains: false,
+            pipeline: TransformPipeline::default(),
+            update_overlap: 5,
+            restate_on_update: true,
             cache_mode: CacheMode::Use,
             retry_override: None,
         }
@@ -118,6 +145,16 @@ impl HistoryBuilder {
         self
     }
 
+    /// Sets whether to back-adjust prices. (Default: `false`)
+    ///
+    /// Back-adjustment adjusts the Open, High, and Low prices, but keeps the Close price as the
+    /// raw, unadjusted close. This forces an internal adjustment even if `auto_adjust` is false.
+    #[must_use]
+    pub const fn back_adjust(mut self, yes: bool) -> Self {
+        self.pipeline.back_adjust = yes;
+        self
+    }
+
     /// Sets whether to include pre-market and post-market data for intraday intervals. (Default: `false`)
     #[must_use]
     pub const fn prepost(mut self, yes: bool) -> Self {
@@ -134,14 +171,107 @@ impl HistoryBuilder {
 
     /// Sets whether to keep data rows that have missing OHLC values. (Default: `false`)
     ///
-    /// If `true`, missing values are represented as `f64::NAN`. If `false`, rows with any missing
-    /// OHLC values are dropped.
+    /// If `true`, missing values are carried as zero. If `false`, rows with any missing OHLC
+    /// values are dropped. [`repair`](Self::repair) leaves the kept rows untouched.
     #[must_use]
     pub const fn keepna(mut self, yes: bool) -> Self {
         self.keepna = yes;
         self
     }
 
+    /// Sets whether capital-gain distributions are folded into the adjustment factor. (Default: `false`)
+    ///
+    /// This only matters when Yahoo omits `adjclose` (common for intraday intervals and some
+    /// funds), in which case the adjustment is rebuilt from the split and distribution events.
+    #[must_use]
+    pub const fn adjust_capital_gains(mut self, yes: bool) -> Self {
+        self.adjust_capital_gains = yes;
+        self
+    }
+
+    /// Sets whether to detect and fix common price defects in the fetched series. (Default: `false`)
+    ///
+    /// See [`repair_history`](crate::history::repair_history) for the repairs performed. Use
+    /// [`fetch_full_with_repairs`](Self::fetch_full_with_repairs) to see what was changed.
+    #[must_use]
+    pub const fn repair(mut self, yes: bool) -> Self {
+        self.pipeline.repair = yes;
+        self
+    }
+
+    /// Sets the thresholds used when [`repair`](Self::repair) is enabled, such as whether stray
+    /// zero-volume prints are removed or only flagged. (Default: [`RepairConfig::default`])
+    #[must_use]
+    pub const fn repair_config(mut self, cfg: RepairConfig) -> Self {
+        self.pipeline.repair_config = cfg;
+        self
+    }
+
+    /// Sets how bars that fail OHLCV integrity checks are handled. (Default: [`ValidationMode::Off`])
+    ///
+    /// Validation runs after any [`repair`](Self::repair). See
+    /// [`validate_history`](crate::history::validate_history) for the checks performed, and
+    /// [`fetch_full_validated`](Self::fetch_full_validated) to get the report.
+    #[must_use]
+    pub const fn validation(mut self, mode: ValidationMode) -> Self {
+        self.pipeline.validation = mode;
+        self
+    }
+
+    /// Sets whether to round prices. (Default: `false`)
+    ///
+    /// Prices are rounded in `Decimal` to [`rounding_places`](Self::rounding_places) using
+    /// [`rounding_strategy`](Self::rounding_strategy), after any back-adjustment.
+    #[must_use]
+    pub const fn rounding(mut self, yes: bool) -> Self {
+        self.pipeline.rounding = yes;
+        self
+    }
+
+    /// Sets the number of decimal places used when [`rounding`](Self::rounding) is enabled. (Default: `2`)
+    #[must_use]
+    pub const fn rounding_places(mut self, places: u32) -> Self {
+        self.pipeline.rounding_spec.places = places;
+        self
+    }
+
+    /// Sets how midpoints are resolved when [`rounding`](Self::rounding) is enabled.
+    /// (Default: [`RoundingStrategy::MidpointAwayFromZero`])
+    #[must_use]
+    pub const fn rounding_strategy(mut self, strategy: RoundingStrategy) -> Self {
+        self.pipeline.rounding_spec.strategy = strategy;
+        self
+    }
+
+    /// Appends a custom transform to run on the fetched series.
+    ///
+    /// Custom transforms run in the order they were added, after repair, validation,
+    /// back-adjustment and rounding. See [`CandleTransform`].
+    #[must_use]
+    pub fn transform(mut self, t: impl CandleTransform + 'static) -> Self {
+        self.pipeline.custom.push(Arc::new(t));
+        self
+    }
+
+    /// Sets how many already-stored bars [`update`](Self::update) re-fetches before the last one. (Default: `5`)
+    ///
+    /// The overlap is compared against the stored bars to detect restated history.
+    #[must_use]
+    pub const fn update_overlap(mut self, bars: usize) -> Self {
+        self.update_overlap = bars;
+        self
+    }
+
+    /// Sets whether [`update`](Self::update) re-fetches the full span when older bars were restated. (Default: `true`)
+    ///
+    /// If `false`, [`HistoryUpdate::RestatementRequired`] is returned instead and no further
+    /// request is made.
+    #[must_use]
+    pub const fn restate_on_update(mut self, yes: bool) -> Self {
+        self.restate_on_update = yes;
+        self
+    }
+
     /// Executes the request and returns only the price candles.
     ///
     /// # Errors
@@ -174,6 +304,66 @@ impl HistoryBuilder {
         )
     )]
     pub async fn fetch_full(self) -> Result<HistoryResponse, YfError> {
+        let (resp, _log) = self.fetch_full_with_repairs().await?;
+        Ok(resp)
+    }
+
+    /// Executes the request and returns the full response together with the log of repairs
+    /// applied to it.
+    ///
+    /// The log is empty unless [`repair`](Self::repair) is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `YfError` if the network request fails, the API returns an error,
+    /// or the response cannot be parsed.
+    pub async fn fetch_full_with_repairs(self) -> Result<(HistoryResponse, RepairLog), YfError> {
+        let checked = self.fetch_checked(false).await?;
+        Ok((checked.resp, checked.repairs))
+    }
+
+    /// Executes the request and returns the full response together with its validation report.
+    ///
+    /// The report is empty unless [`validation`](Self::validation) is enabled. With
+    /// [`ValidationMode::Drop`], the offending bars listed in the report have already been
+    /// removed from the response.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `YfError` if the network request fails, the API returns an error,
+    /// or the response cannot be parsed, and [`YfError::Validation`] if validation fails under
+    /// [`ValidationMode::Error`].
+    pub async fn fetch_full_validated(
+        self,
+    ) -> Result<(HistoryResponse, ValidationReport), YfError> {
+        let checked = self.fetch_checked(false).await?;
+        Ok((checked.resp, checked.validation))
+    }
+
+    /// Executes the request and returns the full response together with the earnings
+    /// releases reported by the chart endpoint over the same span, in timestamp order.
+    ///
+    /// Earnings are requested alongside the corporate actions, so reactions around each
+    /// release can be read straight from the candles. EPS figures are in the reporting
+    /// currency used for dividends.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `YfError` if the network request fails, the API returns an error,
+    /// or the response cannot be parsed.
+    pub async fn fetch_full_with_earnings(
+        self,
+    ) -> Result<(HistoryResponse, Vec<EarningsEvent>), YfError> {
+        let checked = self.fetch_checked(true).await?;
+        Ok((checked.resp, checked.earnings))
+    }
+
+    /// Whether prices are adjusted during assembly; back-adjustment needs adjusted prices.
+    const fn adjusts(&self) -> bool {
+        self.auto_adjust || self.pipeline.back_adjust
+    }
+
+    async fn fetch_checked(self, include_earnings: bool) -> Result<Checked, YfError> {
         // 1) Fetch and parse the /chart payload into owned blocks
         let fetched = fetch_chart(
             &self.client,
@@ -182,33 +372,63 @@ impl HistoryBuilder {
             self.period,
             self.interval,
             self.include_actions,
+            include_earnings,
             self.include_prepost,
             self.cache_mode,
             self.retry_override.as_ref(),
         )
         .await?;
 
+        // The chart metadata names the instrument type, so later downloads and streams of
+        // this symbol are labeled correctly without a quote request.
+        if let Some(meta) = fetched.meta.as_ref()
+            && let Some(kind) = meta
+                .instrument_type
+                .as_deref()
+                .and_then(string_to_asset_kind)
+        {
+            let exchange = string_to_exchange(meta.full_exchange_name.clone());
+            resolve_instrument(&self.client, &self.symbol, Some(kind), exchange).await;
+        }
+
         // 2) Corporate actions & split ratios
         let reporting_currency = self.client.reporting_currency(&self.symbol, None).await;
 
-        let (mut actions_out, split_events) =
+        let (actions_out, split_events) =
             extract_actions(fetched.events.as_ref(), &reporting_currency);
+        let earnings = extract_earnings(fetched.events.as_ref(), &reporting_currency);
 
-        // 3) Cumulative split factors after each bar
+        // 3) Cumulative split and distribution factors after each bar
         let cum_split_after = cumulative_split_after(&fetched.ts, &split_events);
+        let div_events = distribution_events(&actions_out, self.adjust_capital_gains);
+        let raw_close: Vec<Option<f64>> = fetched
+            .quote
+            .close
+            .iter()
+            .map(|c| c.and_then(|d| d.to_f64()))
+            .collect();
+        let cum_dividend_after = cumulative_dividend_after(&fetched.ts, &raw_close, &div_events);
 
         // 4) Assemble candles (+ raw close) with/without adjustments
         let currency = fetched.meta.as_ref().and_then(|m| m.currency.as_deref());
-        let candles = assemble_candles(
+        let (candles, missing) = assemble_candles(
             &fetched.ts,
             &fetched.quote,
             &fetched.adjclose,
-            self.auto_adjust,
+            self.adjusts(),
             self.keepna,
             &cum_split_after,
+            &cum_dividend_after,
             currency,
         );
 
+        // 4b) Optionally re-express minor-unit listings (GBp, ZAc, ILA) in the major currency
+        let (candles, mut actions_out) = if self.client.normalize_minor_units() {
+            normalize_minor_units(candles, actions_out, currency)
+        } else {
+            (candles, actions_out)
+        };
+
         // ensure actions sorted (extract_actions already sorts, keep consistent)
         actions_out.sort_by_key(|a| match a {
             Action::Dividend { ts, .. }
@@ -219,16 +439,125 @@ impl HistoryBuilder {
         // 5) Map metadata
         let meta_out = map_meta(fetched.meta.as_ref());
 
-        Ok(HistoryResponse {
+        let mut resp = HistoryResponse {
             candles,
             actions: actions_out,
-            adjusted: self.auto_adjust,
+            adjusted: self.adjusts(),
             meta: meta_out,
+        };
+
+        // 6) Repair, validation, back-adjust, rounding and custom transforms
+        let (log, report) = self.pipeline.run(&mut resp, &missing)?;
+        #[cfg(feature = "tracing")]
+        if !report.is_empty() {
+            tracing::warn!(symbol = %self.symbol, "history validation: {report}");
+        }
+
+        Ok(Checked {
+            resp,
+            repairs: log,
+            validation: report,
+            earnings,
         })
     }
+
+    /// Fetches only the bars after the last candle of `existing` and merges them in.
+    ///
+    /// A window of [`update_overlap`](Self::update_overlap) stored bars is re-fetched alongside
+    /// the new ones. If a new split (or, for adjusted series, a new dividend or capital gain)
+    /// appears, or an overlapping bar no longer matches, the stored series is considered
+    /// restated: the full span is then re-fetched and returned as [`HistoryUpdate::Restated`],
+    /// or [`HistoryUpdate::RestatementRequired`] is returned if
+    /// [`restate_on_update`](Self::restate_on_update) is disabled.
+    ///
+    /// The last stored bar is always replaced, since it may have been captured mid-session.
+    /// Any range or period set on the builder is ignored. If `existing` has no candles, this
+    /// behaves like [`fetch_full`](Self::fetch_full) and reports every bar as new.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YfError::InvalidParams` if `existing.adjusted` does not match
+    /// [`auto_adjust`](Self::auto_adjust) (or [`back_adjust`](Self::back_adjust)), or any error from the underlying fetches.
+    pub async fn update(self, existing: &HistoryResponse) -> Result<HistoryUpdate, YfError> {
+        if existing.adjusted != self.adjusts() {
+            return Err(YfError::InvalidParams(
+                "existing series adjustment does not match auto_adjust or back_adjust".into(),
+            ));
+        }
+
+        let Some(first) = existing.candles.first().map(|c| c.ts) else {
+            let history = self.fetch_full().await?;
+            let new_bars = history.candles.len();
+            return Ok(HistoryUpdate::Appended { history, new_bars });
+        };
+
+        let now = chrono::Utc::now();
+        let start = existing.candles[overlap_start(existing.candles.len(), self.update_overlap)].ts;
+        let fetched = self.clone().between(start, now).fetch_full().await?;
+
+        let Some(reason) = detect_restatement(existing, &fetched, self.adjusts()) else {
+            let (history, new_bars) = merge(existing, fetched);
+            return Ok(HistoryUpdate::Appended { history, new_bars });
+        };
+
+        if !self.restate_on_update {
+            return Ok(HistoryUpdate::RestatementRequired(reason));
+        }
+
+        let history = self.between(first, now).fetch_full().await?;
+        Ok(HistoryUpdate::Restated { history, reason })
+    }
+}
+
+/// Everything a single chart fetch produces once repaired and validated.
+struct Checked {
+    resp: HistoryResponse,
+    repairs: RepairLog,
+    validation: ValidationReport,
+    earnings: Vec<EarningsEvent>,
 }
 
-/* --- tiny private helper --- */
+/* --- tiny private helpers --- */
+
+fn normalize_minor_units(
+    candles: Vec<Candle>,
+    actions: Vec<Action>,
+    code: Option<&str>,
+) -> (Vec<Candle>, Vec<Action>) {
+    if code.and_then(minor_unit).is_none() {
+        return (candles, actions);
+    }
+    let major = |m: Money| to_major_units(m, code);
+
+    let candles = candles
+        .into_iter()
+        .map(|c| Candle {
+            ts: c.ts,
+            open: major(c.open),
+            high: major(c.high),
+            low: major(c.low),
+            close: major(c.close),
+            close_unadj: c.close_unadj.map(major),
+            volume: c.volume,
+        })
+        .collect();
+    // Chart events are quoted in the same unit as prices.
+    let actions = actions
+        .into_iter()
+        .map(|a| match a {
+            Action::Dividend { ts, amount } => Action::Dividend {
+                ts,
+                amount: major(amount),
+            },
+            Action::CapitalGain { ts, gain } => Action::CapitalGain {
+                ts,
+                gain: major(gain),
+            },
+            split @ Action::Split { .. } => split,
+        })
+        .collect();
+    (candles, actions)
+}
 
 fn map_meta(m: Option<&MetaNode>) -> Option<HistoryMeta> {
     m.as_ref().map(|mm| HistoryMeta {
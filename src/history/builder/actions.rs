@@ -1,4 +1,4 @@
-use crate::core::conversions::{f64_to_money_with_currency, i64_to_datetime};
+use crate::core::conversions::{decimal_to_money_with_currency, i64_to_datetime};
 use crate::history::wire::Events;
 use paft::market::action::Action;
 use paft::money::Currency;
@@ -21,7 +21,7 @@ pub fn extract_actions(
             if let Some(amount) = d.amount {
                 out.push(Action::Dividend {
                     ts: i64_to_datetime(ts),
-                    amount: f64_to_money_with_currency(amount, currency.clone()),
+                    amount: decimal_to_money_with_currency(amount, currency.clone()),
                 });
             }
         }
@@ -33,7 +33,7 @@ pub fn extract_actions(
             if let Some(gain) = g.amount {
                 out.push(Action::CapitalGain {
                     ts: i64_to_datetime(ts),
-                    gain: f64_to_money_with_currency(gain, currency.clone()),
+                    gain: decimal_to_money_with_currency(gain, currency.clone()),
                 });
             }
         }
@@ -1,3 +1,7 @@
+use crate::core::conversions::{f64_to_decimal_shortest, money_to_f64};
+use paft::market::action::Action;
+use rust_decimal::Decimal;
+
 pub fn cumulative_split_after(ts: &[i64], split_events: &[(i64, f64)]) -> Vec<f64> {
     let mut out = vec![1.0; ts.len()];
     if split_events.is_empty() || ts.is_empty() {
@@ -17,14 +21,87 @@ pub fn cumulative_split_after(ts: &[i64], split_events: &[(i64, f64)]) -> Vec<f6
     out
 }
 
+/// Collects cash distributions (dividends and, optionally, capital gains) as
+/// `(ts, amount)` pairs sorted by timestamp.
+pub fn distribution_events(actions: &[Action], include_capital_gains: bool) -> Vec<(i64, f64)> {
+    let mut out: Vec<(i64, f64)> = actions
+        .iter()
+        .filter_map(|a| match a {
+            Action::Dividend { ts, amount } => Some((ts.timestamp(), money_to_f64(amount))),
+            Action::CapitalGain { ts, gain } if include_capital_gains => {
+                Some((ts.timestamp(), money_to_f64(gain)))
+            }
+            _ => None,
+        })
+        .filter(|(_, amt)| amt.is_finite() && *amt > 0.0)
+        .collect();
+    out.sort_by_key(|(ts, _)| *ts);
+    out
+}
+
+/// Cumulative distribution factor applying to each bar, using the standard
+/// `1 - amount / prev_close` method where `prev_close` is the raw close of the
+/// last bar before the ex-date.
+///
+/// Distributions that cannot be priced (no earlier bar, or an amount that would
+/// wipe out the whole close) are skipped.
+pub fn cumulative_dividend_after(
+    ts: &[i64],
+    close: &[Option<f64>],
+    div_events: &[(i64, f64)],
+) -> Vec<f64> {
+    let mut out = vec![1.0; ts.len()];
+    if div_events.is_empty() || ts.is_empty() {
+        return out;
+    }
+
+    // Per-event factor, keyed by the index of the first bar on or after the ex-date; it applies
+    // to every bar before that index.
+    let mut factors: Vec<(usize, f64)> = Vec::with_capacity(div_events.len());
+    for &(ex_ts, amount) in div_events {
+        let before = ts.partition_point(|&t| t < ex_ts);
+        let prev_close = (0..before).rev().find_map(|j| {
+            close
+                .get(j)
+                .and_then(|x| *x)
+                .filter(|c| c.is_finite() && *c > 0.0)
+        });
+        let Some(prev_close) = prev_close else {
+            continue;
+        };
+        let factor = 1.0 - amount / prev_close;
+        if factor > 0.0 && factor < 1.0 {
+            factors.push((before, factor));
+        }
+    }
+
+    let mut f_idx = factors.len();
+    let mut running: f64 = 1.0;
+
+    for i in (0..ts.len()).rev() {
+        while f_idx > 0 && factors[f_idx - 1].0 > i {
+            f_idx -= 1;
+            running *= factors[f_idx].1;
+        }
+        out[i] = running;
+    }
+    out
+}
+
+/// Price factor for row `i`: Yahoo's `adjclose / close` when both are present, otherwise the
+/// split and distribution factors computed locally.
 pub fn price_factor_for_row(
     i: usize,
-    adjclose_i: Option<f64>,
-    close_i: Option<f64>,
+    adjclose_i: Option<Decimal>,
+    close_i: Option<Decimal>,
     cum_split_after: &[f64],
-) -> f64 {
-    match (adjclose_i, close_i) {
-        (Some(adj), Some(c)) if c != 0.0 => adj / c,
-        _ => 1.0 / cum_split_after[i].max(1e-12),
+    cum_dividend_after: &[f64],
+) -> Decimal {
+    if let (Some(adj), Some(c)) = (adjclose_i, close_i)
+        && let Some(f) = adj.checked_div(c)
+    {
+        return f;
     }
+    f64_to_decimal_shortest(cum_dividend_after[i] / cum_split_after[i].max(1e-12))
+        .unwrap_or(Decimal::ONE)
 }
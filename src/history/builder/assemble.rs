@@ -1,116 +1,83 @@
-use crate::core::conversions::{f64_to_money_with_currency_str, i64_to_datetime};
+use crate::core::conversions::{decimal_to_money_with_currency_str, i64_to_datetime};
 use crate::history::wire::QuoteBlock;
+use chrono::{DateTime, Utc};
 use paft::market::responses::history::Candle;
+use rust_decimal::Decimal;
 
 use super::adjust::price_factor_for_row;
 
+#[allow(clippy::too_many_arguments)]
 pub fn assemble_candles(
     ts: &[i64],
     q: &QuoteBlock,
-    adj: &[Option<f64>],
+    adj: &[Option<Decimal>],
     auto_adjust: bool,
     keepna: bool,
     cum_split_after: &[f64],
+    cum_dividend_after: &[f64],
     currency: Option<&str>,
-) -> Vec<Candle> {
+) -> (Vec<Candle>, Vec<DateTime<Utc>>) {
     let mut out = Vec::new();
+    let mut missing = Vec::new();
+    let money =
+        |v: Option<Decimal>| decimal_to_money_with_currency_str(v.unwrap_or_default(), currency);
 
     for (i, &t) in ts.iter().enumerate() {
-        let getter_f64 = |v: &Vec<Option<f64>>| v.get(i).and_then(|x| *x);
-        let mut open = getter_f64(&q.open);
-        let mut high = getter_f64(&q.high);
-        let mut low = getter_f64(&q.low);
-        let mut close = getter_f64(&q.close);
+        let getter = |v: &Vec<Option<Decimal>>| v.get(i).and_then(|x| *x);
+        let mut open = getter(&q.open);
+        let mut high = getter(&q.high);
+        let mut low = getter(&q.low);
+        let mut close = getter(&q.close);
         let volume0 = q.volume.get(i).and_then(|x| *x);
 
-        let raw_close_val = close.unwrap_or(f64::NAN);
+        let raw_close = close;
+        let mut volume = volume0;
 
         if auto_adjust {
-            let pf = price_factor_for_row(i, adj.get(i).and_then(|x| *x), close, cum_split_after);
+            let adj_i = adj.get(i).and_then(|x| *x);
+            let pf = price_factor_for_row(i, adj_i, close, cum_split_after, cum_dividend_after);
+            let scale = |v: Option<Decimal>| v.map(|v| v.checked_mul(pf).unwrap_or(v));
 
-            if let Some(v) = open.as_mut() {
-                *v *= pf;
-            }
-            if let Some(v) = high.as_mut() {
-                *v *= pf;
-            }
-            if let Some(v) = low.as_mut() {
-                *v *= pf;
-            }
-            if let Some(v) = close.as_mut() {
-                *v *= pf;
-            }
+            open = scale(open);
+            high = scale(high);
+            low = scale(low);
+            // Yahoo's adjusted close is already exact; avoid re-deriving it through the factor.
+            close = match (adj_i, close) {
+                (Some(a), Some(c)) if !c.is_zero() => Some(a),
+                _ => scale(close),
+            };
 
-            let volume_adj = volume0.map(|v| {
-                #[allow(clippy::cast_precision_loss)]
-                let v_adj = (v as f64) * cum_split_after[i];
-                if v_adj.is_finite() && v_adj >= 0.0 {
-                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-                    (v_adj.round() as u64)
-                } else {
-                    v
-                }
-            });
-
-            if let (Some(ov), Some(hv), Some(lv), Some(cv)) = (open, high, low, close) {
-                out.push(Candle {
-                    ts: i64_to_datetime(t),
-                    open: f64_to_money_with_currency_str(ov, currency),
-                    high: f64_to_money_with_currency_str(hv, currency),
-                    low: f64_to_money_with_currency_str(lv, currency),
-                    close: f64_to_money_with_currency_str(cv, currency),
-                    close_unadj: if raw_close_val.is_finite() {
-                        Some(f64_to_money_with_currency_str(raw_close_val, currency))
-                    } else {
-                        None
-                    },
-                    volume: volume_adj,
-                });
-            } else if keepna {
-                out.push(Candle {
-                    ts: i64_to_datetime(t),
-                    open: f64_to_money_with_currency_str(open.unwrap_or(f64::NAN), currency),
-                    high: f64_to_money_with_currency_str(high.unwrap_or(f64::NAN), currency),
-                    low: f64_to_money_with_currency_str(low.unwrap_or(f64::NAN), currency),
-                    close: f64_to_money_with_currency_str(close.unwrap_or(f64::NAN), currency),
-                    close_unadj: if raw_close_val.is_finite() {
-                        Some(f64_to_money_with_currency_str(raw_close_val, currency))
+            let complete = open.is_some() && high.is_some() && low.is_some() && close.is_some();
+            if complete {
+                volume = volume0.map(|v| {
+                    #[allow(clippy::cast_precision_loss)]
+                    let v_adj = (v as f64) * cum_split_after[i];
+                    if v_adj.is_finite() && v_adj >= 0.0 {
+                        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                        (v_adj.round() as u64)
                     } else {
-                        None
-                    },
-                    volume: volume0,
+                        v
+                    }
                 });
             }
-        } else if let (Some(ov), Some(hv), Some(lv), Some(cv)) = (open, high, low, close) {
-            out.push(Candle {
-                ts: i64_to_datetime(t),
-                open: f64_to_money_with_currency_str(ov, currency),
-                high: f64_to_money_with_currency_str(hv, currency),
-                low: f64_to_money_with_currency_str(lv, currency),
-                close: f64_to_money_with_currency_str(cv, currency),
-                close_unadj: if raw_close_val.is_finite() {
-                    Some(f64_to_money_with_currency_str(raw_close_val, currency))
-                } else {
-                    None
-                },
-                volume: volume0,
-            });
-        } else if keepna {
+        }
+
+        let complete = open.is_some() && high.is_some() && low.is_some() && close.is_some();
+        if !complete && keepna {
+            missing.push(i64_to_datetime(t));
+        }
+        if complete || keepna {
             out.push(Candle {
                 ts: i64_to_datetime(t),
-                open: f64_to_money_with_currency_str(open.unwrap_or(f64::NAN), currency),
-                high: f64_to_money_with_currency_str(high.unwrap_or(f64::NAN), currency),
-                low: f64_to_money_with_currency_str(low.unwrap_or(f64::NAN), currency),
-                close: f64_to_money_with_currency_str(close.unwrap_or(f64::NAN), currency),
-                close_unadj: if raw_close_val.is_finite() {
-                    Some(f64_to_money_with_currency_str(raw_close_val, currency))
-                } else {
-                    None
-                },
-                volume: volume0,
+                open: money(open),
+                high: money(high),
+                low: money(low),
+                close: money(close),
+                close_unadj: raw_close.map(|c| money(Some(c))),
+                volume,
             });
         }
     }
 
-    out
+    (out, missing)
 }
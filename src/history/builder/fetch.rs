@@ -4,7 +4,7 @@ use crate::history::wire::{Events, MetaNode, QuoteBlock};
 pub struct Fetched {
     pub ts: Vec<i64>,
     pub quote: QuoteBlock,
-    pub adjclose: Vec<Option<f64>>,
+    pub adjclose: Vec<Option<rust_decimal::Decimal>>,
     pub events: Option<Events>,
     pub meta: Option<MetaNode>,
 }
@@ -17,6 +17,7 @@ pub async fn fetch_chart(
     period: Option<(i64, i64)>,
     interval: crate::core::Interval,
     include_actions: bool,
+    include_earnings: bool,
     include_prepost: bool,
     cache_mode: CacheMode,
     retry_override: Option<&RetryConfig>,
@@ -40,8 +41,14 @@ pub async fn fetch_chart(
         }
 
         qp.append_pair("interval", crate::core::models::interval_as_str(interval));
-        if include_actions {
-            qp.append_pair("events", "div|split|capitalGains");
+        let events = match (include_actions, include_earnings) {
+            (true, true) => Some("div|split|capitalGains|earn"),
+            (true, false) => Some("div|split|capitalGains"),
+            (false, true) => Some("earn"),
+            (false, false) => None,
+        };
+        if let Some(events) = events {
+            qp.append_pair("events", events);
         }
         qp.append_pair(
             "includePrePost",
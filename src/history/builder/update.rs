@@ -0,0 +1,144 @@
+use crate::core::conversions::money_to_f64;
+use crate::history::action_ts;
+use paft::market::action::Action;
+use paft::market::responses::history::{Candle, HistoryResponse};
+
+/// Relative tolerance used when comparing re-fetched bars against stored ones.
+const PRICE_TOLERANCE: f64 = 1e-6;
+
+/// Outcome of [`HistoryBuilder::update`](crate::HistoryBuilder::update).
+#[derive(Debug, Clone)]
+pub enum HistoryUpdate {
+    /// New bars were merged onto the existing series. Previously stored bars were unaffected.
+    Appended {
+        /// The merged series.
+        history: HistoryResponse,
+        /// Number of bars in `history` that were not present in the existing series.
+        new_bars: usize,
+    },
+    /// The adjustment basis of older bars changed, so the full span was re-fetched and
+    /// re-adjusted.
+    Restated {
+        /// The freshly fetched series, covering the original span up to now.
+        history: HistoryResponse,
+        /// What triggered the restatement.
+        reason: Restatement,
+    },
+    /// The adjustment basis of older bars changed and re-fetching was disabled with
+    /// [`HistoryBuilder::restate_on_update(false)`](crate::HistoryBuilder::restate_on_update).
+    RestatementRequired(Restatement),
+}
+
+/// Why an incremental update could not simply be appended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Restatement {
+    /// Corporate actions that were not in the existing series and change the adjustment of
+    /// earlier bars.
+    NewActions(Vec<Action>),
+    /// A bar in the overlap window no longer matches the stored bar.
+    OverlapMismatch {
+        /// Timestamp of the first mismatching bar.
+        ts: chrono::DateTime<chrono::Utc>,
+        /// Close previously stored for that bar.
+        stored: f64,
+        /// Close returned by the new fetch.
+        fetched: f64,
+    },
+}
+
+/// Index of the first stored bar that is re-fetched, leaving `overlap` bars before the last one.
+pub const fn overlap_start(len: usize, overlap: usize) -> usize {
+    let back = if overlap < len { overlap } else { len - 1 };
+    len - 1 - back
+}
+
+/// Checks whether the newly fetched bars are consistent with the stored series.
+///
+/// Splits always restate history (Yahoo's raw closes are split-adjusted); dividends and
+/// capital gains only do so for adjusted series. The last stored bar is excluded from the
+/// overlap comparison because it may have been captured mid-session.
+pub fn detect_restatement(
+    existing: &HistoryResponse,
+    fetched: &HistoryResponse,
+    adjusted: bool,
+) -> Option<Restatement> {
+    let first_ts = existing.candles.first()?.ts.timestamp();
+
+    let new_actions: Vec<Action> = fetched
+        .actions
+        .iter()
+        .filter(|a| action_ts(a).timestamp() > first_ts && !existing.actions.contains(a))
+        .filter(|a| adjusted || matches!(a, Action::Split { .. }))
+        .cloned()
+        .collect();
+    if !new_actions.is_empty() {
+        return Some(Restatement::NewActions(new_actions));
+    }
+
+    let last_ts = existing.candles.last()?.ts;
+    for new in fetched.candles.iter().filter(|c| c.ts < last_ts) {
+        let Ok(idx) = existing.candles.binary_search_by_key(&new.ts, |c| c.ts) else {
+            continue;
+        };
+        let stored = money_to_f64(&existing.candles[idx].close);
+        let fresh = money_to_f64(&new.close);
+        if !stored.is_finite() || !fresh.is_finite() {
+            continue;
+        }
+        let scale = stored.abs().max(fresh.abs()).max(1e-12);
+        if (stored - fresh).abs() / scale > PRICE_TOLERANCE {
+            return Some(Restatement::OverlapMismatch {
+                ts: new.ts,
+                stored,
+                fetched: fresh,
+            });
+        }
+    }
+    None
+}
+
+/// Appends the fetched bars to the stored series, replacing the last stored bar and anything
+/// after it. Returns the merged response and the number of bars that are new.
+pub fn merge(existing: &HistoryResponse, fetched: HistoryResponse) -> (HistoryResponse, usize) {
+    let Some(last_ts) = existing.candles.last().map(|c| c.ts) else {
+        let n = fetched.candles.len();
+        return (fetched, n);
+    };
+
+    let tail: Vec<Candle> = fetched
+        .candles
+        .into_iter()
+        .filter(|c| c.ts >= last_ts)
+        .collect();
+
+    let mut candles: Vec<Candle> = if tail.is_empty() {
+        existing.candles.clone()
+    } else {
+        existing
+            .candles
+            .iter()
+            .filter(|c| c.ts < last_ts)
+            .cloned()
+            .collect()
+    };
+    let new_bars = tail.iter().filter(|c| c.ts > last_ts).count();
+    candles.extend(tail);
+
+    let mut actions = existing.actions.clone();
+    for a in fetched.actions {
+        if !actions.contains(&a) {
+            actions.push(a);
+        }
+    }
+    actions.sort_by_key(action_ts);
+
+    (
+        HistoryResponse {
+            candles,
+            actions,
+            adjusted: existing.adjusted,
+            meta: fetched.meta.or_else(|| existing.meta.clone()),
+        },
+        new_bars,
+    )
+}
@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use paft::market::action::Action;
+use paft::market::responses::history::{Candle, HistoryResponse};
+use paft::money::Money;
+
+use crate::history::{PriceField, action_ts};
+
+/// A field of a bar that differs between two fetches.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldDelta {
+    /// An open, high, low or close price changed.
+    Price {
+        /// Which price changed.
+        field: PriceField,
+        /// Value in the older series.
+        old: Money,
+        /// Value in the newer series.
+        new: Money,
+    },
+    /// The unadjusted close changed, appeared or disappeared.
+    UnadjustedClose {
+        /// Value in the older series.
+        old: Option<Money>,
+        /// Value in the newer series.
+        new: Option<Money>,
+    },
+    /// The volume changed, appeared or disappeared.
+    Volume {
+        /// Value in the older series.
+        old: Option<u64>,
+        /// Value in the newer series.
+        new: Option<u64>,
+    },
+}
+
+/// A bar present in both series whose values differ.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarChange {
+    /// Timestamp of the bar.
+    pub ts: DateTime<Utc>,
+    /// Every field that differs, in open, high, low, close, unadjusted close, volume order.
+    pub deltas: Vec<FieldDelta>,
+}
+
+/// A corporate action that differs between two fetches.
+///
+/// Actions are matched by kind and timestamp.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionChange {
+    /// The action only appears in the newer series.
+    Added(Action),
+    /// The action only appears in the older series.
+    Removed(Action),
+    /// The action appears in both series with a different amount or ratio.
+    Changed {
+        /// The action in the older series.
+        old: Action,
+        /// The action in the newer series.
+        new: Action,
+    },
+}
+
+/// Differences between two fetches of the same symbol, produced by [`diff_history`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryDiff {
+    /// Bars only present in the newer series, in timestamp order.
+    pub added: Vec<Candle>,
+    /// Bars only present in the older series, in timestamp order.
+    pub removed: Vec<Candle>,
+    /// Bars present in both series with different values, in timestamp order.
+    pub changed: Vec<BarChange>,
+    /// Corporate actions that were added, removed or changed, in timestamp order.
+    pub actions: Vec<ActionChange>,
+}
+
+impl HistoryDiff {
+    /// Returns `true` if the two series are identical.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.actions.is_empty()
+    }
+
+    /// Returns `true` if data present in the older series was revised: bars removed or
+    /// changed, or actions removed or changed.
+    ///
+    /// Bars and actions that were only added, as on a normal refresh, are not revisions.
+    #[must_use]
+    pub fn has_revisions(&self) -> bool {
+        !self.removed.is_empty()
+            || !self.changed.is_empty()
+            || self
+                .actions
+                .iter()
+                .any(|a| !matches!(a, ActionChange::Added(_)))
+    }
+}
+
+impl fmt::Display for HistoryDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("no differences");
+        }
+        write!(
+            f,
+            "{} bars added, {} removed, {} changed; {} action changes",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len(),
+            self.actions.len()
+        )?;
+        if let Some(first) = self.changed.first() {
+            write!(f, " (first changed bar at {})", first.ts)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two fetches of the same symbol bar by bar and action by action.
+///
+/// Bars are matched by timestamp and compared exactly, since prices are held as `Decimal`.
+/// If a series has several bars with the same timestamp, only the first is compared. Both
+/// series should be fetched with the same interval and adjustment, otherwise every bar will
+/// show as changed.
+#[must_use]
+pub fn diff_history(old: &HistoryResponse, new: &HistoryResponse) -> HistoryDiff {
+    let old_bars = by_ts(&old.candles);
+    let mut new_bars = by_ts(&new.candles);
+    let mut diff = HistoryDiff::default();
+
+    for (ts, before) in old_bars {
+        match new_bars.remove(&ts) {
+            None => diff.removed.push(before.clone()),
+            Some(after) => {
+                let deltas = candle_deltas(before, after);
+                if !deltas.is_empty() {
+                    diff.changed.push(BarChange {
+                        ts: before.ts,
+                        deltas,
+                    });
+                }
+            }
+        }
+    }
+    diff.added = new_bars.into_values().cloned().collect();
+
+    let old_actions = actions_by_key(&old.actions);
+    let mut new_actions = actions_by_key(&new.actions);
+    for (key, before) in old_actions {
+        match new_actions.remove(&key) {
+            None => diff.actions.push(ActionChange::Removed(before.clone())),
+            Some(after) if after != before => diff.actions.push(ActionChange::Changed {
+                old: before.clone(),
+                new: after.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    diff.actions.extend(
+        new_actions
+            .into_values()
+            .map(|a| ActionChange::Added(a.clone())),
+    );
+    diff.actions.sort_by_key(|c| match c {
+        ActionChange::Added(a)
+        | ActionChange::Removed(a)
+        | ActionChange::Changed { old: a, .. } => action_key(a),
+    });
+
+    diff
+}
+
+fn by_ts(candles: &[Candle]) -> BTreeMap<i64, &Candle> {
+    let mut out = BTreeMap::new();
+    for c in candles {
+        out.entry(c.ts.timestamp()).or_insert(c);
+    }
+    out
+}
+
+fn candle_deltas(old: &Candle, new: &Candle) -> Vec<FieldDelta> {
+    let mut deltas: Vec<FieldDelta> = [
+        (PriceField::Open, &old.open, &new.open),
+        (PriceField::High, &old.high, &new.high),
+        (PriceField::Low, &old.low, &new.low),
+        (PriceField::Close, &old.close, &new.close),
+    ]
+    .into_iter()
+    .filter(|(_, a, b)| a != b)
+    .map(|(field, a, b)| FieldDelta::Price {
+        field,
+        old: a.clone(),
+        new: b.clone(),
+    })
+    .collect();
+
+    if old.close_unadj != new.close_unadj {
+        deltas.push(FieldDelta::UnadjustedClose {
+            old: old.close_unadj.clone(),
+            new: new.close_unadj.clone(),
+        });
+    }
+    if old.volume != new.volume {
+        deltas.push(FieldDelta::Volume {
+            old: old.volume,
+            new: new.volume,
+        });
+    }
+    deltas
+}
+
+/// Orders actions by timestamp, then kind.
+fn action_key(a: &Action) -> (i64, u8) {
+    let kind = match a {
+        Action::Dividend { .. } => 0,
+        Action::Split { .. } => 1,
+        Action::CapitalGain { .. } => 2,
+    };
+    (action_ts(a).timestamp(), kind)
+}
+
+fn actions_by_key(actions: &[Action]) -> BTreeMap<(i64, u8), &Action> {
+    let mut out = BTreeMap::new();
+    for a in actions {
+        out.entry(action_key(a)).or_insert(a);
+    }
+    out
+}
@@ -0,0 +1,328 @@
+use chrono::{DateTime, Duration, Utc};
+use paft::market::action::Action;
+use paft::market::responses::history::HistoryResponse;
+use paft::money::Money;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::core::conversions::decimal_to_money_with_currency;
+
+/// Number of neighbouring dividends on each side used as the baseline for special-dividend
+/// detection.
+const SPECIAL_NEIGHBOURS: usize = 4;
+
+/// Number of most recent regular payments whose spacing decides the frequency.
+const FREQUENCY_WINDOW: usize = 9;
+
+/// How often a security pays regular dividends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DividendFrequency {
+    /// About every month.
+    Monthly,
+    /// About every three months.
+    Quarterly,
+    /// About every six months.
+    SemiAnnual,
+    /// About every year.
+    Annual,
+    /// No consistent spacing, or too few payments to tell.
+    Irregular,
+}
+
+impl DividendFrequency {
+    /// Expected number of regular payments per year, if the frequency is regular.
+    #[must_use]
+    pub const fn payments_per_year(self) -> Option<u32> {
+        match self {
+            Self::Monthly => Some(12),
+            Self::Quarterly => Some(4),
+            Self::SemiAnnual => Some(2),
+            Self::Annual => Some(1),
+            Self::Irregular => None,
+        }
+    }
+
+    fn from_gap_days(days: i64) -> Self {
+        match days {
+            0..=45 => Self::Monthly,
+            46..=135 => Self::Quarterly,
+            136..=250 => Self::SemiAnnual,
+            251..=450 => Self::Annual,
+            _ => Self::Irregular,
+        }
+    }
+}
+
+/// A dividend as seen by [`DividendAnalytics`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DividendRecord {
+    /// Ex-date of the dividend.
+    pub ex_date: DateTime<Utc>,
+    /// Amount per share as reported.
+    pub amount: Money,
+    /// Amount per share restated for splits after the ex-date (equal to `amount` unless
+    /// [`DividendAnalytics::split_adjust`] is enabled).
+    pub adjusted_amount: Money,
+    /// Whether the dividend stands out from the surrounding payments as a special dividend.
+    pub special: bool,
+}
+
+/// Dividend analytics over a price history and its actions: trailing-12-month dividends and
+/// yield, payment frequency, special dividends and growth.
+///
+/// Yields use unadjusted closes (`close_unadj` for adjusted histories, `close` otherwise).
+/// Yahoo's chart closes and dividend amounts are both already split-adjusted; enable
+/// [`split_adjust`](Self::split_adjust) only for histories whose amounts are quoted as
+/// originally paid.
+#[derive(Debug, Clone)]
+pub struct DividendAnalytics {
+    dividends: Vec<(DateTime<Utc>, Money)>,
+    splits: Vec<(DateTime<Utc>, Decimal)>,
+    closes: Vec<(DateTime<Utc>, Money)>,
+    split_adjust: bool,
+    special_multiple: Decimal,
+    include_special: bool,
+}
+
+impl DividendAnalytics {
+    /// Collects the dividends, splits and closes of `history`.
+    #[must_use]
+    pub fn from_history(history: &HistoryResponse) -> Self {
+        let mut dividends = Vec::new();
+        let mut splits = Vec::new();
+        for a in &history.actions {
+            match a {
+                Action::Dividend { ts, amount } if amount.amount() > Decimal::ZERO => {
+                    dividends.push((*ts, amount.clone()));
+                }
+                Action::Split {
+                    ts,
+                    numerator,
+                    denominator,
+                } if *numerator > 0 && *denominator > 0 => {
+                    splits.push((*ts, Decimal::from(*numerator) / Decimal::from(*denominator)));
+                }
+                _ => {}
+            }
+        }
+        dividends.sort_by_key(|(ts, _)| *ts);
+
+        let closes = history
+            .candles
+            .iter()
+            .filter_map(|c| {
+                let close = if history.adjusted {
+                    c.close_unadj.clone()?
+                } else {
+                    c.close.clone()
+                };
+                (close.amount() > Decimal::ZERO).then_some((c.ts, close))
+            })
+            .collect();
+
+        Self {
+            dividends,
+            splits,
+            closes,
+            split_adjust: false,
+            special_multiple: Decimal::TWO,
+            include_special: true,
+        }
+    }
+
+    /// Sets whether dividend amounts are restated for later splits. (Default: `false`)
+    #[must_use]
+    pub const fn split_adjust(mut self, yes: bool) -> Self {
+        self.split_adjust = yes;
+        self
+    }
+
+    /// Sets how many times the median of the surrounding payments a dividend must be to count
+    /// as special. (Default: `2`)
+    #[must_use]
+    pub const fn special_multiple(mut self, multiple: Decimal) -> Self {
+        self.special_multiple = multiple;
+        self
+    }
+
+    /// Sets whether special dividends count towards trailing-12-month figures. (Default: `true`)
+    ///
+    /// Growth rates always use regular dividends only.
+    #[must_use]
+    pub const fn include_special(mut self, yes: bool) -> Self {
+        self.include_special = yes;
+        self
+    }
+
+    /// Returns every dividend with its split-adjusted amount and special flag, by ex-date.
+    #[must_use]
+    pub fn records(&self) -> Vec<DividendRecord> {
+        let adjusted: Vec<Money> = self
+            .dividends
+            .iter()
+            .map(|(ts, amount)| {
+                if !self.split_adjust {
+                    return amount.clone();
+                }
+                let factor: Decimal = self
+                    .splits
+                    .iter()
+                    .filter(|(split_ts, _)| split_ts > ts)
+                    .map(|(_, ratio)| *ratio)
+                    .product();
+                decimal_to_money_with_currency(amount.amount() / factor, amount.currency().clone())
+            })
+            .collect();
+
+        (0..adjusted.len())
+            .map(|i| {
+                let lo = i.saturating_sub(SPECIAL_NEIGHBOURS);
+                let hi = (i + SPECIAL_NEIGHBOURS + 1).min(adjusted.len());
+                let neighbours: Vec<Decimal> = (lo..hi)
+                    .filter(|&j| j != i)
+                    .map(|j| adjusted[j].amount())
+                    .collect();
+                let special = median(neighbours)
+                    .is_some_and(|m| adjusted[i].amount() >= m * self.special_multiple);
+                DividendRecord {
+                    ex_date: self.dividends[i].0,
+                    amount: self.dividends[i].1.clone(),
+                    adjusted_amount: adjusted[i].clone(),
+                    special,
+                }
+            })
+            .collect()
+    }
+
+    /// Detects the payment frequency from the spacing of recent regular dividends.
+    #[must_use]
+    pub fn frequency(&self) -> DividendFrequency {
+        let regular: Vec<DateTime<Utc>> = self
+            .records()
+            .into_iter()
+            .filter(|r| !r.special)
+            .map(|r| r.ex_date)
+            .collect();
+        let recent = &regular[regular.len().saturating_sub(FREQUENCY_WINDOW)..];
+        let gaps: Vec<i64> = recent
+            .windows(2)
+            .map(|w| (w[1] - w[0]).num_days())
+            .collect();
+        let Some(typical) = median(gaps.iter().map(|&g| Decimal::from(g)).collect()) else {
+            return DividendFrequency::Irregular;
+        };
+        let typical = typical.to_i64().unwrap_or(i64::MAX);
+
+        // Most gaps must sit near the typical one for the schedule to count as regular.
+        let consistent = gaps
+            .iter()
+            .filter(|&&g| g * 2 >= typical && g * 2 <= typical * 3)
+            .count();
+        if consistent * 4 < gaps.len() * 3 {
+            return DividendFrequency::Irregular;
+        }
+        DividendFrequency::from_gap_days(typical)
+    }
+
+    /// Sums the dividends with ex-dates in the 365 days up to and including `as_of`.
+    ///
+    /// Returns `None` if the history has no dividends at all.
+    #[must_use]
+    pub fn ttm_dividends(&self, as_of: DateTime<Utc>) -> Option<Money> {
+        let records = self.records();
+        let currency = records.first()?.adjusted_amount.currency().clone();
+        let total: Decimal = self.ttm_sum(&records, as_of, self.include_special);
+        Some(decimal_to_money_with_currency(total, currency))
+    }
+
+    /// Trailing-12-month dividends divided by the last close at or before `as_of`.
+    ///
+    /// Returns `None` if there are no dividends or no close on or before `as_of`.
+    #[must_use]
+    pub fn ttm_yield(&self, as_of: DateTime<Utc>) -> Option<Decimal> {
+        let records = self.records();
+        records.first()?;
+        let idx = self.closes.partition_point(|(ts, _)| *ts <= as_of);
+        let (_, close) = self.closes.get(idx.checked_sub(1)?)?;
+        Some(self.ttm_sum(&records, as_of, self.include_special) / close.amount())
+    }
+
+    /// Trailing-12-month yield on every bar of the price series.
+    ///
+    /// Empty if the history has no dividends.
+    #[must_use]
+    pub fn ttm_yield_series(&self) -> Vec<(DateTime<Utc>, Decimal)> {
+        let records = self.records();
+        if records.is_empty() {
+            return Vec::new();
+        }
+        self.closes
+            .iter()
+            .map(|(ts, close)| {
+                (
+                    *ts,
+                    self.ttm_sum(&records, *ts, self.include_special) / close.amount(),
+                )
+            })
+            .collect()
+    }
+
+    /// Compound annual growth rate of regular dividends over `years` years.
+    ///
+    /// Compares the regular dividends paid in the 12 months up to the latest regular ex-date
+    /// with those paid in the 12 months up to `years` years earlier. Returns `None` if `years`
+    /// is zero or either window has no dividends.
+    #[must_use]
+    pub fn cagr(&self, years: u32) -> Option<f64> {
+        if years == 0 {
+            return None;
+        }
+        let records = self.records();
+        let end = records.iter().rev().find(|r| !r.special)?.ex_date;
+        let start = end - Duration::days(365 * i64::from(years));
+        let covered_from = match (self.closes.first(), records.first()) {
+            (Some((close_ts, _)), Some(r)) => (*close_ts).min(r.ex_date),
+            (None, Some(r)) => r.ex_date,
+            _ => return None,
+        };
+        if covered_from > start - Duration::days(365) {
+            // Not enough history to fill the earlier window.
+            return None;
+        }
+
+        let last = self.ttm_sum(&records, end, false).to_f64()?;
+        let first = self.ttm_sum(&records, start, false).to_f64()?;
+        if first <= 0.0 || last <= 0.0 {
+            return None;
+        }
+        Some((last / first).powf(1.0 / f64::from(years)) - 1.0)
+    }
+
+    fn ttm_sum(
+        &self,
+        records: &[DividendRecord],
+        as_of: DateTime<Utc>,
+        include_special: bool,
+    ) -> Decimal {
+        let from = as_of - Duration::days(365);
+        records
+            .iter()
+            .filter(|r| r.ex_date > from && r.ex_date <= as_of)
+            .filter(|r| include_special || !r.special)
+            .map(|r| r.adjusted_amount.amount())
+            .sum()
+    }
+}
+
+fn median(mut xs: Vec<Decimal>) -> Option<Decimal> {
+    if xs.is_empty() {
+        return None;
+    }
+    xs.sort_unstable();
+    let mid = xs.len() / 2;
+    Some(if xs.len().is_multiple_of(2) {
+        (xs[mid - 1] + xs[mid]) / Decimal::TWO
+    } else {
+        xs[mid]
+    })
+}
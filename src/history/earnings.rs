@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use paft::money::{Currency, Money};
+
+use crate::core::conversions::{decimal_to_money_with_currency, i64_to_datetime};
+use crate::history::wire::Events;
+
+/// An earnings release reported by the chart endpoint.
+///
+/// Returned by [`HistoryBuilder::fetch_full_with_earnings`](crate::HistoryBuilder::fetch_full_with_earnings).
+#[derive(Debug, Clone, PartialEq)]
+pub struct EarningsEvent {
+    /// Timestamp of the earnings release.
+    pub ts: DateTime<Utc>,
+    /// Reported earnings per share, if available.
+    pub eps_actual: Option<Money>,
+    /// Consensus earnings-per-share estimate, if available.
+    pub eps_estimate: Option<Money>,
+}
+
+pub(crate) fn extract_earnings(events: Option<&Events>, currency: &Currency) -> Vec<EarningsEvent> {
+    let Some(earnings) = events.and_then(|ev| ev.earnings.as_ref()) else {
+        return Vec::new();
+    };
+
+    let money = |v| decimal_to_money_with_currency(v, currency.clone());
+    let mut out: Vec<EarningsEvent> = earnings
+        .iter()
+        .map(|(k, e)| {
+            let ts = k.parse::<i64>().unwrap_or_else(|_| e.date.unwrap_or(0));
+            EarningsEvent {
+                ts: i64_to_datetime(ts),
+                eps_actual: e.eps_actual.map(money),
+                eps_estimate: e.eps_estimate.map(money),
+            }
+        })
+        .collect();
+    out.sort_by_key(|e| e.ts);
+    out
+}
@@ -1,11 +1,48 @@
+mod as_of;
 mod builder;
-mod wire;
+mod diff;
+mod dividends;
+mod earnings;
+mod repair;
+mod total_return;
+mod transform;
+mod validate;
+pub(crate) mod wire;
 
-pub use builder::HistoryBuilder;
+pub(crate) use as_of::local_dates;
+pub use as_of::{AsOfBar, AsOfBuilder, AsOfLookup, AsOfMiss};
+pub use builder::{HistoryBuilder, HistoryUpdate, Restatement};
+pub use diff::{ActionChange, BarChange, FieldDelta, HistoryDiff, diff_history};
+pub use dividends::{DividendAnalytics, DividendFrequency, DividendRecord};
+pub use earnings::EarningsEvent;
+pub use repair::{
+    PriceField, RepairConfig, RepairEntry, RepairKind, RepairLog, repair_history,
+    repair_history_with,
+};
+pub use total_return::{
+    ReinvestAt, Reinvestment, TotalReturnBuilder, TotalReturnPoint, TotalReturnSeries,
+};
+pub(crate) use transform::TransformPipeline;
+pub use transform::{BackAdjust, CandleTransform, Rounding};
+pub use validate::{
+    ValidationIssue, ValidationMode, ValidationReport, ValidationRule, enforce_validation,
+    validate_history,
+};
 
 use crate::core::{HistoryRequest, HistoryResponse, HistoryService, YfClient, YfError};
+use chrono::{DateTime, Utc};
 use core::future::Future;
 use core::pin::Pin;
+use paft::market::action::Action;
+
+/// Timestamp of a corporate action, whatever its kind.
+pub(crate) const fn action_ts(action: &Action) -> DateTime<Utc> {
+    match action {
+        Action::Dividend { ts, .. } | Action::Split { ts, .. } | Action::CapitalGain { ts, .. } => {
+            *ts
+        }
+    }
+}
 
 impl HistoryService for YfClient {
     fn fetch_full_history<'a>(
@@ -27,7 +64,7 @@ impl HistoryService for YfClient {
                 .keepna(req.keepna);
 
             if let Some((p1, p2)) = req.period {
-                use chrono::{TimeZone, Utc};
+                use chrono::TimeZone;
                 let start = Utc
                     .timestamp_opt(p1, 0)
                     .single()
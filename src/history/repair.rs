@@ -0,0 +1,563 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use paft::market::action::Action;
+use paft::market::responses::history::{Candle, HistoryResponse};
+use paft::money::Money;
+
+use crate::core::conversions::{
+    decimal_to_money_with_currency, f64_to_decimal_shortest, money_to_f64,
+};
+
+/// A price column of a [`Candle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PriceField {
+    /// The opening price.
+    Open,
+    /// The high price.
+    High,
+    /// The low price.
+    Low,
+    /// The closing price.
+    Close,
+}
+
+const PRICE_FIELDS: [PriceField; 4] = [
+    PriceField::Open,
+    PriceField::High,
+    PriceField::Low,
+    PriceField::Close,
+];
+
+/// What a single repair did to a bar.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepairKind {
+    /// A bar with the same timestamp as the following one was removed (the later bar is kept).
+    DuplicateRemoved,
+    /// A zero or negative price was replaced by `value`, taken from the bar's close or its
+    /// neighbours.
+    ZeroPriceFilled {
+        /// The repaired column.
+        field: PriceField,
+        /// The value written.
+        value: f64,
+    },
+    /// The whole bar was off by ~100x (a currency-unit mix-up) and its prices were scaled.
+    UnitScaled {
+        /// Factor applied to every price of the bar.
+        factor: f64,
+    },
+    /// A single price column was off by ~100x relative to the bar's close and was scaled.
+    FieldUnitScaled {
+        /// The repaired column.
+        field: PriceField,
+        /// Factor applied to the column.
+        factor: f64,
+    },
+    /// The bar was missing a split adjustment. Prices were scaled by `factor` and volume by
+    /// its inverse.
+    SplitAdjusted {
+        /// Factor applied to the bar's prices.
+        factor: f64,
+    },
+    /// The bar was missing a dividend adjustment and its adjusted prices were scaled.
+    DividendAdjusted {
+        /// Factor applied to the bar's adjusted prices.
+        factor: f64,
+    },
+    /// A zero-volume bar whose prices jumped outside the recent range was flagged and kept.
+    StrayPrintFlagged,
+    /// A zero-volume bar whose prices jumped outside the recent range was removed (see
+    /// [`RepairConfig::remove_stray_prints`]).
+    StrayPrintRemoved,
+}
+
+/// Settings for [`repair_history_with`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RepairConfig {
+    /// Relative move away from the previous close a zero-volume bar must exceed, on top of
+    /// leaving the recent high-low range, to count as a stray print. (Default: `0.2`)
+    pub stray_print_threshold: f64,
+    /// If `true`, stray prints are removed; otherwise they are only flagged in the log.
+    /// (Default: `false`)
+    pub remove_stray_prints: bool,
+}
+
+impl Default for RepairConfig {
+    fn default() -> Self {
+        Self {
+            stray_print_threshold: 0.2,
+            remove_stray_prints: false,
+        }
+    }
+}
+
+/// A single change made by [`repair_history`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairEntry {
+    /// Timestamp of the affected bar.
+    pub ts: DateTime<Utc>,
+    /// What was changed.
+    pub kind: RepairKind,
+}
+
+/// Every change made while repairing a price series, in the order the repairs ran.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RepairLog {
+    /// The individual repairs.
+    pub entries: Vec<RepairEntry>,
+}
+
+impl RepairLog {
+    /// Returns `true` if no repair was made.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of repairs made.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Iterates over the repairs made.
+    pub fn iter(&self) -> std::slice::Iter<'_, RepairEntry> {
+        self.entries.iter()
+    }
+
+    fn push(&mut self, ts: DateTime<Utc>, kind: RepairKind) {
+        self.entries.push(RepairEntry { ts, kind });
+    }
+}
+
+impl<'a> IntoIterator for &'a RepairLog {
+    type Item = &'a RepairEntry;
+    type IntoIter = std::slice::Iter<'a, RepairEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+/// Detects and fixes common Yahoo price defects in place, returning a log of every change.
+///
+/// The repairs run in this order:
+/// 1. Duplicate bars (same timestamp) are collapsed, keeping the later one.
+/// 2. Zero or negative prices are filled from the bar's close, or from the neighbouring closes.
+/// 3. Bars before a split that are missing the split adjustment are rescaled.
+/// 4. Bars (or single columns) that are ~100x off are rescaled.
+/// 5. For adjusted series, bars before a dividend that are missing the dividend adjustment
+///    are rescaled. This needs `close_unadj` on the bars around the ex-date.
+/// 6. Zero-volume bars that jump well outside the recent range are flagged as stray prints.
+///
+/// Every zero price is treated as a defect here. Rows kept with missing prices by `keepna` are
+/// carried as zero, so repair those series through [`HistoryBuilder::repair`](crate::HistoryBuilder::repair),
+/// which knows which rows were missing in Yahoo's payload and leaves them untouched.
+pub fn repair_history(history: &mut HistoryResponse) -> RepairLog {
+    repair_history_with(history, &RepairConfig::default())
+}
+
+/// Like [`repair_history`], with explicit [`RepairConfig`] settings.
+pub fn repair_history_with(history: &mut HistoryResponse, cfg: &RepairConfig) -> RepairLog {
+    repair_history_skipping(history, cfg, &[])
+}
+
+/// Repairs `history`, leaving the bars at `missing` (rows kept by `keepna`) untouched.
+pub(crate) fn repair_history_skipping(
+    history: &mut HistoryResponse,
+    cfg: &RepairConfig,
+    missing: &[DateTime<Utc>],
+) -> RepairLog {
+    let mut log = RepairLog::default();
+
+    // Rows missing in the payload are set aside, so they are neither filled nor used as
+    // neighbours, and merged back afterwards.
+    let missing: HashSet<DateTime<Utc>> = missing.iter().copied().collect();
+    let (mut rows, kept): (Vec<Candle>, Vec<Candle>) = std::mem::take(&mut history.candles)
+        .into_iter()
+        .partition(|c| !missing.contains(&c.ts));
+
+    remove_duplicates(&mut rows, &mut log);
+    fill_zero_prices(&mut rows, &mut log);
+    repair_missing_split_adjustment(&mut rows, &history.actions, &mut log);
+    repair_unit_scale(&mut rows, &mut log);
+    if history.adjusted {
+        repair_missing_dividend_adjustment(&mut rows, &history.actions, &mut log);
+    }
+    handle_stray_prints(&mut rows, cfg, &mut log);
+
+    if !kept.is_empty() {
+        rows.extend(kept);
+        rows.sort_by_key(|c| c.ts);
+    }
+    history.candles = rows;
+    log
+}
+
+/* ---------------- helpers ---------------- */
+
+fn price(c: &Candle, field: PriceField) -> f64 {
+    money_to_f64(money(c, field))
+}
+
+const fn money(c: &Candle, field: PriceField) -> &Money {
+    match field {
+        PriceField::Open => &c.open,
+        PriceField::High => &c.high,
+        PriceField::Low => &c.low,
+        PriceField::Close => &c.close,
+    }
+}
+
+fn slot(c: &mut Candle, field: PriceField) -> &mut Money {
+    match field {
+        PriceField::Open => &mut c.open,
+        PriceField::High => &mut c.high,
+        PriceField::Low => &mut c.low,
+        PriceField::Close => &mut c.close,
+    }
+}
+
+fn set_price(c: &mut Candle, field: PriceField, value: f64) {
+    let slot = slot(c, field);
+    if let Some(d) = f64_to_decimal_shortest(value) {
+        *slot = decimal_to_money_with_currency(d, slot.currency().clone());
+    }
+}
+
+/// Multiplies in `Decimal` so already-exact amounts do not pick up binary artifacts.
+fn scale_money(m: &mut Money, factor: f64) {
+    if let Some(scaled) = f64_to_decimal_shortest(factor).and_then(|f| m.amount().checked_mul(f)) {
+        *m = decimal_to_money_with_currency(scaled, m.currency().clone());
+    }
+}
+
+fn scale_prices(c: &mut Candle, factor: f64, include_unadj: bool) {
+    for field in PRICE_FIELDS {
+        scale_money(slot(c, field), factor);
+    }
+    if include_unadj && let Some(cu) = c.close_unadj.as_mut() {
+        scale_money(cu, factor);
+    }
+}
+
+fn valid(x: f64) -> bool {
+    x.is_finite() && x > 0.0
+}
+
+/// Index of the last bar strictly before `ts`, provided a bar at or after `ts` follows it.
+fn straddle(rows: &[Candle], ts: DateTime<Utc>) -> Option<usize> {
+    let after = rows.partition_point(|c| c.ts < ts);
+    (after > 0 && after < rows.len()).then(|| after - 1)
+}
+
+fn median(mut xs: Vec<f64>) -> f64 {
+    if xs.is_empty() {
+        return f64::NAN;
+    }
+    xs.sort_by(f64::total_cmp);
+    xs[xs.len() / 2]
+}
+
+/// Scale factor for a ratio that looks like a ~100x unit error, if any.
+fn unit_scale_factor(ratio: f64) -> Option<f64> {
+    if ratio > 50.0 && ratio < 200.0 {
+        Some(if (80.0..125.0).contains(&ratio) {
+            0.01
+        } else {
+            1.0 / ratio
+        })
+    } else if ratio > 0.0 && ratio < 0.02 {
+        Some(if (0.008..0.0125).contains(&ratio) {
+            100.0
+        } else {
+            1.0 / ratio
+        })
+    } else {
+        None
+    }
+}
+
+/// Whether `ratio` matches `expected` within the tolerance used for split detection.
+fn near_ratio(ratio: f64, expected: f64) -> bool {
+    ratio >= expected * 0.8 && ratio <= expected * 1.25
+}
+
+/* ---------------- repairs ---------------- */
+
+fn remove_duplicates(rows: &mut Vec<Candle>, log: &mut RepairLog) {
+    if rows.windows(2).all(|w| w[0].ts < w[1].ts) {
+        return;
+    }
+    rows.sort_by_key(|c| c.ts);
+
+    let mut out: Vec<Candle> = Vec::with_capacity(rows.len());
+    for c in rows.drain(..) {
+        if let Some(last) = out.last_mut()
+            && last.ts == c.ts
+        {
+            log.push(c.ts, RepairKind::DuplicateRemoved);
+            *last = c;
+        } else {
+            out.push(c);
+        }
+    }
+    *rows = out;
+}
+
+fn fill_zero_prices(rows: &mut [Candle], log: &mut RepairLog) {
+    for i in 0..rows.len() {
+        let close = price(&rows[i], PriceField::Close);
+        if close.is_finite() && close <= 0.0 {
+            let prev = rows[..i]
+                .iter()
+                .rev()
+                .map(|c| price(c, PriceField::Close))
+                .find(|&x| valid(x));
+            let next = || {
+                rows[i + 1..]
+                    .iter()
+                    .map(|c| price(c, PriceField::Close))
+                    .find(|&x| valid(x))
+            };
+            if let Some(fill) = prev.or_else(next) {
+                set_price(&mut rows[i], PriceField::Close, fill);
+                log.push(
+                    rows[i].ts,
+                    RepairKind::ZeroPriceFilled {
+                        field: PriceField::Close,
+                        value: fill,
+                    },
+                );
+            }
+        }
+
+        let close = price(&rows[i], PriceField::Close);
+        if !valid(close) {
+            continue;
+        }
+        for field in [PriceField::Open, PriceField::High, PriceField::Low] {
+            let v = price(&rows[i], field);
+            if v.is_finite() && v <= 0.0 {
+                set_price(&mut rows[i], field, close);
+                log.push(
+                    rows[i].ts,
+                    RepairKind::ZeroPriceFilled {
+                        field,
+                        value: close,
+                    },
+                );
+            }
+        }
+    }
+}
+
+fn repair_missing_split_adjustment(rows: &mut [Candle], actions: &[Action], log: &mut RepairLog) {
+    let mut splits: Vec<(DateTime<Utc>, f64)> = actions
+        .iter()
+        .filter_map(|a| match a {
+            Action::Split {
+                ts,
+                numerator,
+                denominator,
+            } if *denominator > 0 => Some((*ts, f64::from(*numerator) / f64::from(*denominator))),
+            _ => None,
+        })
+        // Ratios too close to 1 cannot be told apart from an ordinary price move.
+        .filter(|(_, r)| valid(*r) && (*r > 1.25 || *r < 0.8))
+        .collect();
+    splits.sort_by_key(|(ts, _)| *ts);
+
+    for (split_ts, r) in splits {
+        let Some(p) = straddle(rows, split_ts) else {
+            continue;
+        };
+        let before = price(&rows[p], PriceField::Close);
+        let after = price(&rows[p + 1], PriceField::Close);
+        if !(valid(before) && valid(after) && near_ratio(before / after, r)) {
+            continue;
+        }
+
+        // Yahoo sometimes leaves only the last few bars before the split unadjusted. The run
+        // starts right after an earlier jump that already reflects the split.
+        let start = (0..p)
+            .rev()
+            .find(|&j| {
+                let a = price(&rows[j], PriceField::Close);
+                let b = price(&rows[j + 1], PriceField::Close);
+                valid(a) && valid(b) && near_ratio(a / b, 1.0 / r)
+            })
+            .map_or(0, |j| j + 1);
+
+        let factor = 1.0 / r;
+        for c in &mut rows[start..=p] {
+            scale_prices(c, factor, true);
+            c.volume = c.volume.map(|v| {
+                #[allow(
+                    clippy::cast_precision_loss,
+                    clippy::cast_possible_truncation,
+                    clippy::cast_sign_loss
+                )]
+                let scaled = ((v as f64) * r).round() as u64;
+                scaled
+            });
+            log.push(c.ts, RepairKind::SplitAdjusted { factor });
+        }
+    }
+}
+
+fn repair_unit_scale(rows: &mut [Candle], log: &mut RepairLog) {
+    if rows.len() < 3 {
+        return;
+    }
+
+    for i in 0..rows.len() {
+        // Compare against the previous (already repaired) close. The first bar has none, so
+        // it uses the median of the opening closes, which a single bad bar cannot skew.
+        let reference = if i > 0 {
+            price(&rows[i - 1], PriceField::Close)
+        } else {
+            median(
+                rows[..rows.len().min(5)]
+                    .iter()
+                    .map(|c| price(c, PriceField::Close))
+                    .filter(|&x| valid(x))
+                    .collect(),
+            )
+        };
+        let close = price(&rows[i], PriceField::Close);
+        if valid(reference)
+            && valid(close)
+            && let Some(factor) = unit_scale_factor(close / reference)
+        {
+            scale_prices(&mut rows[i], factor, true);
+            log.push(rows[i].ts, RepairKind::UnitScaled { factor });
+        }
+
+        let close = price(&rows[i], PriceField::Close);
+        if !valid(close) {
+            continue;
+        }
+        for field in [PriceField::Open, PriceField::High, PriceField::Low] {
+            let v = price(&rows[i], field);
+            if valid(v)
+                && let Some(factor) = unit_scale_factor(v / close)
+            {
+                scale_money(slot(&mut rows[i], field), factor);
+                log.push(rows[i].ts, RepairKind::FieldUnitScaled { field, factor });
+            }
+        }
+    }
+}
+
+fn repair_missing_dividend_adjustment(
+    rows: &mut [Candle],
+    actions: &[Action],
+    log: &mut RepairLog,
+) {
+    let mut dividends: Vec<(DateTime<Utc>, f64)> = actions
+        .iter()
+        .filter_map(|a| match a {
+            Action::Dividend { ts, amount } => Some((*ts, money_to_f64(amount))),
+            _ => None,
+        })
+        .filter(|(_, amt)| valid(*amt))
+        .collect();
+    // Latest first, so each check compares against bars whose adjustment is already correct.
+    dividends.sort_by_key(|(ts, _)| std::cmp::Reverse(*ts));
+
+    let adj_ratio = |c: &Candle| {
+        let raw = c.close_unadj.as_ref().map(money_to_f64)?;
+        let adj = price(c, PriceField::Close);
+        (valid(raw) && valid(adj)).then(|| adj / raw)
+    };
+
+    for (ex_ts, amount) in dividends {
+        let Some(p) = straddle(rows, ex_ts) else {
+            continue;
+        };
+        let Some(raw_before) = rows[p].close_unadj.as_ref().map(money_to_f64) else {
+            continue;
+        };
+        if !valid(raw_before) {
+            continue;
+        }
+        let factor = 1.0 - amount / raw_before;
+        if !(factor > 0.0 && factor < 1.0 - 1e-4) {
+            continue;
+        }
+        let (Some(ratio_before), Some(ratio_after)) =
+            (adj_ratio(&rows[p]), adj_ratio(&rows[p + 1]))
+        else {
+            continue;
+        };
+
+        // A correctly adjusted series has ratio_before ≈ ratio_after * factor.
+        if (ratio_before / ratio_after - 1.0).abs() >= (1.0 - factor) / 2.0 {
+            continue;
+        }
+        for c in &mut rows[..=p] {
+            scale_prices(c, factor, false);
+            log.push(c.ts, RepairKind::DividendAdjusted { factor });
+        }
+    }
+}
+
+/// Bars before a zero-volume bar that form the range it is compared against.
+const STRAY_LOOKBACK: usize = 5;
+
+fn handle_stray_prints(rows: &mut Vec<Candle>, cfg: &RepairConfig, log: &mut RepairLog) {
+    // Series without any volume (indices, some FX pairs) carry no signal here.
+    if !rows.iter().any(|c| c.volume.is_some_and(|v| v > 0)) {
+        return;
+    }
+
+    let mut stray = vec![false; rows.len()];
+    for i in 1..rows.len() {
+        let c = &rows[i];
+        // Zero prices are missing data, not prints.
+        if c.volume != Some(0) || !PRICE_FIELDS.iter().all(|&f| valid(price(c, f))) {
+            continue;
+        }
+        let recent: Vec<&Candle> = rows[..i]
+            .iter()
+            .zip(&stray)
+            .rev()
+            .filter(|(p, s)| !**s && valid(price(p, PriceField::Close)))
+            .map(|(p, _)| p)
+            .take(STRAY_LOOKBACK)
+            .collect();
+        let Some(prev) = recent.first() else {
+            continue;
+        };
+        let pc = price(prev, PriceField::Close);
+        let (lo, hi) = recent.iter().fold((pc, pc), |(lo, hi), p| {
+            let l = price(p, PriceField::Low);
+            let h = price(p, PriceField::High);
+            (
+                if valid(l) { lo.min(l) } else { lo },
+                if valid(h) { hi.max(h) } else { hi },
+            )
+        });
+        stray[i] = PRICE_FIELDS.iter().any(|&f| {
+            let v = price(c, f);
+            (v > hi || v < lo) && (v - pc).abs() / pc > cfg.stray_print_threshold
+        });
+    }
+
+    if cfg.remove_stray_prints {
+        let mut flags = stray.into_iter();
+        rows.retain(|c| {
+            let remove = flags.next().unwrap_or(false);
+            if remove {
+                log.push(c.ts, RepairKind::StrayPrintRemoved);
+            }
+            !remove
+        });
+    } else {
+        for (c, _) in rows.iter().zip(stray).filter(|(_, s)| *s) {
+            log.push(c.ts, RepairKind::StrayPrintFlagged);
+        }
+    }
+}
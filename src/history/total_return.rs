@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use paft::market::action::Action;
+use paft::market::responses::history::HistoryResponse;
+use paft::money::Money;
+use rust_decimal::Decimal;
+
+use crate::core::YfError;
+
+/// When a distribution is reinvested into the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReinvestAt {
+    /// Reinvest at the close of the ex-date bar. (Default)
+    #[default]
+    ExDate,
+    /// Hold the distribution as cash from the ex-date and reinvest it at the close of the
+    /// pay-date bar.
+    ///
+    /// Yahoo's chart data has no pay dates, so each pay date is the ex-date plus
+    /// [`TotalReturnBuilder::pay_lag`] unless set explicitly with
+    /// [`TotalReturnBuilder::pay_date`].
+    PayDate,
+}
+
+/// One bar of a total-return index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TotalReturnPoint {
+    /// Timestamp of the bar.
+    pub ts: DateTime<Utc>,
+    /// Unadjusted close of the bar.
+    pub close: Money,
+    /// Index level: the value of the position, starting at the builder's base.
+    pub index: Decimal,
+    /// Shares held after any reinvestment on this bar.
+    pub shares: Decimal,
+    /// Distributions received but not yet reinvested (pay-date reinvestment only), in index
+    /// units. Included in `index`.
+    pub cash: Decimal,
+}
+
+/// A distribution that was reinvested into the index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reinvestment {
+    /// Ex-date of the distribution.
+    pub ex_date: DateTime<Utc>,
+    /// Timestamp of the bar whose close the distribution was reinvested at.
+    pub reinvested_at: DateTime<Utc>,
+    /// Amount per share before withholding tax.
+    pub gross: Decimal,
+    /// Amount per share after withholding tax.
+    pub net: Decimal,
+    /// Shares bought with the distribution.
+    pub shares_added: Decimal,
+}
+
+/// A dividend-reinvested index built by [`TotalReturnBuilder`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TotalReturnSeries {
+    /// Index level per bar, in timestamp order.
+    pub points: Vec<TotalReturnPoint>,
+    /// Every distribution that was reinvested, in the order it was reinvested.
+    pub reinvestments: Vec<Reinvestment>,
+}
+
+/// Builds a total-return index from a price history and its corporate actions.
+///
+/// The index starts at [`base`](Self::base) on the first bar and buys `base / close` shares.
+/// Each dividend (and, optionally, capital gain) pays its amount per share, less
+/// [`withholding_tax`](Self::withholding_tax), and is reinvested at the close of either the
+/// ex-date or the pay-date bar (see [`ReinvestAt`]).
+///
+/// Unadjusted closes are used: `close_unadj` for adjusted histories, `close` otherwise.
+/// Yahoo's closes and distribution amounts are both split-adjusted, so splits need no
+/// handling. Bars without a positive close are skipped.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), yfinance_rs::YfError> {
+/// use yfinance_rs::history::{ReinvestAt, TotalReturnBuilder};
+/// use yfinance_rs::{HistoryBuilder, YfClient};
+///
+/// let client = YfClient::default();
+/// let history = HistoryBuilder::new(&client, "KO").auto_adjust(false).fetch_full().await?;
+/// let tr = TotalReturnBuilder::new(&history)
+///     .reinvest_at(ReinvestAt::PayDate)
+///     .withholding_tax(rust_decimal::Decimal::new(15, 2))
+///     .build()?;
+/// println!("{:?}", tr.points.last().map(|p| p.index));
+/// # Ok(())
+/// # }
+/// ```
+pub struct TotalReturnBuilder<'a> {
+    history: &'a HistoryResponse,
+    reinvest_at: ReinvestAt,
+    pay_lag: Duration,
+    pay_dates: HashMap<DateTime<Utc>, DateTime<Utc>>,
+    withholding_tax: Decimal,
+    include_capital_gains: bool,
+    base: Decimal,
+}
+
+impl<'a> TotalReturnBuilder<'a> {
+    /// Creates a builder over `history`, which must include its actions.
+    #[must_use]
+    pub fn new(history: &'a HistoryResponse) -> Self {
+        Self {
+            history,
+            reinvest_at: ReinvestAt::ExDate,
+            pay_lag: Duration::zero(),
+            pay_dates: HashMap::new(),
+            withholding_tax: Decimal::ZERO,
+            include_capital_gains: true,
+            base: Decimal::ONE_HUNDRED,
+        }
+    }
+
+    /// Sets when distributions are reinvested. (Default: [`ReinvestAt::ExDate`])
+    #[must_use]
+    pub const fn reinvest_at(mut self, at: ReinvestAt) -> Self {
+        self.reinvest_at = at;
+        self
+    }
+
+    /// Sets the delay between ex-date and pay date used when no explicit pay date is known.
+    /// (Default: zero)
+    #[must_use]
+    pub const fn pay_lag(mut self, lag: Duration) -> Self {
+        self.pay_lag = lag;
+        self
+    }
+
+    /// Sets the pay date of the distribution that goes ex on `ex_date`.
+    #[must_use]
+    pub fn pay_date(mut self, ex_date: DateTime<Utc>, pay_date: DateTime<Utc>) -> Self {
+        self.pay_dates.insert(ex_date, pay_date);
+        self
+    }
+
+    /// Sets the fraction of each distribution withheld as tax, between 0 and 1. (Default: `0`)
+    #[must_use]
+    pub const fn withholding_tax(mut self, rate: Decimal) -> Self {
+        self.withholding_tax = rate;
+        self
+    }
+
+    /// Sets whether capital-gain distributions are reinvested like dividends. (Default: `true`)
+    #[must_use]
+    pub const fn include_capital_gains(mut self, yes: bool) -> Self {
+        self.include_capital_gains = yes;
+        self
+    }
+
+    /// Sets the index level on the first bar. (Default: `100`)
+    #[must_use]
+    pub const fn base(mut self, base: Decimal) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// Builds the index.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YfError::InvalidParams` if the withholding rate is outside `[0, 1]`, the base
+    /// is not positive, or the history is adjusted but has no unadjusted closes.
+    pub fn build(self) -> Result<TotalReturnSeries, YfError> {
+        if self.withholding_tax < Decimal::ZERO || self.withholding_tax > Decimal::ONE {
+            return Err(YfError::InvalidParams(
+                "withholding tax must be between 0 and 1".into(),
+            ));
+        }
+        if self.base <= Decimal::ZERO {
+            return Err(YfError::InvalidParams("base must be positive".into()));
+        }
+
+        let mut bars: Vec<(DateTime<Utc>, Money)> = Vec::with_capacity(self.history.candles.len());
+        for c in &self.history.candles {
+            let close = if self.history.adjusted {
+                c.close_unadj.clone().ok_or_else(|| {
+                    YfError::InvalidParams(
+                        "total return needs unadjusted closes; fetch with auto_adjust(false) \
+                         or keep close_unadj"
+                            .into(),
+                    )
+                })?
+            } else {
+                c.close.clone()
+            };
+            if close.amount() > Decimal::ZERO {
+                bars.push((c.ts, close));
+            }
+        }
+
+        let keep_tax = Decimal::ONE - self.withholding_tax;
+        // (index of the ex-date bar, ex-date, gross amount per share)
+        let mut ex_events: Vec<(usize, DateTime<Utc>, Decimal)> = Vec::new();
+        for a in &self.history.actions {
+            let (ts, amount) = match a {
+                Action::Dividend { ts, amount } => (*ts, amount.amount()),
+                Action::CapitalGain { ts, gain } if self.include_capital_gains => {
+                    (*ts, gain.amount())
+                }
+                _ => continue,
+            };
+            if amount <= Decimal::ZERO || bars.first().is_none_or(|(first, _)| ts <= *first) {
+                continue;
+            }
+            if let Some(ex_idx) = bar_at_or_after(&bars, ts) {
+                ex_events.push((ex_idx, ts, amount));
+            }
+        }
+        ex_events.sort_by_key(|(_, ts, _)| *ts);
+
+        let mut points = Vec::with_capacity(bars.len());
+        let mut reinvestments = Vec::new();
+        let mut pending: Vec<Pending> = Vec::new();
+        let mut shares = Decimal::ZERO;
+        let mut cash = Decimal::ZERO;
+        let mut next_ex = 0;
+
+        for (i, (ts, close)) in bars.iter().enumerate() {
+            let price = close.amount();
+            if i == 0 {
+                shares = self.base / price;
+            }
+
+            // Distributions are owed on the shares held going into the ex-date.
+            let held = shares;
+            while next_ex < ex_events.len() && ex_events[next_ex].0 == i {
+                let (_, ex_date, gross) = ex_events[next_ex];
+                next_ex += 1;
+                let at = match self.reinvest_at {
+                    ReinvestAt::ExDate => i,
+                    ReinvestAt::PayDate => {
+                        let pay = self
+                            .pay_dates
+                            .get(&ex_date)
+                            .copied()
+                            .unwrap_or(ex_date + self.pay_lag);
+                        // Not yet paid by the end of the history: stays as cash.
+                        bar_at_or_after(&bars, pay).map_or(usize::MAX, |p| p.max(i))
+                    }
+                };
+                let proceeds = held * gross * keep_tax;
+                if at != i {
+                    cash += proceeds;
+                }
+                pending.push(Pending {
+                    at,
+                    ex_date,
+                    gross,
+                    proceeds,
+                    as_cash: at != i,
+                });
+            }
+
+            let mut k = 0;
+            while k < pending.len() {
+                if pending[k].at != i {
+                    k += 1;
+                    continue;
+                }
+                let p = pending.remove(k);
+                if p.as_cash {
+                    cash -= p.proceeds;
+                }
+                let added = p.proceeds / price;
+                shares += added;
+                reinvestments.push(Reinvestment {
+                    ex_date: p.ex_date,
+                    reinvested_at: *ts,
+                    gross: p.gross,
+                    net: p.gross * keep_tax,
+                    shares_added: added,
+                });
+            }
+
+            points.push(TotalReturnPoint {
+                ts: *ts,
+                close: close.clone(),
+                index: shares * price + cash,
+                shares,
+                cash,
+            });
+        }
+
+        Ok(TotalReturnSeries {
+            points,
+            reinvestments,
+        })
+    }
+}
+
+struct Pending {
+    at: usize,
+    ex_date: DateTime<Utc>,
+    gross: Decimal,
+    proceeds: Decimal,
+    as_cash: bool,
+}
+
+fn bar_at_or_after(bars: &[(DateTime<Utc>, Money)], ts: DateTime<Utc>) -> Option<usize> {
+    let idx = bars.partition_point(|(t, _)| *t < ts);
+    (idx < bars.len()).then_some(idx)
+}
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use paft::market::responses::history::HistoryResponse;
+use paft::money::Money;
+use rust_decimal::RoundingStrategy;
+
+use crate::core::YfError;
+use crate::history::repair::{RepairConfig, RepairLog, repair_history_skipping};
+use crate::history::validate::{ValidationMode, ValidationReport, enforce_validation};
+
+/// A post-processing step applied to a fetched price series.
+///
+/// Transforms added with [`HistoryBuilder::transform`](crate::HistoryBuilder::transform) or
+/// [`DownloadBuilder::transform`](crate::DownloadBuilder::transform) run in the order they were
+/// added, after the built-in repair, validation, back-adjustment and rounding stages. Any
+/// `Fn(&mut HistoryResponse)` closure is a transform.
+pub trait CandleTransform: Send + Sync {
+    /// Transforms the series in place.
+    fn apply(&self, history: &mut HistoryResponse);
+}
+
+impl<F> CandleTransform for F
+where
+    F: Fn(&mut HistoryResponse) + Send + Sync,
+{
+    fn apply(&self, history: &mut HistoryResponse) {
+        self(history);
+    }
+}
+
+/// Replaces each adjusted close with the raw, unadjusted close.
+///
+/// Open, high and low keep their adjustment. Bars without an unadjusted close are left as is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackAdjust;
+
+impl CandleTransform for BackAdjust {
+    fn apply(&self, history: &mut HistoryResponse) {
+        for c in &mut history.candles {
+            if let Some(raw) = c.close_unadj.as_ref() {
+                c.close = raw.clone();
+            }
+        }
+    }
+}
+
+/// Rounds open, high, low and close in `Decimal` to a fixed number of places.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rounding {
+    /// Number of decimal places kept.
+    pub places: u32,
+    /// How midpoints are resolved.
+    pub strategy: RoundingStrategy,
+}
+
+impl Rounding {
+    /// Creates a rounding transform.
+    #[must_use]
+    pub const fn new(places: u32, strategy: RoundingStrategy) -> Self {
+        Self { places, strategy }
+    }
+}
+
+impl Default for Rounding {
+    /// Two places, with midpoints rounded away from zero.
+    fn default() -> Self {
+        Self::new(2, RoundingStrategy::MidpointAwayFromZero)
+    }
+}
+
+impl CandleTransform for Rounding {
+    fn apply(&self, history: &mut HistoryResponse) {
+        let round = |m: &Money| {
+            Money::new(
+                m.amount()
+                    .round_dp_with_strategy(self.places, self.strategy),
+                m.currency().clone(),
+            )
+            .expect("currency metadata available")
+        };
+        for c in &mut history.candles {
+            c.open = round(&c.open);
+            c.high = round(&c.high);
+            c.low = round(&c.low);
+            c.close = round(&c.close);
+        }
+    }
+}
+
+/// The post-processing stages shared by [`HistoryBuilder`](crate::HistoryBuilder) and
+/// [`DownloadBuilder`](crate::DownloadBuilder).
+///
+/// Stages run in a fixed order: repair, validation, back-adjustment, rounding, then the
+/// user transforms in insertion order.
+#[derive(Clone, Default)]
+pub(crate) struct TransformPipeline {
+    pub(crate) repair: bool,
+    pub(crate) repair_config: RepairConfig,
+    pub(crate) validation: ValidationMode,
+    pub(crate) back_adjust: bool,
+    pub(crate) rounding: bool,
+    pub(crate) rounding_spec: Rounding,
+    pub(crate) custom: Vec<Arc<dyn CandleTransform>>,
+}
+
+impl TransformPipeline {
+    /// Runs every stage; `missing` lists the bars kept by `keepna`, which repair leaves alone.
+    pub(crate) fn run(
+        &self,
+        history: &mut HistoryResponse,
+        missing: &[DateTime<Utc>],
+    ) -> Result<(RepairLog, ValidationReport), YfError> {
+        let log = if self.repair {
+            repair_history_skipping(history, &self.repair_config, missing)
+        } else {
+            RepairLog::default()
+        };
+        let report = enforce_validation(history, self.validation)?;
+
+        if self.back_adjust {
+            BackAdjust.apply(history);
+        }
+        if self.rounding {
+            self.rounding_spec.apply(history);
+        }
+        for t in &self.custom {
+            t.apply(history);
+        }
+        Ok((log, report))
+    }
+}
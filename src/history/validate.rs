@@ -0,0 +1,240 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use paft::market::responses::history::HistoryResponse;
+
+use crate::core::YfError;
+use crate::core::conversions::money_to_f64;
+use crate::history::PriceField;
+
+/// Relative slack allowed when checking that open and close lie within `[low, high]`, so bars
+/// scaled by an adjustment factor are not flagged for rounding noise.
+const RANGE_TOLERANCE: f64 = 1e-9;
+
+/// How strictly [`HistoryBuilder`](crate::HistoryBuilder) treats bars that fail validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValidationMode {
+    /// Do not validate. (Default)
+    #[default]
+    Off,
+    /// Report anomalies but return the series unchanged.
+    Warn,
+    /// Report anomalies and remove every offending bar.
+    Drop,
+    /// Fail the request with [`YfError::Validation`] if any anomaly is found.
+    Error,
+}
+
+/// An integrity rule a bar can violate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValidationRule {
+    /// The high is below the low.
+    HighBelowLow,
+    /// The open lies outside `[low, high]`.
+    OpenOutsideRange,
+    /// The close lies outside `[low, high]`.
+    CloseOutsideRange,
+    /// The price is negative.
+    NegativePrice(PriceField),
+    /// The price is missing. Yahoo nulls (kept by `keepna`) are carried as zero.
+    MissingPrice(PriceField),
+    /// The bar has the same timestamp as the previous one.
+    DuplicateTimestamp,
+    /// The bar's timestamp is earlier than a previous bar's.
+    NonMonotonicTimestamp,
+}
+
+impl fmt::Display for ValidationRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HighBelowLow => f.write_str("high below low"),
+            Self::OpenOutsideRange => f.write_str("open outside [low, high]"),
+            Self::CloseOutsideRange => f.write_str("close outside [low, high]"),
+            Self::NegativePrice(field) => write!(f, "negative {field:?}"),
+            Self::MissingPrice(field) => write!(f, "missing {field:?}"),
+            Self::DuplicateTimestamp => f.write_str("duplicate timestamp"),
+            Self::NonMonotonicTimestamp => f.write_str("non-monotonic timestamp"),
+        }
+    }
+}
+
+/// A single rule violation found by [`validate_history`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    /// Index of the offending bar in the validated series.
+    pub index: usize,
+    /// Timestamp of the offending bar.
+    pub ts: DateTime<Utc>,
+    /// The rule the bar violates.
+    pub rule: ValidationRule,
+}
+
+/// Every anomaly found in a price series, ordered by bar.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// The individual violations. A bar can appear more than once, once per rule.
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if no anomaly was found.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns the number of violations found.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.issues.len()
+    }
+
+    /// Iterates over the violations found.
+    pub fn iter(&self) -> std::slice::Iter<'_, ValidationIssue> {
+        self.issues.iter()
+    }
+
+    /// Returns the indices of the offending bars, sorted and without duplicates.
+    #[must_use]
+    pub fn bars(&self) -> Vec<usize> {
+        let mut out: Vec<usize> = self.issues.iter().map(|i| i.index).collect();
+        out.sort_unstable();
+        out.dedup();
+        out
+    }
+
+    fn push(&mut self, index: usize, ts: DateTime<Utc>, rule: ValidationRule) {
+        self.issues.push(ValidationIssue { index, ts, rule });
+    }
+}
+
+impl<'a> IntoIterator for &'a ValidationReport {
+    type Item = &'a ValidationIssue;
+    type IntoIter = std::slice::Iter<'a, ValidationIssue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.issues.iter()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(first) = self.issues.first() else {
+            return f.write_str("no anomalies");
+        };
+        write!(
+            f,
+            "{} anomalies in {} bars (first: {} at {})",
+            self.issues.len(),
+            self.bars().len(),
+            first.rule,
+            first.ts
+        )
+    }
+}
+
+/// Checks every bar of `history` for OHLC and timestamp integrity.
+///
+/// The series is not modified. Bars are checked for:
+/// - negative or missing (zero) prices,
+/// - a high below the low,
+/// - an open or close outside `[low, high]`,
+/// - timestamps that repeat or go backwards.
+///
+/// Range checks are skipped for bars with a missing or negative price, since those are
+/// already reported.
+#[must_use]
+pub fn validate_history(history: &HistoryResponse) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let mut latest: Option<DateTime<Utc>> = None;
+
+    for (i, c) in history.candles.iter().enumerate() {
+        if let Some(prev) = latest {
+            if c.ts == prev {
+                report.push(i, c.ts, ValidationRule::DuplicateTimestamp);
+            } else if c.ts < prev {
+                report.push(i, c.ts, ValidationRule::NonMonotonicTimestamp);
+            }
+        }
+        latest = Some(latest.map_or(c.ts, |p| p.max(c.ts)));
+
+        let prices = [
+            (PriceField::Open, &c.open),
+            (PriceField::High, &c.high),
+            (PriceField::Low, &c.low),
+            (PriceField::Close, &c.close),
+        ];
+        let mut priced = true;
+        for (field, m) in prices {
+            if m.amount().is_sign_negative() && !m.amount().is_zero() {
+                report.push(i, c.ts, ValidationRule::NegativePrice(field));
+                priced = false;
+            } else if m.amount().is_zero() {
+                report.push(i, c.ts, ValidationRule::MissingPrice(field));
+                priced = false;
+            }
+        }
+        if !priced {
+            continue;
+        }
+
+        let (open, high, low, close) = (
+            money_to_f64(&c.open),
+            money_to_f64(&c.high),
+            money_to_f64(&c.low),
+            money_to_f64(&c.close),
+        );
+        let tol = high.abs().max(low.abs()) * RANGE_TOLERANCE;
+        if high < low - tol {
+            report.push(i, c.ts, ValidationRule::HighBelowLow);
+            continue;
+        }
+        let outside = |v: f64| v < low - tol || v > high + tol;
+        if outside(open) {
+            report.push(i, c.ts, ValidationRule::OpenOutsideRange);
+        }
+        if outside(close) {
+            report.push(i, c.ts, ValidationRule::CloseOutsideRange);
+        }
+    }
+
+    report
+}
+
+/// Validates `history` and applies `mode` to the result.
+///
+/// With [`ValidationMode::Drop`] the offending bars are removed from `history`; the report's
+/// indices refer to the series as it was before dropping. [`ValidationMode::Off`] skips the
+/// checks and returns an empty report.
+///
+/// # Errors
+///
+/// Returns [`YfError::Validation`] in [`ValidationMode::Error`] if any anomaly is found.
+pub fn enforce_validation(
+    history: &mut HistoryResponse,
+    mode: ValidationMode,
+) -> Result<ValidationReport, YfError> {
+    if mode == ValidationMode::Off {
+        return Ok(ValidationReport::default());
+    }
+
+    let report = validate_history(history);
+    if report.is_empty() {
+        return Ok(report);
+    }
+
+    match mode {
+        ValidationMode::Off | ValidationMode::Warn => {}
+        ValidationMode::Drop => {
+            let bad = report.bars();
+            let mut idx = 0;
+            history.candles.retain(|_| {
+                let keep = bad.binary_search(&idx).is_err();
+                idx += 1;
+                keep
+            });
+        }
+        ValidationMode::Error => return Err(YfError::Validation(Box::new(report))),
+    }
+    Ok(report)
+}
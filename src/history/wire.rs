@@ -1,7 +1,10 @@
+use rust_decimal::Decimal;
 use serde::Deserialize;
 use serde::Deserializer;
 use std::collections::BTreeMap;
 
+use crate::core::wire::{de_opt_decimal_lenient, de_vec_opt_decimal_lenient};
+
 #[derive(Deserialize)]
 pub struct ChartEnvelope {
     pub(crate) chart: Option<ChartNode>,
@@ -38,6 +41,16 @@ pub struct MetaNode {
     pub(crate) gmtoffset: Option<i64>,
     #[serde(default)]
     pub(crate) currency: Option<String>,
+    #[serde(
+        default,
+        rename = "chartPreviousClose",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) chart_previous_close: Option<Decimal>,
+    #[serde(default, rename = "instrumentType")]
+    pub(crate) instrument_type: Option<String>,
+    #[serde(default, rename = "fullExchangeName")]
+    pub(crate) full_exchange_name: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -50,22 +63,22 @@ pub struct Indicators {
 
 #[derive(Deserialize, Clone)]
 pub struct QuoteBlock {
-    #[serde(default)]
-    pub(crate) open: Vec<Option<f64>>,
-    #[serde(default)]
-    pub(crate) high: Vec<Option<f64>>,
-    #[serde(default)]
-    pub(crate) low: Vec<Option<f64>>,
-    #[serde(default)]
-    pub(crate) close: Vec<Option<f64>>,
+    #[serde(default, deserialize_with = "de_vec_opt_decimal_lenient")]
+    pub(crate) open: Vec<Option<Decimal>>,
+    #[serde(default, deserialize_with = "de_vec_opt_decimal_lenient")]
+    pub(crate) high: Vec<Option<Decimal>>,
+    #[serde(default, deserialize_with = "de_vec_opt_decimal_lenient")]
+    pub(crate) low: Vec<Option<Decimal>>,
+    #[serde(default, deserialize_with = "de_vec_opt_decimal_lenient")]
+    pub(crate) close: Vec<Option<Decimal>>,
     #[serde(default)]
     pub(crate) volume: Vec<Option<u64>>,
 }
 
 #[derive(Deserialize, Clone)]
 pub struct AdjCloseBlock {
-    #[serde(default)]
-    pub(crate) adjclose: Vec<Option<f64>>,
+    #[serde(default, deserialize_with = "de_vec_opt_decimal_lenient")]
+    pub(crate) adjclose: Vec<Option<Decimal>>,
 }
 
 #[derive(Deserialize, Default, Clone)]
@@ -76,11 +89,14 @@ pub struct Events {
     pub(crate) splits: Option<BTreeMap<String, SplitEvent>>,
     #[serde(default, rename = "capitalGains")]
     pub(crate) capital_gains: Option<BTreeMap<String, CapitalGainEvent>>,
+    #[serde(default)]
+    pub(crate) earnings: Option<BTreeMap<String, EarningsWireEvent>>,
 }
 
 #[derive(Deserialize, Clone)]
 pub struct DividendEvent {
-    pub(crate) amount: Option<f64>,
+    #[serde(default, deserialize_with = "de_opt_decimal_lenient")]
+    pub(crate) amount: Option<Decimal>,
     pub(crate) date: Option<i64>,
 }
 
@@ -97,8 +113,26 @@ pub struct SplitEvent {
 
 #[derive(Deserialize, Clone)]
 pub struct CapitalGainEvent {
-    pub(crate) amount: Option<f64>,
+    #[serde(default, deserialize_with = "de_opt_decimal_lenient")]
+    pub(crate) amount: Option<Decimal>,
+    pub(crate) date: Option<i64>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct EarningsWireEvent {
     pub(crate) date: Option<i64>,
+    #[serde(
+        default,
+        rename = "epsActual",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) eps_actual: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "epsEstimate",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) eps_estimate: Option<Decimal>,
 }
 
 /// Accepts u64, integer-like f64 (e.g., 4.0), numeric strings ("4"), or null/missing.
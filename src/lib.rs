@@ -64,6 +64,7 @@
 //! ### Advanced Features
 //! * **Data Repair**: Automatic detection and repair of price outliers.
 //! * **Data Rounding**: Control price precision and rounding.
+//! * **Currency Conversion**: Convert prices, histories, option chains and statements with Yahoo FX rates.
 //! * **Missing Data Handling**: Configurable handling of NA/missing values.
 //! * **Back Adjustment**: Alternative price adjustment methods.
 //! * **Historical Metadata**: Timezone and other metadata for historical data.
@@ -130,6 +131,8 @@ pub mod download;
 pub mod esg;
 /// Fetch financial statements (income, balance sheet, cash flow) and earnings data.
 pub mod fundamentals;
+/// Convert `Money` values between currencies using Yahoo FX rates.
+pub mod fx;
 /// Fetch historical OHLCV data for a single symbol.
 pub mod history;
 /// Fetch holder information, including major, institutional, and insider holders.
@@ -142,6 +145,8 @@ pub mod profile;
 pub mod quote;
 /// Search for tickers by name or keyword.
 pub mod search;
+/// Fetch close-price series for many symbols per request via the spark endpoint.
+pub mod spark;
 /// Stream real-time quote updates via `WebSockets` or polling.
 pub mod stream;
 /// A high-level interface for a single ticker, providing access to all data types.
@@ -153,16 +158,18 @@ pub use core::client::ApiPreference;
 pub use core::{CacheMode, RetryConfig, YfClient, YfClientBuilder, YfError};
 
 // Provider-specific builders and utilities
-pub use download::DownloadBuilder;
+pub use download::{DownloadBuilder, DownloadProgress, PartialDownload};
 pub use esg::EsgBuilder;
 pub use fundamentals::FundamentalsBuilder;
-pub use history::HistoryBuilder;
+pub use fx::FxConverter;
+pub use history::{AsOfBuilder, HistoryBuilder, HistoryUpdate, Restatement};
 pub use holders::HoldersBuilder;
 pub use news::{NewsBuilder, NewsTab};
 pub use paft::market::responses::download::{DownloadEntry, DownloadResponse};
-pub use quote::{QuotesBuilder, quotes};
+pub use quote::{QuoteBatch, QuoteDetail, QuoteField, QuotesBuilder, quotes};
 pub use search::{SearchBuilder, search};
-pub use stream::{StreamBuilder, StreamConfig, StreamHandle, StreamMethod};
+pub use spark::{SparkBuilder, SparkPoint, SparkSeries};
+pub use stream::{QuoteUpdateDetail, StreamBuilder, StreamConfig, StreamHandle, StreamMethod};
 pub use ticker::{FastInfo, Info, Ticker};
 
 /// Initialize a default tracing subscriber for tests/examples when the
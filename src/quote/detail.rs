@@ -0,0 +1,104 @@
+use chrono::{DateTime, Utc};
+use paft::market::quote::Quote;
+use paft::money::Money;
+use rust_decimal::Decimal;
+
+/// Price and change for one trading session of a [`QuoteDetail`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarketSession {
+    /// Last price in the session.
+    pub price: Option<Money>,
+    /// Change against the previous close.
+    pub change: Option<Money>,
+    /// Change against the previous close, in percent.
+    pub change_percent: Option<Decimal>,
+    /// Time of the last price.
+    pub time: Option<DateTime<Utc>>,
+}
+
+/// A quote with the full set of fields returned by the v7 quote endpoint.
+///
+/// [`quote`](Self::quote) holds the fields every quote carries; everything else is `None` when
+/// Yahoo does not report it for the instrument. Prices are in the quote currency and follow
+/// [`YfClientBuilder::normalize_minor_units`](crate::YfClientBuilder::normalize_minor_units).
+/// Market cap, valuation ratios, EPS, book value and dividend figures are passed through as
+/// reported, since Yahoo quotes some of them in the financial rather than the trading currency.
+#[derive(Debug, Clone)]
+pub struct QuoteDetail {
+    /// The core quote: instrument, name, price, previous close, volume, exchange and market state.
+    pub quote: Quote,
+    /// Full name of the instrument.
+    pub long_name: Option<String>,
+    /// IANA timezone of the exchange, e.g. `America/New_York`.
+    pub exchange_timezone: Option<String>,
+
+    /// Opening price of the regular session.
+    pub open: Option<Money>,
+    /// High of the regular session.
+    pub day_high: Option<Money>,
+    /// Low of the regular session.
+    pub day_low: Option<Money>,
+    /// Regular session price, change and time.
+    pub regular: MarketSession,
+    /// Pre-market price, change and time.
+    pub pre_market: MarketSession,
+    /// Post-market price, change and time.
+    pub post_market: MarketSession,
+
+    /// Best bid.
+    pub bid: Option<Money>,
+    /// Best ask.
+    pub ask: Option<Money>,
+    /// Size at the best bid.
+    pub bid_size: Option<u64>,
+    /// Size at the best ask.
+    pub ask_size: Option<u64>,
+
+    /// 52-week high.
+    pub fifty_two_week_high: Option<Money>,
+    /// 52-week low.
+    pub fifty_two_week_low: Option<Money>,
+    /// 50-day average price.
+    pub fifty_day_average: Option<Money>,
+    /// 200-day average price.
+    pub two_hundred_day_average: Option<Money>,
+    /// Average daily volume over three months.
+    pub average_volume_3_month: Option<u64>,
+    /// Average daily volume over ten days.
+    pub average_volume_10_day: Option<u64>,
+
+    /// Market capitalization.
+    pub market_cap: Option<Decimal>,
+    /// Shares outstanding.
+    pub shares_outstanding: Option<u64>,
+    /// Trailing price-to-earnings ratio.
+    pub trailing_pe: Option<Decimal>,
+    /// Forward price-to-earnings ratio.
+    pub forward_pe: Option<Decimal>,
+    /// Earnings per share over the trailing twelve months.
+    pub eps_trailing_twelve_months: Option<Decimal>,
+    /// Forward earnings per share estimate.
+    pub eps_forward: Option<Decimal>,
+    /// Price-to-book ratio.
+    pub price_to_book: Option<Decimal>,
+    /// Book value per share.
+    pub book_value: Option<Decimal>,
+    /// Forward annual dividend per share.
+    pub dividend_rate: Option<Decimal>,
+    /// Forward annual dividend yield, in percent.
+    pub dividend_yield: Option<Decimal>,
+    /// Dividends per share paid over the trailing twelve months.
+    pub trailing_annual_dividend_rate: Option<Decimal>,
+    /// Trailing-twelve-month dividend yield, as a fraction.
+    pub trailing_annual_dividend_yield: Option<Decimal>,
+}
+
+impl QuoteDetail {
+    /// Ask minus bid, if both are quoted and positive.
+    #[must_use]
+    pub fn spread(&self) -> Option<Decimal> {
+        let bid = self.bid.as_ref()?.amount();
+        let ask = self.ask.as_ref()?.amount();
+        (bid > Decimal::ZERO && ask > Decimal::ZERO).then(|| ask - bid)
+    }
+}
@@ -0,0 +1,245 @@
+use crate::core::quotes::V7QuoteNode;
+
+/// A group of v7 quote fields that can be requested with [`QuotesBuilder::fields`](crate::QuotesBuilder::fields).
+///
+/// Each variant maps to one or more of Yahoo's `fields=` names. The symbol, quote type,
+/// currency and exchange are always requested, since every quote needs them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuoteField {
+    /// Short display name (`shortName`).
+    ShortName,
+    /// Full name (`longName`).
+    LongName,
+    /// Regular-session price (`regularMarketPrice`).
+    Price,
+    /// Previous close (`regularMarketPreviousClose`).
+    PreviousClose,
+    /// Regular-session volume (`regularMarketVolume`).
+    Volume,
+    /// Market state, e.g. pre-market or regular (`marketState`).
+    MarketState,
+    /// IANA timezone of the exchange (`exchangeTimezoneName`).
+    ExchangeTimezone,
+    /// Regular-session open, high and low.
+    DayRange,
+    /// Regular-session change, change percent and time.
+    Change,
+    /// Pre-market price, change, change percent and time.
+    PreMarket,
+    /// Post-market price, change, change percent and time.
+    PostMarket,
+    /// Best bid, best ask and their sizes.
+    BidAsk,
+    /// 52-week high and low.
+    FiftyTwoWeekRange,
+    /// 50-day and 200-day average prices.
+    MovingAverages,
+    /// Three-month and ten-day average volumes.
+    AverageVolume,
+    /// Market capitalization (`marketCap`).
+    MarketCap,
+    /// Shares outstanding (`sharesOutstanding`).
+    SharesOutstanding,
+    /// Trailing and forward price-to-earnings ratios.
+    PriceToEarnings,
+    /// Trailing and forward earnings per share.
+    EarningsPerShare,
+    /// Price-to-book ratio and book value per share.
+    BookValue,
+    /// Forward and trailing dividend rate and yield.
+    Dividends,
+}
+
+/// Fields requested whatever the selection: they identify the instrument and price currency.
+const IDENTITY: &[&str] = &[
+    "symbol",
+    "quoteType",
+    "currency",
+    "exchange",
+    "fullExchangeName",
+    "market",
+];
+
+impl QuoteField {
+    /// Every field group, in a stable order.
+    pub const ALL: [Self; 21] = [
+        Self::ShortName,
+        Self::LongName,
+        Self::Price,
+        Self::PreviousClose,
+        Self::Volume,
+        Self::MarketState,
+        Self::ExchangeTimezone,
+        Self::DayRange,
+        Self::Change,
+        Self::PreMarket,
+        Self::PostMarket,
+        Self::BidAsk,
+        Self::FiftyTwoWeekRange,
+        Self::MovingAverages,
+        Self::AverageVolume,
+        Self::MarketCap,
+        Self::SharesOutstanding,
+        Self::PriceToEarnings,
+        Self::EarningsPerShare,
+        Self::BookValue,
+        Self::Dividends,
+    ];
+
+    /// The names Yahoo uses for this group in the `fields=` parameter.
+    #[must_use]
+    pub const fn wire_names(self) -> &'static [&'static str] {
+        match self {
+            Self::ShortName => &["shortName"],
+            Self::LongName => &["longName"],
+            Self::Price => &["regularMarketPrice"],
+            Self::PreviousClose => &["regularMarketPreviousClose"],
+            Self::Volume => &["regularMarketVolume"],
+            Self::MarketState => &["marketState"],
+            Self::ExchangeTimezone => &["exchangeTimezoneName"],
+            Self::DayRange => &[
+                "regularMarketOpen",
+                "regularMarketDayHigh",
+                "regularMarketDayLow",
+            ],
+            Self::Change => &[
+                "regularMarketChange",
+                "regularMarketChangePercent",
+                "regularMarketTime",
+            ],
+            Self::PreMarket => &[
+                "preMarketPrice",
+                "preMarketChange",
+                "preMarketChangePercent",
+                "preMarketTime",
+            ],
+            Self::PostMarket => &[
+                "postMarketPrice",
+                "postMarketChange",
+                "postMarketChangePercent",
+                "postMarketTime",
+            ],
+            Self::BidAsk => &["bid", "ask", "bidSize", "askSize"],
+            Self::FiftyTwoWeekRange => &["fiftyTwoWeekHigh", "fiftyTwoWeekLow"],
+            Self::MovingAverages => &["fiftyDayAverage", "twoHundredDayAverage"],
+            Self::AverageVolume => &["averageDailyVolume3Month", "averageDailyVolume10Day"],
+            Self::MarketCap => &["marketCap"],
+            Self::SharesOutstanding => &["sharesOutstanding"],
+            Self::PriceToEarnings => &["trailingPE", "forwardPE"],
+            Self::EarningsPerShare => &["epsTrailingTwelveMonths", "epsForward"],
+            Self::BookValue => &["priceToBook", "bookValue"],
+            Self::Dividends => &[
+                "dividendRate",
+                "dividendYield",
+                "trailingAnnualDividendRate",
+                "trailingAnnualDividendYield",
+            ],
+        }
+    }
+
+    /// Clears this group on a node Yahoo returned it on without being asked.
+    fn clear(self, n: &mut V7QuoteNode) {
+        match self {
+            Self::ShortName => n.short_name = None,
+            Self::LongName => n.long_name = None,
+            Self::Price => n.regular_market_price = None,
+            Self::PreviousClose => n.regular_market_previous_close = None,
+            Self::Volume => n.regular_market_volume = None,
+            Self::MarketState => n.market_state = None,
+            Self::ExchangeTimezone => n.exchange_timezone_name = None,
+            Self::DayRange => {
+                n.regular_market_open = None;
+                n.regular_market_day_high = None;
+                n.regular_market_day_low = None;
+            }
+            Self::Change => {
+                n.regular_market_change = None;
+                n.regular_market_change_percent = None;
+                n.regular_market_time = None;
+            }
+            Self::PreMarket => {
+                n.pre_market_price = None;
+                n.pre_market_change = None;
+                n.pre_market_change_percent = None;
+                n.pre_market_time = None;
+            }
+            Self::PostMarket => {
+                n.post_market_price = None;
+                n.post_market_change = None;
+                n.post_market_change_percent = None;
+                n.post_market_time = None;
+            }
+            Self::BidAsk => {
+                n.bid = None;
+                n.ask = None;
+                n.bid_size = None;
+                n.ask_size = None;
+            }
+            Self::FiftyTwoWeekRange => {
+                n.fifty_two_week_high = None;
+                n.fifty_two_week_low = None;
+            }
+            Self::MovingAverages => {
+                n.fifty_day_average = None;
+                n.two_hundred_day_average = None;
+            }
+            Self::AverageVolume => {
+                n.average_daily_volume_3_month = None;
+                n.average_daily_volume_10_day = None;
+            }
+            Self::MarketCap => n.market_cap = None,
+            Self::SharesOutstanding => n.shares_outstanding = None,
+            Self::PriceToEarnings => {
+                n.trailing_pe = None;
+                n.forward_pe = None;
+            }
+            Self::EarningsPerShare => {
+                n.eps_trailing_twelve_months = None;
+                n.eps_forward = None;
+            }
+            Self::BookValue => {
+                n.price_to_book = None;
+                n.book_value = None;
+            }
+            Self::Dividends => {
+                n.dividend_rate = None;
+                n.dividend_yield = None;
+                n.trailing_annual_dividend_rate = None;
+                n.trailing_annual_dividend_yield = None;
+            }
+        }
+    }
+}
+
+/// The `fields=` value for a selection, or `None` to request Yahoo's default payload.
+///
+/// Names are emitted in a fixed order so equal selections share a cache entry.
+pub(crate) fn wire_fields(selected: &[QuoteField]) -> Option<String> {
+    if selected.is_empty() {
+        return None;
+    }
+    let names: Vec<&str> = IDENTITY
+        .iter()
+        .copied()
+        .chain(
+            QuoteField::ALL
+                .into_iter()
+                .filter(|f| selected.contains(f))
+                .flat_map(QuoteField::wire_names)
+                .copied(),
+        )
+        .collect();
+    Some(names.join(","))
+}
+
+/// Empties every field group outside `selected`; an empty selection keeps everything.
+pub(crate) fn retain_fields(node: &mut V7QuoteNode, selected: &[QuoteField]) {
+    if selected.is_empty() {
+        return;
+    }
+    for f in QuoteField::ALL {
+        if !selected.contains(&f) {
+            f.clear(node);
+        }
+    }
+}
@@ -1,3 +1,9 @@
+mod detail;
+mod fields;
+
+pub use detail::{MarketSession, QuoteDetail};
+pub use fields::QuoteField;
+
 use crate::core::client::CacheMode;
 use crate::core::client::RetryConfig;
 use crate::core::{Quote, YfClient, YfError, quotes as core_quotes};
@@ -19,10 +25,27 @@ where
         .await
 }
 
+/// Quotes fetched by [`QuotesBuilder::fetch_batch`] or [`QuotesBuilder::fetch_detailed_batch`].
+#[derive(Debug)]
+pub struct QuoteBatch<Q = Quote> {
+    /// Quotes in the order their symbols were first requested.
+    pub quotes: Vec<Q>,
+    /// Requested symbols Yahoo returned no quote for, in request order.
+    pub missing: Vec<String>,
+    /// Requests that failed, each with the symbols it carried. These symbols are not listed
+    /// in [`missing`](Self::missing).
+    pub failed: Vec<(Vec<String>, YfError)>,
+}
+
 /// A builder for fetching quotes for one or more symbols.
+///
+/// Large symbol lists are split into requests of [`chunk_size`](Self::chunk_size) symbols,
+/// run [`concurrency`](Self::concurrency) at a time, and merged back in request order.
 pub struct QuotesBuilder {
     client: YfClient,
     symbols: Vec<String>,
+    batching: core_quotes::Batching,
+    fields: Vec<QuoteField>,
     cache_mode: CacheMode,
     retry_override: Option<RetryConfig>,
 }
@@ -34,6 +57,11 @@ impl QuotesBuilder {
         Self {
             client,
             symbols: Vec::new(),
+            batching: core_quotes::Batching {
+                chunk_size: core_quotes::DEFAULT_CHUNK_SIZE,
+                concurrency: core_quotes::DEFAULT_CONCURRENCY,
+            },
+            fields: Vec::new(),
             cache_mode: CacheMode::Use,
             retry_override: None,
         }
@@ -53,6 +81,39 @@ impl QuotesBuilder {
         self
     }
 
+    /// Sets how many symbols are sent per request. (Default: `100`)
+    ///
+    /// Very long symbol lists make URLs Yahoo rejects or truncates. A size of `0` is treated
+    /// as `1`.
+    #[must_use]
+    pub const fn chunk_size(mut self, size: usize) -> Self {
+        self.batching.chunk_size = size;
+        self
+    }
+
+    /// Sets how many requests run at the same time. (Default: `4`)
+    ///
+    /// A limit of `0` is treated as `1`.
+    #[must_use]
+    pub const fn concurrency(mut self, limit: usize) -> Self {
+        self.batching.concurrency = limit;
+        self
+    }
+
+    /// Requests only the given fields instead of Yahoo's full default payload.
+    ///
+    /// Fields outside the selection are left empty on the returned quotes, even if Yahoo sends
+    /// them anyway. The symbol, quote type, currency and exchange are always requested. An empty
+    /// selection restores the full payload.
+    #[must_use]
+    pub fn fields<I>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = QuoteField>,
+    {
+        self.fields = fields.into_iter().collect();
+        self
+    }
+
     /// Replaces the current list of symbols with a new list.
     #[must_use]
     pub fn symbols<I, S>(mut self, syms: I) -> Self
@@ -71,28 +132,88 @@ impl QuotesBuilder {
         self
     }
 
-    /// Fetches the quotes for the configured symbols.
+    /// Fetches the quotes for the configured symbols, in request order.
+    ///
+    /// Symbols Yahoo returns nothing for, and those in a failed request, are skipped; use
+    /// [`fetch_batch`](Self::fetch_batch) to find out which.
     ///
     /// # Errors
     ///
-    /// Returns `YfError` if no symbols were provided, the network request fails,
-    /// the response cannot be parsed, or data for the symbols is not available.
-    pub async fn fetch(self) -> Result<Vec<crate::core::Quote>, crate::core::YfError> {
+    /// Returns `YfError` if no symbols were provided or every request fails.
+    pub async fn fetch(self) -> Result<Vec<Quote>, YfError> {
+        Ok(self.fetch_batch().await?.quotes)
+    }
+
+    /// Fetches the quotes for the configured symbols with every field the v7 endpoint reports,
+    /// including bid/ask, day range, 52-week range, averages, valuation and extended-hours prices.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YfError` if no symbols were provided or every request fails.
+    pub async fn fetch_detailed(self) -> Result<Vec<QuoteDetail>, YfError> {
+        Ok(self.fetch_detailed_batch().await?.quotes)
+    }
+
+    /// Fetches the quotes for the configured symbols and reports the symbols that returned
+    /// nothing or whose request failed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YfError` if no symbols were provided or every request fails.
+    pub async fn fetch_batch(self) -> Result<QuoteBatch, YfError> {
+        let normalize = self.client.normalize_minor_units();
+        let batch = self.fetch_nodes().await?;
+        Ok(QuoteBatch {
+            quotes: batch
+                .nodes
+                .into_iter()
+                .map(|n| core_quotes::quote_from_node(n, normalize))
+                .collect(),
+            missing: batch.missing,
+            failed: batch.failed,
+        })
+    }
+
+    /// Like [`fetch_batch`](Self::fetch_batch), returning [`QuoteDetail`]s.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YfError` if no symbols were provided or every request fails.
+    pub async fn fetch_detailed_batch(self) -> Result<QuoteBatch<QuoteDetail>, YfError> {
+        let normalize = self.client.normalize_minor_units();
+        let batch = self.fetch_nodes().await?;
+        Ok(QuoteBatch {
+            quotes: batch
+                .nodes
+                .into_iter()
+                .map(|n| core_quotes::detail_from_node(n, normalize))
+                .collect(),
+            missing: batch.missing,
+            failed: batch.failed,
+        })
+    }
+
+    async fn fetch_nodes(&self) -> Result<core_quotes::BatchedNodes, YfError> {
         if self.symbols.is_empty() {
-            return Err(crate::core::YfError::InvalidParams(
+            return Err(YfError::InvalidParams(
                 "symbols list cannot be empty".into(),
             ));
         }
 
         let symbol_slices: Vec<&str> = self.symbols.iter().map(AsRef::as_ref).collect();
-        let results = core_quotes::fetch_v7_quotes(
+        let wire = fields::wire_fields(&self.fields);
+        let mut batch = core_quotes::fetch_v7_quotes_batched(
             &self.client,
             &symbol_slices,
+            wire.as_deref(),
+            self.batching,
             self.cache_mode,
             self.retry_override.as_ref(),
         )
         .await?;
-
-        Ok(results.into_iter().map(Into::into).collect())
+        for node in &mut batch.nodes {
+            fields::retain_fields(node, &self.fields);
+        }
+        Ok(batch)
     }
 }
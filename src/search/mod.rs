@@ -1,10 +1,11 @@
-use paft::domain::{AssetKind, Exchange, Instrument};
+use paft::domain::{Exchange, Instrument};
 use paft::market::responses::search::{SearchResponse, SearchResult};
 use serde::Deserialize;
 use url::Url;
 
 use crate::core::client::CacheMode;
 use crate::core::client::RetryConfig;
+use crate::core::conversions::{string_to_asset_kind, symbol_to_asset_kind};
 use crate::{YfClient, YfError};
 
 fn parse_search_body(body: &str) -> Result<SearchResponse, YfError> {
@@ -18,8 +19,9 @@ fn parse_search_body(body: &str) -> Result<SearchResponse, YfError> {
             let exchange_opt = q.exchange.and_then(|s| s.parse::<Exchange>().ok());
             let kind = q
                 .quote_type
-                .and_then(|s| s.parse::<AssetKind>().ok())
-                .unwrap_or_default();
+                .as_deref()
+                .and_then(string_to_asset_kind)
+                .unwrap_or_else(|| symbol_to_asset_kind(&sym));
 
             let instrument = exchange_opt
                 .clone()
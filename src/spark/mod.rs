@@ -0,0 +1,349 @@
+mod wire;
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use paft::money::{Currency, Money};
+use rust_decimal::Decimal;
+
+use crate::core::client::{CacheMode, RetryConfig};
+use crate::core::conversions::{decimal_to_money_with_currency_str_normalized, i64_to_datetime};
+use crate::core::currency::minor_unit;
+use crate::core::{Interval, Range, YfClient, YfError, net};
+use crate::history::wire::ChartResult;
+
+use wire::{FlatSeries, SparkEnvelope};
+
+/// Default number of symbols sent in a single spark request.
+const DEFAULT_BATCH_SIZE: usize = 20;
+/// Default number of spark requests in flight at once.
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// A single close observation in a [`SparkSeries`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparkPoint {
+    /// Timestamp of the bar.
+    pub ts: DateTime<Utc>,
+    /// Close price of the bar.
+    pub close: Money,
+}
+
+/// The close-price series returned by the spark endpoint for one symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparkSeries {
+    /// The symbol, as requested.
+    pub symbol: String,
+    /// Currency of the closes, if Yahoo reported one. Closes default to USD otherwise.
+    pub currency: Option<Currency>,
+    /// Close before the first bar of the series, if reported.
+    pub previous_close: Option<Money>,
+    /// Closes in timestamp order. Bars with a null close are skipped.
+    pub closes: Vec<SparkPoint>,
+}
+
+/// A builder for fetching close-price series for many symbols through Yahoo's spark endpoint.
+///
+/// Spark returns closes for several symbols per request, so it is much cheaper than one chart
+/// call per symbol when only closes are needed. Symbols are split into batches of
+/// [`batch_size`](Self::batch_size) and up to [`concurrency`](Self::concurrency) batches are
+/// fetched at a time.
+///
+/// Spark closes are split-adjusted but not dividend-adjusted, and carry no OHLV or actions.
+pub struct SparkBuilder {
+    client: YfClient,
+    symbols: Vec<String>,
+    range: Option<Range>,
+    period: Option<(i64, i64)>,
+    interval: Interval,
+    batch_size: usize,
+    concurrency: usize,
+    cache_mode: CacheMode,
+    retry_override: Option<RetryConfig>,
+}
+
+impl SparkBuilder {
+    /// Creates a new `SparkBuilder`.
+    #[must_use]
+    pub fn new(client: &YfClient) -> Self {
+        Self {
+            client: client.clone(),
+            symbols: Vec::new(),
+            range: Some(Range::D1),
+            period: None,
+            interval: Interval::I5m,
+            batch_size: DEFAULT_BATCH_SIZE,
+            concurrency: DEFAULT_CONCURRENCY,
+            cache_mode: CacheMode::Use,
+            retry_override: None,
+        }
+    }
+
+    /// Sets the cache mode for this specific API call.
+    #[must_use]
+    pub const fn cache_mode(mut self, mode: CacheMode) -> Self {
+        self.cache_mode = mode;
+        self
+    }
+
+    /// Overrides the default retry policy for this specific API call.
+    #[must_use]
+    pub fn retry_policy(mut self, cfg: Option<RetryConfig>) -> Self {
+        self.retry_override = cfg;
+        self
+    }
+
+    /// Replaces the current list of symbols with a new list.
+    #[must_use]
+    pub fn symbols<I, S>(mut self, syms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.symbols = syms.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Adds a single symbol to the list.
+    #[must_use]
+    pub fn add_symbol(mut self, sym: impl Into<String>) -> Self {
+        self.symbols.push(sym.into());
+        self
+    }
+
+    /// Sets a relative date range for the request. (Default: `Range::D1`)
+    ///
+    /// This will override any previously set period using `between()`.
+    #[must_use]
+    pub const fn range(mut self, range: Range) -> Self {
+        self.period = None;
+        self.range = Some(range);
+        self
+    }
+
+    /// Sets an absolute date range for the request.
+    ///
+    /// This will override any previously set range using `range()`.
+    #[must_use]
+    pub const fn between(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.range = None;
+        self.period = Some((start.timestamp(), end.timestamp()));
+        self
+    }
+
+    /// Sets the time interval for each bar. (Default: `Interval::I5m`)
+    #[must_use]
+    pub const fn interval(mut self, interval: Interval) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets how many symbols are sent per spark request. (Default: `20`)
+    ///
+    /// Values below 1 are treated as 1.
+    #[must_use]
+    pub const fn batch_size(mut self, n: usize) -> Self {
+        self.batch_size = if n == 0 { 1 } else { n };
+        self
+    }
+
+    /// Sets how many spark requests run at the same time. (Default: `4`)
+    ///
+    /// A limit of `0` is treated as `1`.
+    #[must_use]
+    pub const fn concurrency(mut self, limit: usize) -> Self {
+        self.concurrency = limit;
+        self
+    }
+
+    /// Fetches the close series for all configured symbols.
+    ///
+    /// Series are returned in the order the symbols were given, matched case-insensitively
+    /// against Yahoo's symbols. Symbols Yahoo returned no data for are omitted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YfError` if no symbols were provided, the dates are invalid, or any batch
+    /// request fails or cannot be parsed.
+    pub async fn fetch(self) -> Result<Vec<SparkSeries>, YfError> {
+        if self.symbols.is_empty() {
+            return Err(YfError::InvalidParams(
+                "symbols list cannot be empty".into(),
+            ));
+        }
+        if let Some((p1, p2)) = self.period
+            && p1 >= p2
+        {
+            return Err(YfError::InvalidDates);
+        }
+
+        let batches: Vec<Vec<SparkSeries>> = stream::iter(self.symbols.chunks(self.batch_size))
+            .map(|chunk| self.fetch_batch(chunk))
+            .buffered(self.concurrency.max(1))
+            .try_collect()
+            .await?;
+        let mut by_symbol: HashMap<String, SparkSeries> = batches
+            .into_iter()
+            .flatten()
+            .map(|s| (s.symbol.to_ascii_uppercase(), s))
+            .collect();
+
+        Ok(self
+            .symbols
+            .iter()
+            .filter_map(|sym| {
+                let mut series = by_symbol.remove(&sym.to_ascii_uppercase())?;
+                series.symbol.clone_from(sym);
+                Some(series)
+            })
+            .collect())
+    }
+
+    async fn fetch_batch(&self, symbols: &[String]) -> Result<Vec<SparkSeries>, YfError> {
+        let mut url = self.client.base_spark().clone();
+        {
+            let mut qp = url.query_pairs_mut();
+            qp.append_pair("symbols", &symbols.join(","));
+            if let Some((p1, p2)) = self.period {
+                qp.append_pair("period1", &p1.to_string());
+                qp.append_pair("period2", &p2.to_string());
+            } else if let Some(r) = self.range {
+                qp.append_pair("range", crate::core::models::range_as_str(r));
+            }
+            qp.append_pair(
+                "interval",
+                crate::core::models::interval_as_str(self.interval),
+            );
+        }
+
+        let body = if self.cache_mode == CacheMode::Use
+            && let Some(body) = self.client.cache_get(&url).await
+        {
+            body
+        } else {
+            let resp = self
+                .client
+                .send_with_retry(
+                    self.client.http().get(url.clone()),
+                    self.retry_override.as_ref(),
+                )
+                .await?;
+            if !resp.status().is_success() {
+                let code = resp.status().as_u16();
+                let url_s = url.to_string();
+                return Err(match code {
+                    404 => YfError::NotFound { url: url_s },
+                    429 => YfError::RateLimited { url: url_s },
+                    500..=599 => YfError::ServerError {
+                        status: code,
+                        url: url_s,
+                    },
+                    _ => YfError::Status {
+                        status: code,
+                        url: url_s,
+                    },
+                });
+            }
+            let body = net::get_text(resp, "spark", &symbols.join("-"), "json").await?;
+            if self.cache_mode != CacheMode::Bypass {
+                self.client.cache_put(&url, &body, None).await;
+            }
+            body
+        };
+
+        decode_spark(&body, self.client.normalize_minor_units())
+    }
+}
+
+fn decode_spark(body: &str, normalize: bool) -> Result<Vec<SparkSeries>, YfError> {
+    let envelope: SparkEnvelope = serde_json::from_str(body).map_err(YfError::Json)?;
+
+    match envelope {
+        SparkEnvelope::Wrapped { spark } => {
+            if let Some(error) = spark.error {
+                return Err(YfError::Api(format!(
+                    "spark error: {} - {}",
+                    error.code, error.description
+                )));
+            }
+            Ok(spark
+                .result
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|r| {
+                    let chart = r.response.into_iter().next()?;
+                    Some(series_from_chart(r.symbol, chart, normalize))
+                })
+                .collect())
+        }
+        SparkEnvelope::Flat(map) => Ok(map
+            .into_iter()
+            .map(|(key, s)| series_from_flat(key, s, normalize))
+            .collect()),
+    }
+}
+
+fn series_from_chart(symbol: String, chart: ChartResult, normalize: bool) -> SparkSeries {
+    let code = chart.meta.as_ref().and_then(|m| m.currency.clone());
+    let previous_close = chart.meta.as_ref().and_then(|m| m.chart_previous_close);
+    let closes = chart
+        .indicators
+        .quote
+        .into_iter()
+        .next()
+        .map(|q| q.close)
+        .unwrap_or_default();
+    build_series(
+        symbol,
+        code.as_deref(),
+        chart.timestamp.unwrap_or_default(),
+        closes,
+        previous_close,
+        normalize,
+    )
+}
+
+fn series_from_flat(key: String, s: FlatSeries, normalize: bool) -> SparkSeries {
+    build_series(
+        s.symbol.unwrap_or(key),
+        s.currency.as_deref(),
+        s.timestamp.unwrap_or_default(),
+        s.close,
+        s.chart_previous_close,
+        normalize,
+    )
+}
+
+fn build_series(
+    symbol: String,
+    code: Option<&str>,
+    ts: Vec<i64>,
+    closes: Vec<Option<Decimal>>,
+    previous_close: Option<Decimal>,
+    normalize: bool,
+) -> SparkSeries {
+    let money = |v: Decimal| decimal_to_money_with_currency_str_normalized(v, code, normalize);
+    let currency = code.and_then(|c| {
+        minor_unit(c)
+            .filter(|_| normalize)
+            .map(|(major, _)| major)
+            .or_else(|| Currency::from_str(c).ok())
+    });
+
+    SparkSeries {
+        symbol,
+        currency,
+        previous_close: previous_close.map(money),
+        closes: ts
+            .into_iter()
+            .zip(closes)
+            .filter_map(|(t, c)| {
+                Some(SparkPoint {
+                    ts: i64_to_datetime(t),
+                    close: money(c?),
+                })
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::core::wire::{de_opt_decimal_lenient, de_vec_opt_decimal_lenient};
+use crate::history::wire::{ChartError, ChartResult};
+
+/// Yahoo serves spark payloads in two shapes: a `spark.result[]` envelope whose entries wrap
+/// regular chart results, and a flat object keyed by symbol.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum SparkEnvelope {
+    Wrapped { spark: SparkNode },
+    Flat(BTreeMap<String, FlatSeries>),
+}
+
+#[derive(Deserialize)]
+pub struct SparkNode {
+    #[serde(default)]
+    pub(crate) result: Option<Vec<SparkResult>>,
+    #[serde(default)]
+    pub(crate) error: Option<ChartError>,
+}
+
+#[derive(Deserialize)]
+pub struct SparkResult {
+    pub(crate) symbol: String,
+    #[serde(default)]
+    pub(crate) response: Vec<ChartResult>,
+}
+
+#[derive(Deserialize)]
+pub struct FlatSeries {
+    #[serde(default)]
+    pub(crate) symbol: Option<String>,
+    #[serde(default)]
+    pub(crate) timestamp: Option<Vec<i64>>,
+    #[serde(default, deserialize_with = "de_vec_opt_decimal_lenient")]
+    pub(crate) close: Vec<Option<Decimal>>,
+    #[serde(
+        default,
+        rename = "chartPreviousClose",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    pub(crate) chart_previous_close: Option<Decimal>,
+    #[serde(default)]
+    pub(crate) currency: Option<String>,
+}
@@ -19,17 +19,27 @@ use tokio_tungstenite::{
 
 use crate::{
     YfClient, YfError,
-    core::client::{CacheMode, RetryConfig},
-    core::conversions::f64_to_money_with_currency_str,
+    core::client::{Backoff, CacheMode, RetryConfig, compute_backoff_duration},
+    core::conversions::{
+        decimal_to_money_with_currency_str_normalized, f64_to_money_with_currency_str,
+        f64_to_money_with_currency_str_normalized, string_to_asset_kind, string_to_exchange,
+    },
+    core::instrument::{build_instrument, resolve_instrument},
 };
-use paft::domain::{AssetKind, Instrument};
+use paft::domain::Exchange;
 use paft::market::quote::QuoteUpdate;
 
-// Yahoo Finance websocket wire types (generated from `yaticker.proto`).
+// Yahoo Finance websocket wire types (generated from `yaticker.proto`). Not every generated
+// accessor and enum helper is used.
+#[allow(dead_code)]
 mod wire_ws {
     include!(concat!(env!("OUT_DIR"), "/yaticker.rs"));
 }
 
+mod update;
+
+pub use update::{MarketHours, OptionSide, QuoteUpdateDetail, ws_quote_type_to_asset_kind};
+
 // Use paft's QuoteUpdate which carries Money and DateTime<Utc>
 // pub use paft::market::quote::QuoteUpdate; (imported above)
 
@@ -49,13 +59,28 @@ mod wire_ws {
 // - If you need cumulative volume, accumulate the per-update `volume` values yourself or
 //   use the `day_volume` from quote endpoints.
 // - Expect `None` for the first message per symbol and after rollovers.
-/// Configuration for a polling-based quote stream.
+/// Configuration for a quote stream.
 #[derive(Debug, Clone)]
 pub struct StreamConfig {
     /// The interval at which to poll for new quote data.
     pub interval: Duration,
     /// If `true`, only emit updates when the price has changed.
     pub diff_only: bool,
+    /// Number of symbols sent per quote request when polling.
+    pub chunk_size: usize,
+    /// Number of quote requests in flight at once when polling.
+    pub concurrency: usize,
+    /// If `true`, a websocket that closes or fails is reconnected and resubscribed.
+    pub reconnect: bool,
+    /// Delay between websocket reconnect attempts.
+    pub reconnect_backoff: Backoff,
+    /// Consecutive failed reconnects allowed before giving up; `None` retries forever.
+    pub max_reconnect_attempts: Option<u32>,
+    /// How often to ping an idle websocket; `None` disables pings.
+    pub ping_interval: Option<Duration>,
+    /// Ping intervals without any frame from the server before the websocket is treated as
+    /// dead; `None` never gives up on a silent connection.
+    pub max_missed_pings: Option<u32>,
 }
 
 impl Default for StreamConfig {
@@ -63,17 +88,68 @@ impl Default for StreamConfig {
         Self {
             interval: Duration::from_secs(1),
             diff_only: true,
+            chunk_size: crate::core::quotes::DEFAULT_CHUNK_SIZE,
+            concurrency: crate::core::quotes::DEFAULT_CONCURRENCY,
+            reconnect: true,
+            reconnect_backoff: Backoff::Exponential {
+                base: Duration::from_millis(500),
+                factor: 2.0,
+                max: Duration::from_secs(30),
+                jitter: true,
+            },
+            max_reconnect_attempts: None,
+            ping_interval: Some(Duration::from_secs(15)),
+            max_missed_pings: Some(3),
         }
     }
 }
 
-/// A handle to a running quote stream, used to stop it gracefully.
+/// A handle to a running quote stream, used to change its symbols or stop it gracefully.
 pub struct StreamHandle {
     join: JoinHandle<()>,
     stop_tx: Option<oneshot::Sender<()>>,
+    commands: mpsc::UnboundedSender<Command>,
 }
 
 impl StreamHandle {
+    /// Adds symbols to the running stream. A websocket subscribes to them right away; a polling
+    /// stream includes them from its next poll. Symbols already streamed are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream has already ended.
+    pub fn subscribe<I, S>(&self, syms: I) -> Result<(), YfError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.send(Command::Subscribe(
+            syms.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    /// Removes symbols from the running stream and drops their volume-delta state, so a later
+    /// [`subscribe`](Self::subscribe) starts them afresh.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream has already ended.
+    pub fn unsubscribe<I, S>(&self, syms: I) -> Result<(), YfError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.send(Command::Unsubscribe(
+            syms.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    fn send(&self, cmd: Command) -> Result<(), YfError> {
+        self.commands
+            .send(cmd)
+            .map_err(|_| YfError::InvalidParams("stream has already ended".into()))
+    }
+
     /// Stops the stream and waits for the background task to complete.
     pub async fn stop(mut self) {
         if let Some(tx) = self.stop_tx.take() {
@@ -91,7 +167,8 @@ impl StreamHandle {
 /// Defines the transport method for streaming quote data.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum StreamMethod {
-    /// Attempt to use `WebSockets`, and fall back to polling if the connection fails. (Default)
+    /// Attempt to use `WebSockets`, and fall back to polling if the connection fails or reconnects
+    /// are exhausted. (Default)
     #[default]
     WebsocketWithFallback,
     /// Use `WebSockets` only. This is the preferred method for real-time data. The stream will fail if a WebSocket connection cannot be established.
@@ -177,6 +254,61 @@ impl StreamBuilder {
         self
     }
 
+    /// Sets how many symbols are sent per quote request when polling. (Default: `100`)
+    #[must_use]
+    pub const fn chunk_size(mut self, size: usize) -> Self {
+        self.cfg.chunk_size = size;
+        self
+    }
+
+    /// Sets how many quote requests run at the same time when polling. (Default: `4`)
+    #[must_use]
+    pub const fn concurrency(mut self, limit: usize) -> Self {
+        self.cfg.concurrency = limit;
+        self
+    }
+
+    /// If `true`, reconnect and resubscribe when the websocket drops. Volume deltas carry on
+    /// across reconnects. (Default: `true`)
+    #[must_use]
+    pub const fn reconnect(mut self, yes: bool) -> Self {
+        self.cfg.reconnect = yes;
+        self
+    }
+
+    /// Sets the delay between websocket reconnect attempts. (Default: exponential from 500ms,
+    /// capped at 30s)
+    #[must_use]
+    pub fn reconnect_backoff(mut self, backoff: Backoff) -> Self {
+        self.cfg.reconnect_backoff = backoff;
+        self
+    }
+
+    /// Limits consecutive failed reconnects before the websocket gives up, after which
+    /// `WebsocketWithFallback` switches to polling. `None` retries forever. (Default: `None`)
+    #[must_use]
+    pub const fn max_reconnect_attempts(mut self, max: Option<u32>) -> Self {
+        self.cfg.max_reconnect_attempts = max;
+        self
+    }
+
+    /// Sets how often the websocket is pinged to keep it alive; `None` disables pings.
+    /// (Default: 15s)
+    #[must_use]
+    pub const fn ping_interval(mut self, every: Option<Duration>) -> Self {
+        self.cfg.ping_interval = every;
+        self
+    }
+
+    /// Sets how many ping intervals may pass without any frame, pongs included, before the
+    /// websocket is dropped and reconnected; `None` keeps silent connections open. Has no
+    /// effect without a [`ping_interval`](Self::ping_interval). (Default: `3`)
+    #[must_use]
+    pub const fn max_missed_pings(mut self, max: Option<u32>) -> Self {
+        self.cfg.max_missed_pings = max;
+        self
+    }
+
     /// Starts the stream, returning a handle to control it and a channel receiver for quote updates.
     ///
     /// # Errors
@@ -185,6 +317,29 @@ impl StreamBuilder {
     pub fn start(
         self,
     ) -> Result<(StreamHandle, tokio::sync::mpsc::Receiver<QuoteUpdate>), crate::core::YfError>
+    {
+        self.spawn()
+    }
+
+    /// Like [`start`](Self::start), but each update carries every field Yahoo sends: bid/ask
+    /// with sizes, session range, change, market hours, asset kind, and option and crypto
+    /// details (see [`QuoteUpdateDetail`]).
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if no symbols have been added to the builder.
+    pub fn start_detailed(
+        self,
+    ) -> Result<(StreamHandle, tokio::sync::mpsc::Receiver<QuoteUpdateDetail>), crate::core::YfError>
+    {
+        self.spawn()
+    }
+
+    fn spawn<T>(
+        self,
+    ) -> Result<(StreamHandle, tokio::sync::mpsc::Receiver<T>), crate::core::YfError>
+    where
+        T: From<QuoteUpdateDetail> + Send + 'static,
     {
         if self.symbols.is_empty() {
             return Err(crate::core::YfError::InvalidParams(
@@ -192,25 +347,28 @@ impl StreamBuilder {
             ));
         }
 
-        let (tx, rx) = tokio::sync::mpsc::channel::<QuoteUpdate>(1024);
+        let (tx, rx) = tokio::sync::mpsc::channel::<T>(1024);
         let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+        let (cmd_tx, cmd_rx) = mpsc::unbounded_channel::<Command>();
 
         let join = tokio::spawn({
             let client = self.client;
-            let symbols = self.symbols.clone();
             let cfg = self.cfg.clone();
 
-            let mut stop_rx = stop_rx;
+            let mut ctl = Control {
+                symbols: self.symbols,
+                stop_rx,
+                commands: cmd_rx,
+            };
 
-            // NEW:
+            // Request settings for the polling path, including the websocket fallback.
             let cache_mode = self.cache_mode;
             let retry_override = self.retry_override.clone();
 
             async move {
                 match self.method {
                     StreamMethod::Websocket => {
-                        if let Err(e) =
-                            run_websocket_stream(&client, symbols, tx, &mut stop_rx).await
+                        if let Err(e) = run_websocket_stream(&client, &cfg, tx, &mut ctl).await
                             && std::env::var("YF_DEBUG").ok().as_deref() == Some("1")
                         {
                             eprintln!("YF_DEBUG(stream): websocket stream failed: {e}");
@@ -218,8 +376,7 @@ impl StreamBuilder {
                     }
                     StreamMethod::WebsocketWithFallback => {
                         if let Err(e) =
-                            run_websocket_stream(&client, symbols.clone(), tx.clone(), &mut stop_rx)
-                                .await
+                            run_websocket_stream(&client, &cfg, tx.clone(), &mut ctl).await
                         {
                             if std::env::var("YF_DEBUG").ok().as_deref() == Some("1") {
                                 eprintln!(
@@ -228,10 +385,9 @@ impl StreamBuilder {
                             }
                             run_polling_stream(
                                 client,
-                                symbols,
                                 cfg,
                                 tx,
-                                &mut stop_rx,
+                                &mut ctl,
                                 cache_mode,
                                 retry_override.as_ref(),
                             )
@@ -241,10 +397,9 @@ impl StreamBuilder {
                     StreamMethod::Polling => {
                         run_polling_stream(
                             client,
-                            symbols,
                             cfg,
                             tx,
-                            &mut stop_rx,
+                            &mut ctl,
                             cache_mode,
                             retry_override.as_ref(),
                         )
@@ -258,30 +413,133 @@ impl StreamBuilder {
             StreamHandle {
                 join,
                 stop_tx: Some(stop_tx),
+                commands: cmd_tx,
             },
             rx,
         ))
     }
 }
 
-#[derive(Serialize)]
-struct WsSubscribe<'a> {
-    subscribe: &'a [String],
+/// A change to the streamed symbols, sent from a [`StreamHandle`].
+enum Command {
+    Subscribe(Vec<String>),
+    Unsubscribe(Vec<String>),
 }
 
-#[allow(clippy::too_many_lines)]
-async fn run_websocket_stream(
-    client: &YfClient,
+impl Command {
+    /// Applies the command to `symbols`, narrowed to the symbols it actually added or removed.
+    fn apply(self, symbols: &mut Vec<String>) -> Self {
+        match self {
+            Self::Subscribe(syms) => {
+                let mut added: Vec<String> = Vec::new();
+                for s in syms {
+                    if !contains_symbol(symbols, &s) && !contains_symbol(&added, &s) {
+                        added.push(s);
+                    }
+                }
+                symbols.extend(added.iter().cloned());
+                Self::Subscribe(added)
+            }
+            Self::Unsubscribe(syms) => {
+                let removed = symbols
+                    .iter()
+                    .filter(|s| contains_symbol(&syms, s))
+                    .cloned()
+                    .collect();
+                symbols.retain(|s| !contains_symbol(&syms, s));
+                Self::Unsubscribe(removed)
+            }
+        }
+    }
+
+    fn request(&self) -> WsRequest<'_> {
+        match self {
+            Self::Subscribe(syms) => WsRequest::Subscribe(syms),
+            Self::Unsubscribe(syms) => WsRequest::Unsubscribe(syms),
+        }
+    }
+}
+
+fn contains_symbol(list: &[String], sym: &str) -> bool {
+    list.iter().any(|s| s.eq_ignore_ascii_case(sym))
+}
+
+/// The stream task's side of a [`StreamHandle`], plus the symbols it currently streams.
+struct Control {
     symbols: Vec<String>,
-    tx: mpsc::Sender<QuoteUpdate>,
-    stop_rx: &mut oneshot::Receiver<()>,
-) -> Result<(), YfError> {
+    stop_rx: oneshot::Receiver<()>,
+    commands: mpsc::UnboundedReceiver<Command>,
+}
+
+/// A websocket request, sent as `{"subscribe":[...]}` or `{"unsubscribe":[...]}`.
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+enum WsRequest<'a> {
+    Subscribe(&'a [String]),
+    Unsubscribe(&'a [String]),
+}
+
+impl WsRequest<'_> {
+    /// Sends the request; one without symbols is skipped.
+    async fn send(&self, write: &mut WsSink) -> Result<(), YfError> {
+        let (Self::Subscribe(syms) | Self::Unsubscribe(syms)) = self;
+        if syms.is_empty() {
+            return Ok(());
+        }
+        let text = serde_json::to_string(self).map_err(YfError::Json)?;
+        write.send(WsMessage::Text(text.into())).await?;
+        Ok(())
+    }
+}
+
+type WsConnection =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+type WsSink = futures_util::stream::SplitSink<WsConnection, WsMessage>;
+
+/// Per-symbol state kept across reconnects, so volume deltas continue where they left off.
+#[derive(Default)]
+struct WsState {
+    last_day_volume: std::collections::HashMap<String, u64>,
+    last_ts: std::collections::HashMap<String, DateTime<Utc>>,
+    /// Unsubscribed symbols whose in-flight ticks are ignored.
+    dropped: Vec<String>,
+    #[cfg(feature = "test-mode")]
+    recorded: bool,
+}
+
+impl WsState {
+    /// Tracks a symbol change applied with [`Command::apply`].
+    fn note(&mut self, change: &Command) {
+        match change {
+            Command::Subscribe(added) => self.dropped.retain(|s| !contains_symbol(added, s)),
+            Command::Unsubscribe(removed) => {
+                self.last_day_volume
+                    .retain(|k, _| !contains_symbol(removed, k));
+                self.last_ts.retain(|k, _| !contains_symbol(removed, k));
+                self.dropped.extend(removed.iter().cloned());
+            }
+        }
+    }
+}
+
+/// How a single websocket connection ended.
+enum SessionEnd {
+    /// The stream was stopped or the receiver was dropped.
+    Stopped,
+    /// The socket closed (`error` is `None`) or failed.
+    Disconnected {
+        error: Option<YfError>,
+        received: bool,
+    },
+}
+
+fn ws_request(client: &YfClient) -> Result<Request, YfError> {
     let base = client.base_stream();
     let host = base
         .host_str()
         .ok_or_else(|| YfError::InvalidParams("URL has no host".into()))?;
 
-    let request = Request::builder()
+    Request::builder()
         .uri(base.as_str())
         .header("Host", host)
         .header("Origin", "https://finance.yahoo.com")
@@ -291,90 +549,213 @@ async fn run_websocket_stream(
         .header("Sec-WebSocket-Key", generate_key())
         .header("Sec-WebSocket-Version", "13")
         .body(())
-        .map_err(|e| YfError::InvalidParams(format!("Failed to build websocket request: {e}")))?;
+        .map_err(|e| YfError::InvalidParams(format!("Failed to build websocket request: {e}")))
+}
 
-    let (ws_stream, _) = connect_async(request).await?;
-    let (mut write, mut read) = ws_stream.split();
+async fn connect_ws(client: &YfClient) -> Result<WsConnection, YfError> {
+    let (ws, _) = connect_async(ws_request(client)?).await?;
+    Ok(ws)
+}
 
-    let sub_msg = serde_json::to_string(&WsSubscribe {
-        subscribe: &symbols,
-    })
-    .map_err(YfError::Json)?;
-    write.send(WsMessage::Text(sub_msg.into())).await?;
+// The first connection is not retried, so `WebsocketWithFallback` can switch to polling right
+// away. Once connected, dropped sockets are reconnected with `cfg.reconnect_backoff`; the
+// attempt counter resets after a connection delivers data. Running out of attempts returns the
+// last error.
+async fn run_websocket_stream<T: From<QuoteUpdateDetail>>(
+    client: &YfClient,
+    cfg: &StreamConfig,
+    tx: mpsc::Sender<T>,
+    ctl: &mut Control,
+) -> Result<(), YfError> {
+    let mut ws = connect_ws(client).await?;
+    let mut state = WsState::default();
+    let mut failures: u32 = 0;
 
-    #[cfg(feature = "test-mode")]
-    let mut recorded = false;
+    loop {
+        let mut last_err = match run_ws_session(client, ws, cfg, &tx, ctl, &mut state).await {
+            SessionEnd::Stopped => return Ok(()),
+            SessionEnd::Disconnected { error, received } => {
+                if received {
+                    failures = 0;
+                }
+                error
+            }
+        };
+        if !cfg.reconnect {
+            return last_err.map_or(Ok(()), Err);
+        }
 
-    let mut last_day_volume: std::collections::HashMap<String, u64> =
-        std::collections::HashMap::new();
-    let mut last_ts: std::collections::HashMap<String, DateTime<Utc>> =
-        std::collections::HashMap::new();
+        ws = loop {
+            if cfg
+                .max_reconnect_attempts
+                .is_some_and(|max| failures >= max)
+            {
+                return last_err.map_or(Ok(()), Err);
+            }
+            let delay = compute_backoff_duration(&cfg.reconnect_backoff, failures);
+            if std::env::var("YF_DEBUG").ok().as_deref() == Some("1") {
+                match &last_err {
+                    Some(e) => eprintln!(
+                        "YF_DEBUG(stream): websocket dropped ({e}), reconnecting in {delay:?}"
+                    ),
+                    None => {
+                        eprintln!("YF_DEBUG(stream): websocket closed, reconnecting in {delay:?}")
+                    }
+                }
+            }
+            let mut sleep = std::pin::pin!(tokio::time::sleep(delay));
+            loop {
+                select! {
+                    () = sleep.as_mut() => break,
+                    Some(cmd) = ctl.commands.recv() => state.note(&cmd.apply(&mut ctl.symbols)),
+                    _ = &mut ctl.stop_rx => return Ok(()),
+                }
+            }
+            failures += 1;
+            match connect_ws(client).await {
+                Ok(ws) => break ws,
+                Err(e) => last_err = Some(e),
+            }
+        };
+    }
+}
+
+/// Subscribes on a fresh connection and forwards updates until it closes, fails or is stopped.
+async fn run_ws_session<T: From<QuoteUpdateDetail>>(
+    client: &YfClient,
+    ws: WsConnection,
+    cfg: &StreamConfig,
+    tx: &mpsc::Sender<T>,
+    ctl: &mut Control,
+    state: &mut WsState,
+) -> SessionEnd {
+    let (mut write, mut read) = ws.split();
+    if let Err(e) = WsRequest::Subscribe(&ctl.symbols).send(&mut write).await {
+        return SessionEnd::Disconnected {
+            error: Some(e),
+            received: false,
+        };
+    }
+
+    let mut ping = cfg
+        .ping_interval
+        .map(|d| tokio::time::interval_at(tokio::time::Instant::now() + d, d));
+    // Pongs count as frames, so a connection stays alive while the server answers pings.
+    let stale_after = cfg
+        .ping_interval
+        .zip(cfg.max_missed_pings)
+        .map(|(every, max)| every * max.max(1));
+    let mut last_frame = tokio::time::Instant::now();
+    let mut received = false;
 
     loop {
         select! {
             msg = read.next() => {
-                match msg {
+                last_frame = tokio::time::Instant::now();
+                let ticker = match msg {
                     Some(Ok(WsMessage::Text(text))) => {
+                        received = true;
                         #[cfg(feature = "test-mode")]
                         {
-                            if !recorded && std::env::var("YF_RECORD").ok().as_deref() == Some("1") {
+                            if !state.recorded && std::env::var("YF_RECORD").ok().as_deref() == Some("1") {
                                 if let Err(e) = crate::core::fixtures::record_fixture("stream_ws", "MULTI", "b64", &text) {
                                     eprintln!("YF_RECORD: failed to write stream fixture: {e}");
                                 }
-                                recorded = true;
-                            }
-                        }
-
-                        match decode_ws_pricing(&text) {
-                            Ok(ticker) => {
-                                if let Some(update) = map_ws_pricing_to_update_with_delta(client, &ticker, &mut last_day_volume, &mut last_ts).await
-                                    && tx.send(update).await.is_err() { break; }
-                            },
-                            Err(e) => {
-                                if std::env::var("YF_DEBUG").ok().as_deref() == Some("1") {
-                                    eprintln!("YF_DEBUG(stream): ws text decode error: {e}");
-                                }
-                                // Non-price frames (acks/heartbeats) may lack "message"; ignore.
+                                state.recorded = true;
                             }
                         }
+                        decode_ws_text(&text)
                     }
                     Some(Ok(WsMessage::Binary(bin))) => {
-                        // Try to interpret as UTF-8 JSON-wrapped base64 first
-                        let handled = if let Ok(as_text) = std::str::from_utf8(&bin) {
-                            if let Ok(ticker) = decode_ws_pricing(as_text) {
-                                if let Some(update) = map_ws_pricing_to_update_with_delta(client, &ticker, &mut last_day_volume, &mut last_ts).await
-                                    && tx.send(update).await.is_err() { break; }
-                                true
-                            } else { false }
-                        } else { false };
-                        // If not handled, treat as raw protobuf bytes
-                        if !handled {
-                            match wire_ws::PricingData::decode(&*bin) {
-                                Ok(ticker) => {
-                                    if let Some(update) = map_ws_pricing_to_update_with_delta(client, &ticker, &mut last_day_volume, &mut last_ts).await
-                                        && tx.send(update).await.is_err() { break; }
-                                }
-                                Err(e) => {
-                                    if std::env::var("YF_DEBUG").ok().as_deref() == Some("1") {
-                                        eprintln!("YF_DEBUG(stream): ws binary decode error: {e}");
-                                    }
-                                }
-                            }
-                        }
+                        received = true;
+                        decode_ws_binary(&bin)
+                    }
+                    Some(Ok(_)) => None, // pings, pongs and close frames
+                    Some(Err(e)) => {
+                        return SessionEnd::Disconnected { error: Some(e.into()), received };
                     }
-                    Some(Ok(WsMessage::Ping(_) | WsMessage::Pong(_) | _)) => { /* catch-all for variants like Frame(_) */ }
-                    Some(Err(e)) => return Err(e.into()),
-                    None => break,
+                    None => return SessionEnd::Disconnected { error: None, received },
+                };
+                if let Some(ticker) = ticker
+                    && !contains_symbol(&state.dropped, &ticker.id)
+                    && let Some(update) = map_ws_pricing_to_update_with_delta(client, &ticker, &mut state.last_day_volume, &mut state.last_ts).await
+                    && tx.send(update.into()).await.is_err()
+                {
+                    return SessionEnd::Stopped;
+                }
+            },
+            () = next_ping(ping.as_mut()) => {
+                if let Some(limit) = stale_after
+                    && last_frame.elapsed() >= limit
+                {
+                    let error = std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        format!("no websocket frames for {limit:?}"),
+                    );
+                    return SessionEnd::Disconnected {
+                        error: Some(tokio_tungstenite::tungstenite::Error::Io(error).into()),
+                        received,
+                    };
+                }
+                if let Err(e) = write.send(WsMessage::Ping(Vec::new().into())).await {
+                    return SessionEnd::Disconnected { error: Some(e.into()), received };
+                }
+            },
+            Some(cmd) = ctl.commands.recv() => {
+                let change = cmd.apply(&mut ctl.symbols);
+                state.note(&change);
+                if let Err(e) = change.request().send(&mut write).await {
+                    return SessionEnd::Disconnected { error: Some(e), received };
                 }
             },
-            _ = &mut *stop_rx => {
-                break;
+            _ = &mut ctl.stop_rx => {
+                return SessionEnd::Stopped;
+            }
+        }
+    }
+}
+
+async fn next_ping(ping: Option<&mut tokio::time::Interval>) {
+    match ping {
+        Some(p) => {
+            p.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+fn decode_ws_text(text: &str) -> Option<wire_ws::PricingData> {
+    match decode_ws_pricing(text) {
+        Ok(ticker) => Some(ticker),
+        Err(e) => {
+            if std::env::var("YF_DEBUG").ok().as_deref() == Some("1") {
+                eprintln!("YF_DEBUG(stream): ws text decode error: {e}");
+            }
+            // Non-price frames (acks/heartbeats) may lack "message"; ignore.
+            None
+        }
+    }
+}
+
+fn decode_ws_binary(bin: &[u8]) -> Option<wire_ws::PricingData> {
+    // Try to interpret as UTF-8 JSON-wrapped base64 first, then as raw protobuf bytes.
+    if let Ok(text) = std::str::from_utf8(bin)
+        && let Ok(ticker) = decode_ws_pricing(text)
+    {
+        return Some(ticker);
+    }
+    match wire_ws::PricingData::decode(bin) {
+        Ok(ticker) => Some(ticker),
+        Err(e) => {
+            if std::env::var("YF_DEBUG").ok().as_deref() == Some("1") {
+                eprintln!("YF_DEBUG(stream): ws binary decode error: {e}");
             }
+            None
         }
     }
-    Ok(())
 }
 
+/// Decodes a pricing frame, either raw base64 or Yahoo's `{"message":"<base64>"}` wrapper.
 fn decode_ws_pricing(text: &str) -> Result<wire_ws::PricingData, YfError> {
     let s = text.trim();
     let b64_cow: std::borrow::Cow<str> = if s.starts_with('{') {
@@ -397,31 +778,35 @@ fn decode_ws_pricing(text: &str) -> Result<wire_ws::PricingData, YfError> {
     Ok(ticker)
 }
 
+fn ws_exchange(ticker: &wire_ws::PricingData) -> Option<Exchange> {
+    Some(ticker.exchange.clone())
+        .filter(|e| !e.is_empty())
+        .and_then(|e| string_to_exchange(Some(e)))
+}
+
 async fn map_ws_pricing_to_update_with_delta(
     client: &YfClient,
     ticker: &wire_ws::PricingData,
     last_vol: &mut std::collections::HashMap<String, u64>,
     last_ts: &mut std::collections::HashMap<String, DateTime<Utc>>,
-) -> Option<QuoteUpdate> {
+) -> Option<QuoteUpdateDetail> {
     let currency_str = Some(ticker.currency.as_str());
-    let instrument = if let Some(inst) = client.cached_instrument(&ticker.id).await {
-        inst
-    } else {
-        let kind = AssetKind::Equity;
-        if let Ok(inst) = Instrument::from_symbol(&ticker.id, kind) {
-            client
-                .store_instrument(ticker.id.clone(), inst.clone())
-                .await;
-            inst
-        } else {
-            if std::env::var("YF_DEBUG").ok().as_deref() == Some("1") {
-                eprintln!(
-                    "YF_DEBUG(stream): skipping ws update with invalid symbol: {}",
-                    ticker.id
-                );
-            }
-            return None;
+    let normalize = client.normalize_minor_units();
+    let Some(instrument) = resolve_instrument(
+        client,
+        &ticker.id,
+        ws_quote_type_to_asset_kind(ticker.quote_type),
+        ws_exchange(ticker),
+    )
+    .await
+    else {
+        if std::env::var("YF_DEBUG").ok().as_deref() == Some("1") {
+            eprintln!(
+                "YF_DEBUG(stream): skipping ws update with invalid symbol: {}",
+                ticker.id
+            );
         }
+        return None;
     };
     let Some(timestamp) = DateTime::from_timestamp_millis(ticker.time) else {
         if std::env::var("YF_DEBUG").ok().as_deref() == Some("1") {
@@ -437,19 +822,22 @@ async fn map_ws_pricing_to_update_with_delta(
     if let Some(prev_ts) = last_ts.get(&ticker.id)
         && timestamp < *prev_ts
     {
-        return Some(QuoteUpdate {
+        let update = QuoteUpdate {
             instrument,
-            price: Some(f64_to_money_with_currency_str(
+            price: Some(f64_to_money_with_currency_str_normalized(
                 f64::from(ticker.price),
                 currency_str,
+                normalize,
             )),
-            previous_close: Some(f64_to_money_with_currency_str(
+            previous_close: Some(f64_to_money_with_currency_str_normalized(
                 f64::from(ticker.previous_close),
                 currency_str,
+                normalize,
             )),
             ts: timestamp,
             volume: None,
-        });
+        };
+        return Some(update::detail_from_pricing(ticker, update, normalize));
     }
 
     let cur_vol = u64::try_from(ticker.day_volume).unwrap_or(0);
@@ -466,52 +854,41 @@ async fn map_ws_pricing_to_update_with_delta(
     last_ts.insert(ticker.id.clone(), timestamp);
     last_vol.insert(ticker.id.clone(), cur_vol);
 
-    Some(QuoteUpdate {
+    let update = QuoteUpdate {
         instrument,
-        price: Some(f64_to_money_with_currency_str(
+        price: Some(f64_to_money_with_currency_str_normalized(
             f64::from(ticker.price),
             currency_str,
+            normalize,
         )),
-        previous_close: Some(f64_to_money_with_currency_str(
+        previous_close: Some(f64_to_money_with_currency_str_normalized(
             f64::from(ticker.previous_close),
             currency_str,
+            normalize,
         )),
         ts: timestamp,
         volume,
-    })
+    };
+    Some(update::detail_from_pricing(ticker, update, normalize))
 }
 
 /// Decodes a single base64-encoded protobuf message from the Yahoo Finance WebSocket stream.
 #[doc(hidden)]
 pub fn decode_and_map_message(text: &str) -> Result<QuoteUpdate, YfError> {
-    // Support both:
-    //   1) Raw base64 string
-    //   2) JSON wrapper: {"message":"<base64...>"}  (Yahoo's current format)
-    let s = text.trim();
-
-    // Use Cow to avoid borrowing from a temporary JSON value
-    let b64_cow: std::borrow::Cow<str> = if s.starts_with('{') {
-        match serde_json::from_str::<serde_json::Value>(s) {
-            Ok(v) => {
-                let msg = v.get("message").and_then(|m| m.as_str()).ok_or_else(|| {
-                    YfError::MissingData("ws json message missing 'message' field".into())
-                })?;
-                std::borrow::Cow::Owned(msg.to_string())
-            }
-            // If it's not valid JSON, treat the whole thing as raw base64
-            Err(_) => std::borrow::Cow::Borrowed(s),
-        }
-    } else {
-        std::borrow::Cow::Borrowed(s)
-    };
+    decode_and_map_detailed_message(text).map(Into::into)
+}
 
-    let decoded = general_purpose::STANDARD
-        .decode(b64_cow.as_ref())
-        .map_err(YfError::Base64)?;
-    let ticker = wire_ws::PricingData::decode(&*decoded)?;
+/// Like [`decode_and_map_message`], keeping every decoded field.
+#[doc(hidden)]
+pub fn decode_and_map_detailed_message(text: &str) -> Result<QuoteUpdateDetail, YfError> {
+    let ticker = decode_ws_pricing(text)?;
     let currency_str = Some(ticker.currency.as_str());
-    let instrument = Instrument::from_symbol(&ticker.id, AssetKind::Equity)
-        .map_err(|_| YfError::InvalidParams(format!("ws symbol invalid: {}", ticker.id)))?;
+    let instrument = build_instrument(
+        &ticker.id,
+        ws_quote_type_to_asset_kind(ticker.quote_type),
+        ws_exchange(&ticker),
+    )
+    .ok_or_else(|| YfError::InvalidParams(format!("ws symbol invalid: {}", ticker.id)))?;
 
     let Some(timestamp) = DateTime::from_timestamp_millis(ticker.time) else {
         // Log the error and return an error from this function
@@ -530,7 +907,7 @@ pub fn decode_and_map_message(text: &str) -> Result<QuoteUpdate, YfError> {
         )));
     };
 
-    Ok(QuoteUpdate {
+    let update = QuoteUpdate {
         instrument,
         price: Some(f64_to_money_with_currency_str(
             f64::from(ticker.price),
@@ -542,35 +919,44 @@ pub fn decode_and_map_message(text: &str) -> Result<QuoteUpdate, YfError> {
         )),
         ts: timestamp,
         volume: None,
-    })
+    };
+    Ok(update::detail_from_pricing(&ticker, update, false))
 }
 
-#[allow(clippy::too_many_arguments)]
-async fn run_polling_stream(
+async fn run_polling_stream<T: From<QuoteUpdateDetail>>(
     client: crate::core::YfClient,
-    symbols: Vec<String>,
     cfg: StreamConfig,
-    tx: tokio::sync::mpsc::Sender<QuoteUpdate>,
-    stop_rx: &mut tokio::sync::oneshot::Receiver<()>,
+    tx: tokio::sync::mpsc::Sender<T>,
+    ctl: &mut Control,
     cache_mode: CacheMode,
     retry_override: Option<&RetryConfig>,
 ) {
     let mut ticker = tokio::time::interval(cfg.interval);
-    let mut last_price: std::collections::HashMap<String, Option<f64>> =
+    let mut last_price: std::collections::HashMap<String, Option<rust_decimal::Decimal>> =
         std::collections::HashMap::new();
     let mut last_day_volume: std::collections::HashMap<String, u64> =
         std::collections::HashMap::new();
 
-    let symbol_slices: Vec<&str> = symbols.iter().map(AsRef::as_ref).collect();
+    let batching = crate::core::quotes::Batching {
+        chunk_size: cfg.chunk_size,
+        concurrency: cfg.concurrency,
+    };
 
     loop {
         tokio::select! {
             _ = ticker.tick() => {
                 if tx.is_closed() { break; }
+                if ctl.symbols.is_empty() { continue; }
+                let symbol_slices: Vec<&str> = ctl.symbols.iter().map(AsRef::as_ref).collect();
                 let ts: DateTime<Utc> = chrono::Utc::now();
-                match crate::core::quotes::fetch_v7_quotes(&client, &symbol_slices, cache_mode, retry_override).await {
-                    Ok(quotes) => {
-                        for q in quotes {
+                match crate::core::quotes::fetch_v7_quotes_batched(&client, &symbol_slices, None, batching, cache_mode, retry_override).await {
+                    Ok(batch) => {
+                        if std::env::var("YF_DEBUG").ok().as_deref() == Some("1") {
+                            for (syms, e) in &batch.failed {
+                                eprintln!("YF_DEBUG(stream): fetch error for {}: {e}", syms.join(","));
+                            }
+                        }
+                        for q in batch.nodes {
                             let sym_s = q.symbol.clone().unwrap_or_default();
                             let lp = q.regular_market_price.or(q.regular_market_previous_close);
 
@@ -604,25 +990,21 @@ async fn run_polling_stream(
                             }
 
                             let currency_str = q.currency.as_deref();
-                            let instrument = if let Some(inst) = client.cached_instrument(&sym_s).await {
-                                inst
-                            } else {
-                                let kind = AssetKind::Equity;
-                                match Instrument::from_symbol(&sym_s, kind) {
-                                    Ok(inst) => {
-                                        client.store_instrument(sym_s.clone(), inst.clone()).await;
-                                        inst
-                                    }
-                                    Err(_) => continue,
-                                }
+                            let normalize = client.normalize_minor_units();
+                            let kind = q.quote_type.as_deref().and_then(string_to_asset_kind);
+                            let exchange = string_to_exchange(q.full_exchange_name.clone().or_else(|| q.exchange.clone()));
+                            let Some(instrument) = resolve_instrument(&client, &sym_s, kind, exchange).await else {
+                                continue;
                             };
-                            if tx.send(QuoteUpdate {
+                            let update = QuoteUpdate {
                                 instrument,
-                                price: lp.map(|v| f64_to_money_with_currency_str(v, currency_str)),
-                                previous_close: q.regular_market_previous_close.map(|v| f64_to_money_with_currency_str(v, currency_str)),
+                                price: lp.map(|v| decimal_to_money_with_currency_str_normalized(v, currency_str, normalize)),
+                                previous_close: q.regular_market_previous_close.map(|v| decimal_to_money_with_currency_str_normalized(v, currency_str, normalize)),
                                 ts,
                                 volume: vol_delta,
-                            }).await.is_err() {
+                            };
+                            let detail = update::detail_from_node(&q, update, normalize);
+                            if tx.send(detail.into()).await.is_err() {
                                 // Break outer loop if receiver is dropped
                                 break;
                             }
@@ -636,7 +1018,13 @@ async fn run_polling_stream(
                 }
                 if tx.is_closed() { break; }
             }
-            _ = &mut *stop_rx => { break; }
+            Some(cmd) = ctl.commands.recv() => {
+                if let Command::Unsubscribe(removed) = cmd.apply(&mut ctl.symbols) {
+                    last_price.retain(|k, _| !contains_symbol(&removed, k));
+                    last_day_volume.retain(|k, _| !contains_symbol(&removed, k));
+                }
+            }
+            _ = &mut ctl.stop_rx => { break; }
         }
     }
 }
@@ -0,0 +1,294 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use paft::domain::{AssetKind, Exchange};
+use paft::market::quote::QuoteUpdate;
+use paft::money::Money;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use super::wire_ws::PricingData;
+use super::wire_ws::pricing_data::{MarketHours as WsMarketHours, OptionType, QuoteType};
+use crate::core::conversions::{
+    decimal_to_money_with_currency_str_normalized, f64_to_decimal_shortest, i64_to_datetime,
+    string_to_asset_kind, string_to_exchange,
+};
+use crate::core::quotes::V7QuoteNode;
+
+/// Convert the websocket `quote_type` code to `AssetKind`
+#[must_use]
+pub fn ws_quote_type_to_asset_kind(code: i32) -> Option<AssetKind> {
+    match QuoteType::try_from(code).ok()? {
+        QuoteType::Equity => Some(AssetKind::Equity),
+        QuoteType::Index => Some(AssetKind::Index),
+        QuoteType::Mutualfund | QuoteType::Moneymarket | QuoteType::Etf => Some(AssetKind::Fund),
+        QuoteType::Option => Some(AssetKind::Option),
+        QuoteType::Currency => Some(AssetKind::Forex),
+        QuoteType::Bond => Some(AssetKind::Bond),
+        QuoteType::Future => Some(AssetKind::Future),
+        QuoteType::Cryptocurrency => Some(AssetKind::Crypto),
+        _ => None,
+    }
+}
+
+/// The trading session a streamed price belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarketHours {
+    /// Before the regular session opens.
+    PreMarket,
+    /// The regular session.
+    Regular,
+    /// After the regular session closes.
+    PostMarket,
+    /// Extended overnight trading.
+    Extended,
+}
+
+impl MarketHours {
+    /// Maps the websocket `market_hours` code.
+    ///
+    /// Proto3 does not encode zero, so an unset code reads as pre-market. Crypto and currency
+    /// pairs trade around the clock and carry no session, so zero is unknown for them.
+    fn from_ws(code: i32, kind: Option<AssetKind>) -> Option<Self> {
+        match WsMarketHours::try_from(code).ok()? {
+            WsMarketHours::PreMarket
+                if matches!(kind, Some(AssetKind::Crypto | AssetKind::Forex)) =>
+            {
+                None
+            }
+            WsMarketHours::PreMarket => Some(Self::PreMarket),
+            WsMarketHours::RegularMarket => Some(Self::Regular),
+            WsMarketHours::PostMarket => Some(Self::PostMarket),
+            WsMarketHours::ExtendedHoursMarket => Some(Self::Extended),
+        }
+    }
+
+    /// Maps the v7 `marketState` string; `CLOSED` has no session.
+    fn from_market_state(s: &str) -> Option<Self> {
+        match s {
+            "PRE" | "PREPRE" => Some(Self::PreMarket),
+            "REGULAR" => Some(Self::Regular),
+            "POST" | "POSTPOST" => Some(Self::PostMarket),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a streamed option is a call or a put.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OptionSide {
+    /// A call option.
+    Call,
+    /// A put option.
+    Put,
+}
+
+impl OptionSide {
+    /// Maps the websocket `options_type` code.
+    fn from_ws(code: i64) -> Option<Self> {
+        match OptionType::try_from(i32::try_from(code).ok()?).ok()? {
+            OptionType::Call => Some(Self::Call),
+            OptionType::Put => Some(Self::Put),
+        }
+    }
+}
+
+/// A stream update with every field Yahoo sends, returned by
+/// [`StreamBuilder::start_detailed`](super::StreamBuilder::start_detailed).
+///
+/// [`update`](Self::update) is the [`QuoteUpdate`] that [`StreamBuilder::start`](super::StreamBuilder::start)
+/// would emit, with the same per-update volume deltas. Yahoo encodes missing values as zero, so
+/// zero prices, sizes and volumes are reported as `None`. Polling streams fill the fields the v7
+/// quote endpoint carries; option and crypto fields are websocket-only.
+#[derive(Debug, Clone)]
+pub struct QuoteUpdateDetail {
+    /// Instrument, price, previous close, time and volume delta.
+    pub update: QuoteUpdate,
+    /// Asset kind reported by Yahoo for the symbol.
+    pub quote_type: Option<AssetKind>,
+    /// Session the price belongs to.
+    ///
+    /// Yahoo's pre-market code is zero, which the websocket encoding cannot tell apart from an
+    /// absent one, so frames without a session report `PreMarket`. Crypto and currency frames
+    /// report `None` instead.
+    pub market_hours: Option<MarketHours>,
+    /// Exchange the update comes from.
+    pub exchange: Option<Exchange>,
+    /// Short display name.
+    pub short_name: Option<String>,
+    /// Number of decimal places Yahoo displays the price with.
+    pub price_hint: Option<u32>,
+
+    /// Change against the previous close.
+    pub change: Option<Money>,
+    /// Change against the previous close, in percent.
+    pub change_percent: Option<Decimal>,
+    /// Opening price of the session.
+    pub open: Option<Money>,
+    /// High of the session.
+    pub day_high: Option<Money>,
+    /// Low of the session.
+    pub day_low: Option<Money>,
+    /// Cumulative volume of the session.
+    pub day_volume: Option<u64>,
+    /// Size of the last trade.
+    pub last_size: Option<u64>,
+
+    /// Best bid.
+    pub bid: Option<Money>,
+    /// Size at the best bid.
+    pub bid_size: Option<u64>,
+    /// Best ask.
+    pub ask: Option<Money>,
+    /// Size at the best ask.
+    pub ask_size: Option<u64>,
+
+    /// Option strike.
+    pub strike: Option<Money>,
+    /// Option expiry.
+    pub expiry: Option<DateTime<Utc>>,
+    /// Call or put.
+    pub option_side: Option<OptionSide>,
+    /// Symbol of the option's underlying.
+    pub underlying_symbol: Option<String>,
+    /// Option open interest.
+    pub open_interest: Option<u64>,
+    /// Whether the option is a mini contract.
+    pub mini_option: bool,
+
+    /// Crypto volume over the last 24 hours.
+    pub volume_24h: Option<u64>,
+    /// Crypto volume over the last 24 hours across all quote currencies.
+    pub volume_all_currencies: Option<u64>,
+    /// Currency a crypto pair is quoted from.
+    pub from_currency: Option<String>,
+    /// Market the last crypto trade happened on.
+    pub last_market: Option<String>,
+    /// Circulating crypto supply.
+    pub circulating_supply: Option<Decimal>,
+    /// Market capitalization.
+    pub market_cap: Option<Money>,
+}
+
+impl From<QuoteUpdateDetail> for QuoteUpdate {
+    fn from(d: QuoteUpdateDetail) -> Self {
+        d.update
+    }
+}
+
+/// `f32` prices are read through their shortest decimal form, so `0.1f32` becomes `0.1`.
+fn f32_decimal(v: f32) -> Option<Decimal> {
+    if v.is_finite() {
+        Decimal::from_str(&v.to_string()).ok()
+    } else {
+        None
+    }
+}
+
+fn nonzero_f32(v: f32) -> Option<Decimal> {
+    f32_decimal(v).filter(|d| !d.is_zero())
+}
+
+fn positive(v: i64) -> Option<u64> {
+    u64::try_from(v).ok().filter(|v| *v > 0)
+}
+
+fn nonempty(s: &str) -> Option<String> {
+    Some(s.to_string()).filter(|s| !s.is_empty())
+}
+
+/// Maps a decoded websocket frame; `update` already carries the instrument, time and volume.
+pub(super) fn detail_from_pricing(
+    t: &PricingData,
+    update: QuoteUpdate,
+    normalize: bool,
+) -> QuoteUpdateDetail {
+    let currency = Some(t.currency.as_str()).filter(|c| !c.is_empty());
+    let money = |v: Option<Decimal>| {
+        v.map(|d| decimal_to_money_with_currency_str_normalized(d, currency, normalize))
+    };
+
+    let quote_type = ws_quote_type_to_asset_kind(t.quote_type);
+
+    QuoteUpdateDetail {
+        update,
+        quote_type,
+        market_hours: MarketHours::from_ws(t.market_hours, quote_type),
+        exchange: nonempty(&t.exchange).and_then(|e| string_to_exchange(Some(e))),
+        short_name: nonempty(&t.short_name),
+        price_hint: u32::try_from(t.price_hint).ok().filter(|h| *h > 0),
+        change: money(f32_decimal(t.change)),
+        change_percent: f32_decimal(t.change_percent),
+        open: money(nonzero_f32(t.open_price)),
+        day_high: money(nonzero_f32(t.day_high)),
+        day_low: money(nonzero_f32(t.day_low)),
+        day_volume: positive(t.day_volume),
+        last_size: positive(t.last_size),
+        bid: money(nonzero_f32(t.bid)),
+        bid_size: positive(t.bid_size),
+        ask: money(nonzero_f32(t.ask)),
+        ask_size: positive(t.ask_size),
+        strike: money(nonzero_f32(t.strike_price)),
+        expiry: Some(t.expire_date).filter(|s| *s > 0).map(i64_to_datetime),
+        option_side: (quote_type == Some(AssetKind::Option))
+            .then(|| OptionSide::from_ws(t.options_type))
+            .flatten(),
+        underlying_symbol: nonempty(&t.underlying_symbol),
+        open_interest: positive(t.open_interest),
+        mini_option: t.mini_option != 0,
+        volume_24h: positive(t.vol_24hr),
+        volume_all_currencies: positive(t.vol_all_currencies),
+        from_currency: nonempty(&t.from_currency),
+        last_market: nonempty(&t.last_market),
+        circulating_supply: f64_to_decimal_shortest(t.circulating_supply).filter(|d| !d.is_zero()),
+        market_cap: money(f64_to_decimal_shortest(t.market_cap).filter(|d| !d.is_zero())),
+    }
+}
+
+/// Maps a polled v7 quote node; `update` already carries the instrument, time and volume.
+pub(super) fn detail_from_node(
+    n: &V7QuoteNode,
+    update: QuoteUpdate,
+    normalize: bool,
+) -> QuoteUpdateDetail {
+    let currency = n.currency.as_deref();
+    let money = |v: Option<Decimal>| {
+        v.map(|d| decimal_to_money_with_currency_str_normalized(d, currency, normalize))
+    };
+    let count = |v: Option<Decimal>| v.and_then(|d| d.round().to_u64()).filter(|v| *v > 0);
+
+    QuoteUpdateDetail {
+        update,
+        quote_type: n.quote_type.as_deref().and_then(string_to_asset_kind),
+        market_hours: n
+            .market_state
+            .as_deref()
+            .and_then(MarketHours::from_market_state),
+        exchange: string_to_exchange(n.full_exchange_name.clone().or_else(|| n.exchange.clone())),
+        short_name: n.short_name.clone(),
+        price_hint: None,
+        change: money(n.regular_market_change),
+        change_percent: n.regular_market_change_percent,
+        open: money(n.regular_market_open),
+        day_high: money(n.regular_market_day_high),
+        day_low: money(n.regular_market_day_low),
+        day_volume: n.regular_market_volume.filter(|v| *v > 0),
+        last_size: None,
+        bid: money(n.bid.filter(|d| !d.is_zero())),
+        bid_size: count(n.bid_size),
+        ask: money(n.ask.filter(|d| !d.is_zero())),
+        ask_size: count(n.ask_size),
+        strike: None,
+        expiry: None,
+        option_side: None,
+        underlying_symbol: None,
+        open_interest: None,
+        mini_option: false,
+        volume_24h: None,
+        volume_all_currencies: None,
+        from_currency: None,
+        last_market: None,
+        circulating_supply: None,
+        market_cap: money(n.market_cap),
+    }
+}
@@ -2,11 +2,11 @@ use crate::{
     YfClient, YfError, analysis,
     core::client::{CacheMode, RetryConfig},
     core::conversions::i64_to_datetime,
+    core::instrument::build_instrument,
     esg,
     profile::Profile,
 };
 use paft::aggregates::Info;
-use paft::domain::{AssetKind, Instrument};
 
 /// Private helper to handle optional async results, logging errors in debug mode.
 fn log_err_async<T>(res: Result<T, YfError>, name: &str, symbol: &str) -> Option<T> {
@@ -97,10 +97,7 @@ fn assemble_info(
 ) -> Info {
     Info {
         instrument: quote.map_or_else(
-            || {
-                let kind = AssetKind::Equity;
-                Instrument::from_symbol(symbol, kind).expect("invalid symbol")
-            },
+            || build_instrument(symbol, None, None).expect("invalid symbol"),
             |q| q.instrument.clone(),
         ),
         name: quote.and_then(|q| q.shortname.clone()),
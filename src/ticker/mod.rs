@@ -23,7 +23,10 @@ use crate::{
     news::NewsBuilder,
 };
 use crate::{
-    analysis::AnalysisBuilder, fundamentals::FundamentalsBuilder, history::HistoryBuilder,
+    analysis::AnalysisBuilder,
+    fundamentals::FundamentalsBuilder,
+    history::{DividendAnalytics, HistoryBuilder},
+    quote::QuoteDetail,
 };
 use paft::fundamentals::analysis::{
     Earnings, EarningsTrendRow, PriceTarget, RecommendationRow, RecommendationSummary,
@@ -141,6 +144,23 @@ impl Ticker {
         .await
     }
 
+    /// Fetches the ticker's quote with every field the v7 endpoint reports, including bid/ask,
+    /// day range, 52-week range, averages, valuation and extended-hours prices.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the request fails or the response cannot be parsed.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), err, fields(symbol = %self.symbol)))]
+    pub async fn quote_detail(&self) -> Result<QuoteDetail, YfError> {
+        quote::fetch_quote_detail(
+            &self.client,
+            &self.symbol,
+            self.cache_mode,
+            self.retry_override.as_ref(),
+        )
+        .await
+    }
+
     /// Fetches a "fast" info quote, containing the most essential price and market data.
     ///
     /// # Errors
@@ -271,6 +291,31 @@ impl Ticker {
             .collect())
     }
 
+    /// Fetches unadjusted history and dividends for the given range and returns dividend
+    /// analytics over them (TTM yield, frequency, special dividends, growth).
+    ///
+    /// Defaults to the maximum available range if `None`.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the request fails or the response cannot be parsed.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), err, fields(symbol = %self.symbol)))]
+    pub async fn dividend_analytics(
+        &self,
+        range: Option<Range>,
+    ) -> Result<DividendAnalytics, YfError> {
+        let resp = self
+            .history_builder()
+            .range(range.unwrap_or(Range::Max))
+            .auto_adjust(false)
+            .actions(true)
+            .cache_mode(self.cache_mode)
+            .retry_policy(self.retry_override.clone())
+            .fetch_full()
+            .await?;
+        Ok(DividendAnalytics::from_history(&resp))
+    }
+
     /// Fetches all stock splits for the given range.
     ///
     /// Returns a `Vec` of tuples containing `(timestamp, numerator, denominator)`.
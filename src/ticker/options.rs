@@ -1,5 +1,6 @@
 use std::str::FromStr;
 
+use rust_decimal::Decimal;
 use serde::Deserialize;
 use url::Url;
 
@@ -7,8 +8,10 @@ use crate::{
     YfClient, YfError,
     core::{
         client::{CacheMode, RetryConfig},
-        conversions::{f64_to_decimal_safely, f64_to_money_with_currency, i64_to_datetime},
+        conversions::{decimal_to_money_with_currency, f64_to_decimal_safely, i64_to_datetime},
+        currency::{minor_unit, to_major_units},
         net,
+        wire::de_opt_decimal_lenient,
     },
 };
 use paft::money::Currency;
@@ -56,6 +59,11 @@ pub async fn option_chain(
         .ok_or_else(|| YfError::MissingData("empty options result".into()))?;
 
     let currency_from_response = currency_from_result(&first);
+    // Only the options payload tells us the raw unit; the quote fallback is already normalized.
+    let minor_code = currency_code_from_result(&first)
+        .filter(|_| client.normalize_minor_units())
+        .filter(|code| minor_unit(code).is_some())
+        .map(str::to_string);
 
     let Some(od) = first.options.and_then(|mut v| v.pop()) else {
         return Ok(OptionChain {
@@ -91,48 +99,46 @@ pub async fn option_chain(
             })?
     };
 
-    let map_side = |side: Option<Vec<OptContractNode>>,
-                    currency: &Currency|
-     -> Vec<OptionContract> {
-        side.unwrap_or_default()
-            .into_iter()
-            .filter_map(|c| {
-                let sym = c.contract_symbol.as_deref().unwrap_or("");
-                let Ok(instrument) = Instrument::from_symbol(sym, AssetKind::Option) else {
-                    return None;
-                };
-
-                let exp_ts = c.expiration.unwrap_or(expiration);
-                let exp_dt = i64_to_datetime(exp_ts);
-                let exp_date: NaiveDate = exp_dt.date_naive();
-
-                Some(OptionContract {
-                    instrument,
-                    strike: f64_to_money_with_currency(c.strike.unwrap_or(0.0), currency.clone()),
-                    price: c
-                        .last_price
-                        .map(|v| f64_to_money_with_currency(v, currency.clone())),
-                    bid: c
-                        .bid
-                        .map(|v| f64_to_money_with_currency(v, currency.clone())),
-                    ask: c
-                        .ask
-                        .map(|v| f64_to_money_with_currency(v, currency.clone())),
-                    volume: c.volume,
-                    open_interest: c.open_interest,
-                    implied_volatility: c.implied_volatility.map(f64_to_decimal_safely),
-                    in_the_money: c.in_the_money.unwrap_or(false),
-                    expiration_date: exp_date,
-                    expiration_at: Some(exp_dt),
-                    last_trade_at: c
-                        .last_trade_date
-                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
-                    greeks: None,
-                })
-            })
-            .collect()
+    let money = |v: Decimal, currency: &Currency| {
+        let m = decimal_to_money_with_currency(v, currency.clone());
+        to_major_units(m, minor_code.as_deref())
     };
 
+    let map_side =
+        |side: Option<Vec<OptContractNode>>, currency: &Currency| -> Vec<OptionContract> {
+            side.unwrap_or_default()
+                .into_iter()
+                .filter_map(|c| {
+                    let sym = c.contract_symbol.as_deref().unwrap_or("");
+                    let Ok(instrument) = Instrument::from_symbol(sym, AssetKind::Option) else {
+                        return None;
+                    };
+
+                    let exp_ts = c.expiration.unwrap_or(expiration);
+                    let exp_dt = i64_to_datetime(exp_ts);
+                    let exp_date: NaiveDate = exp_dt.date_naive();
+
+                    Some(OptionContract {
+                        instrument,
+                        strike: money(c.strike.unwrap_or_default(), currency),
+                        price: c.last_price.map(|v| money(v, currency)),
+                        bid: c.bid.map(|v| money(v, currency)),
+                        ask: c.ask.map(|v| money(v, currency)),
+                        volume: c.volume,
+                        open_interest: c.open_interest,
+                        implied_volatility: c.implied_volatility.map(f64_to_decimal_safely),
+                        in_the_money: c.in_the_money.unwrap_or(false),
+                        expiration_date: exp_date,
+                        expiration_at: Some(exp_dt),
+                        last_trade_at: c
+                            .last_trade_date
+                            .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                        greeks: None,
+                    })
+                })
+                .collect()
+        };
+
     Ok(OptionChain {
         calls: map_side(od.calls, &currency),
         puts: map_side(od.puts, &currency),
@@ -281,11 +287,18 @@ struct OptContractNode {
     expiration: Option<i64>,
     #[serde(rename = "lastTradeDate")]
     last_trade_date: Option<i64>,
-    strike: Option<f64>,
-    #[serde(rename = "lastPrice")]
-    last_price: Option<f64>,
-    bid: Option<f64>,
-    ask: Option<f64>,
+    #[serde(default, deserialize_with = "de_opt_decimal_lenient")]
+    strike: Option<Decimal>,
+    #[serde(
+        default,
+        rename = "lastPrice",
+        deserialize_with = "de_opt_decimal_lenient"
+    )]
+    last_price: Option<Decimal>,
+    #[serde(default, deserialize_with = "de_opt_decimal_lenient")]
+    bid: Option<Decimal>,
+    #[serde(default, deserialize_with = "de_opt_decimal_lenient")]
+    ask: Option<Decimal>,
     volume: Option<u64>,
     #[serde(rename = "openInterest")]
     open_interest: Option<u64>,
@@ -301,3 +314,7 @@ fn currency_from_result(node: &OptResultNode) -> Option<Currency> {
         .and_then(|q| q.currency.as_deref())
         .and_then(|code| Currency::from_str(code).ok())
 }
+
+fn currency_code_from_result(node: &OptResultNode) -> Option<&str> {
+    node.quote.as_ref().and_then(|q| q.currency.as_deref())
+}
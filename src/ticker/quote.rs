@@ -4,6 +4,7 @@ use crate::core::{
     models::Quote,
     quotes,
 };
+use crate::quote::QuoteDetail;
 
 pub async fn fetch_quote(
     client: &YfClient,
@@ -11,13 +12,39 @@ pub async fn fetch_quote(
     cache_mode: CacheMode,
     retry_override: Option<&RetryConfig>,
 ) -> Result<Quote, YfError> {
-    let symbols = [symbol];
-    let mut results = quotes::fetch_v7_quotes(client, &symbols, cache_mode, retry_override).await?;
-
-    let result = results.pop().ok_or_else(|| {
-        YfError::MissingData(format!("no quote result found for symbol {symbol}"))
-    })?;
+    let node = fetch_node(client, symbol, cache_mode, retry_override).await?;
 
     // Use the same currency-aware conversion as the batch quotes API
-    Ok(result.into())
+    Ok(quotes::quote_from_node(
+        node,
+        client.normalize_minor_units(),
+    ))
+}
+
+pub async fn fetch_quote_detail(
+    client: &YfClient,
+    symbol: &str,
+    cache_mode: CacheMode,
+    retry_override: Option<&RetryConfig>,
+) -> Result<QuoteDetail, YfError> {
+    let node = fetch_node(client, symbol, cache_mode, retry_override).await?;
+    Ok(quotes::detail_from_node(
+        node,
+        client.normalize_minor_units(),
+    ))
+}
+
+async fn fetch_node(
+    client: &YfClient,
+    symbol: &str,
+    cache_mode: CacheMode,
+    retry_override: Option<&RetryConfig>,
+) -> Result<quotes::V7QuoteNode, YfError> {
+    let symbols = [symbol];
+    let mut results =
+        quotes::fetch_v7_quotes(client, &symbols, None, cache_mode, retry_override).await?;
+
+    results
+        .pop()
+        .ok_or_else(|| YfError::MissingData(format!("no quote result found for symbol {symbol}")))
 }
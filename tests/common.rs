@@ -1,16 +1,102 @@
 #![allow(dead_code)]
 
 use httpmock::{Method::GET, Mock, MockServer};
+use rust_decimal::Decimal;
 use std::{
     fs,
     path::{Path, PathBuf},
+    str::FromStr,
 };
+use url::Url;
+use yfinance_rs::{YfClient, YfClientBuilder};
 
 #[must_use]
 pub fn setup_server() -> MockServer {
     MockServer::start()
 }
 
+/// A client builder with the chart, v7 quote and spark endpoints pointed at `server`.
+#[must_use]
+pub fn client_builder(server: &MockServer) -> YfClientBuilder {
+    let url = |path: &str| Url::parse(&format!("{}{path}", server.base_url())).unwrap();
+    YfClient::builder()
+        .base_chart(url("/v8/finance/chart/"))
+        .base_quote_v7(url("/v7/finance/quote"))
+        .base_spark(url("/v8/finance/spark"))
+}
+
+/// A client with the chart, v7 quote and spark endpoints pointed at `server`.
+///
+/// # Panics
+///
+/// Panics if the client cannot be built.
+#[must_use]
+pub fn client_for(server: &MockServer) -> YfClient {
+    client_builder(server).build().unwrap()
+}
+
+/// Parses a decimal literal.
+///
+/// # Panics
+///
+/// Panics if `s` is not a valid decimal.
+#[must_use]
+pub fn dec(s: &str) -> Decimal {
+    Decimal::from_str(s).unwrap()
+}
+
+/// A chart payload with flat bars at `close` (adjusted close included), a volume of 100 and
+/// the given `events` members, e.g. `"splits": {...}`.
+///
+/// # Panics
+///
+/// Panics if the series cannot be serialized.
+#[must_use]
+pub fn chart_body(ts: &[i64], close: &[f64], events: &str) -> String {
+    let volume = serde_json::to_string(&vec![100_u64; ts.len()]).unwrap();
+    let ts = serde_json::to_string(ts).unwrap();
+    let close = serde_json::to_string(close).unwrap();
+    format!(
+        r#"{{
+      "chart":{{
+        "result":[
+          {{
+            "timestamp":{ts},
+            "indicators":{{
+              "quote":[{{
+                "open":{close},
+                "high":{close},
+                "low":{close},
+                "close":{close},
+                "volume":{volume}
+              }}],
+              "adjclose":[{{"adjclose":{close}}}]
+            }},
+            "events": {{ {events} }}
+          }}
+        ],
+        "error": null
+      }}
+    }}"#
+    )
+}
+
+/// Mocks the chart endpoint for `symbol` with an inline payload.
+#[must_use]
+pub fn mock_chart_body<'a>(
+    server: &'a MockServer,
+    symbol: &str,
+    body: impl Into<String>,
+) -> Mock<'a> {
+    let body = body.into();
+    server.mock(|when, then| {
+        when.method(GET).path(format!("/v8/finance/chart/{symbol}"));
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(body);
+    })
+}
+
 fn fixture_dir() -> PathBuf {
     std::env::var("YF_FIXDIR").map_or_else(
         |_| Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures"),
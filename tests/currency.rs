@@ -4,5 +4,7 @@ mod common;
 mod inference_live;
 #[path = "currency/inference_offline.rs"]
 mod inference_offline;
+#[path = "currency/minor_units.rs"]
+mod minor_units;
 #[path = "currency/verification.rs"]
 mod verification;
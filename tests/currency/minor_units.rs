@@ -0,0 +1,118 @@
+use crate::common::{client_builder, mock_chart_body, setup_server};
+use httpmock::Method::GET;
+use httpmock::MockServer;
+use paft::money::{Currency, IsoCurrency};
+use yfinance_rs::core::conversions::*;
+use yfinance_rs::{HistoryBuilder, QuotesBuilder, YfClient};
+
+const GBP_CHART: &str = r#"{
+  "chart":{
+    "result":[
+      {
+        "meta":{ "currency":"GBp", "symbol":"TSCO.L" },
+        "timestamp":[1000,2000],
+        "indicators":{
+          "quote":[{
+            "open":[1234.5,1240.0],
+            "high":[1250.0,1250.0],
+            "low":[1230.0,1230.0],
+            "close":[1245.25,1248.0],
+            "volume":[10,10]
+          }],
+          "adjclose":[{"adjclose":[1245.25,1248.0]}]
+        }
+      }
+    ],
+    "error": null
+  }
+}"#;
+
+fn chart_client(server: &MockServer, normalize: bool) -> YfClient {
+    client_builder(server)
+        .normalize_minor_units(normalize)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn history_minor_units_normalized_to_major_currency() {
+    let server = setup_server();
+    let _mock = mock_chart_body(&server, "TSCO.L", GBP_CHART);
+
+    let client = chart_client(&server, true);
+    let candles = HistoryBuilder::new(&client, "TSCO.L")
+        .fetch()
+        .await
+        .unwrap();
+
+    assert_eq!(candles.len(), 2);
+    assert!((money_to_f64(&candles[0].open) - 12.345).abs() < 1e-12);
+    assert!((money_to_f64(&candles[0].close) - 12.4525).abs() < 1e-12);
+    assert_eq!(
+        candles[0].close.currency(),
+        &Currency::Iso(IsoCurrency::GBP)
+    );
+    let raw = candles[0].close_unadj.as_ref().unwrap();
+    assert_eq!(raw.currency(), &Currency::Iso(IsoCurrency::GBP));
+    assert!((money_to_f64(raw) - 12.4525).abs() < 1e-12);
+}
+
+#[tokio::test]
+async fn history_minor_units_untouched_by_default() {
+    let server = setup_server();
+    let _mock = mock_chart_body(&server, "TSCO.L", GBP_CHART);
+
+    let client = chart_client(&server, false);
+    let candles = HistoryBuilder::new(&client, "TSCO.L")
+        .fetch()
+        .await
+        .unwrap();
+
+    assert!((money_to_f64(&candles[0].close) - 1245.25).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn quotes_minor_units_normalized_to_major_currency() {
+    let server = setup_server();
+    server.mock(|when, then| {
+        when.method(GET).path("/v7/finance/quote");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(
+                r#"{
+              "quoteResponse": {
+                "result": [
+                  { "symbol":"TSCO.L", "regularMarketPrice": 325.4, "regularMarketPreviousClose": 320.0, "currency":"GBp" },
+                  { "symbol":"NPN.JO", "regularMarketPrice": 350000.0, "currency":"ZAc" },
+                  { "symbol":"AAPL", "regularMarketPrice": 123.0, "currency":"USD" }
+                ],
+                "error": null
+              }
+            }"#,
+            );
+    });
+
+    let client = client_builder(&server)
+        .normalize_minor_units(true)
+        .build()
+        .unwrap();
+
+    let quotes = QuotesBuilder::new(client)
+        .symbols(["TSCO.L", "NPN.JO", "AAPL"])
+        .fetch()
+        .await
+        .unwrap();
+
+    let tsco = quotes[0].price.as_ref().unwrap();
+    assert!((money_to_f64(tsco) - 3.254).abs() < 1e-12);
+    assert_eq!(tsco.currency(), &Currency::Iso(IsoCurrency::GBP));
+    assert!((money_to_f64(quotes[0].previous_close.as_ref().unwrap()) - 3.2).abs() < 1e-12);
+
+    let npn = quotes[1].price.as_ref().unwrap();
+    assert!((money_to_f64(npn) - 3500.0).abs() < 1e-9);
+    assert_eq!(npn.currency(), &Currency::Iso(IsoCurrency::ZAR));
+
+    let aapl = quotes[2].price.as_ref().unwrap();
+    assert!((money_to_f64(aapl) - 123.0).abs() < 1e-9);
+    assert_eq!(aapl.currency(), &Currency::Iso(IsoCurrency::USD));
+}
@@ -8,5 +8,15 @@ mod download_keepna_rounding;
 mod download_live;
 #[path = "download/offline.rs"]
 mod download_offline;
+#[path = "download/overrides.rs"]
+mod download_overrides;
+#[path = "download/panel.rs"]
+mod download_panel;
+#[path = "download/partial.rs"]
+mod download_partial;
+#[path = "download/progress.rs"]
+mod download_progress;
 #[path = "download/repair.rs"]
 mod download_repair;
+#[path = "download/rounding_places.rs"]
+mod download_rounding_places;
@@ -0,0 +1,61 @@
+use crate::common::{client_for, setup_server};
+use httpmock::Method::GET;
+use yfinance_rs::download::SymbolOverrides;
+use yfinance_rs::{DownloadBuilder, Interval, Range};
+
+const BODY: &str = r#"{"chart":{"result":[{
+    "meta":{"currency":"USD"},
+    "timestamp":[1000],
+    "indicators":{"quote":[{"open":[1.0],"high":[1.5],"low":[0.5],"close":[1.0],"volume":[10]}]}
+}],"error":null}}"#;
+
+#[tokio::test]
+async fn overrides_replace_defaults_per_symbol() {
+    let server = setup_server();
+    let plain = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/chart/SPY")
+            .query_param("range", "1y")
+            .query_param("interval", "1d")
+            .query_param("events", "div|split|capitalGains")
+            .query_param("includePrePost", "true");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(BODY);
+    });
+    let crypto = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/chart/BTC-USD")
+            .query_param("range", "max")
+            .query_param("interval", "1wk")
+            .query_param("includePrePost", "false");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(BODY);
+    });
+
+    let client = client_for(&server);
+
+    let res = DownloadBuilder::new(&client)
+        .symbols(["SPY", "BTC-USD"])
+        .range(Range::Y1)
+        .prepost(true)
+        .override_symbol(
+            "BTC-USD",
+            SymbolOverrides::new()
+                .range(Range::Max)
+                .interval(Interval::W1)
+                .prepost(false)
+                .actions(false)
+                .auto_adjust(false),
+        )
+        .run()
+        .await
+        .unwrap();
+
+    plain.assert();
+    crypto.assert();
+    assert_eq!(res.entries.len(), 2);
+    assert!(res.entries[0].history.adjusted);
+    assert!(!res.entries[1].history.adjusted);
+}
@@ -0,0 +1,137 @@
+use crate::common::{client_for, mock_chart_body, setup_server};
+use rust_decimal::Decimal;
+use yfinance_rs::download::{FillPolicy, PanelBuilder, PanelIndex};
+use yfinance_rs::history::PriceField;
+use yfinance_rs::{DownloadBuilder, DownloadResponse};
+
+fn chart(ts: &[i64], closes: &[f64]) -> String {
+    let list = |v: &[f64]| {
+        v.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let ts = ts
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    let c = list(closes);
+    format!(
+        r#"{{"chart":{{"result":[{{"meta":{{"currency":"USD"}},"timestamp":[{ts}],"indicators":{{"quote":[{{"open":[{c}],"high":[{c}],"low":[{c}],"close":[{c}],"volume":[{v}]}}]}}}}],"error":null}}}}"#,
+        v = closes.iter().map(|_| "100").collect::<Vec<_>>().join(",")
+    )
+}
+
+async fn download(series: &[(&str, String)]) -> DownloadResponse {
+    let server = setup_server();
+    for (sym, body) in series {
+        let _mock = mock_chart_body(&server, sym, body.as_str());
+    }
+    let client = client_for(&server);
+    DownloadBuilder::new(&client)
+        .symbols(series.iter().map(|(s, _)| *s))
+        .auto_adjust(false)
+        .run()
+        .await
+        .unwrap()
+}
+
+fn closes(panel: &yfinance_rs::download::Panel, sym: &str) -> Vec<Option<Decimal>> {
+    panel
+        .prices(sym, PriceField::Close)
+        .unwrap()
+        .into_iter()
+        .map(|m| m.map(|m| m.amount()))
+        .collect()
+}
+
+fn d(n: i64) -> Option<Decimal> {
+    Some(Decimal::from(n))
+}
+
+async fn two_calendars() -> DownloadResponse {
+    download(&[
+        (
+            "AAA",
+            chart(&[1000, 2000, 3000, 4000], &[1.0, 2.0, 3.0, 4.0]),
+        ),
+        ("BBB", chart(&[2000, 5000], &[20.0, 50.0])),
+    ])
+    .await
+}
+
+#[tokio::test]
+async fn union_and_intersection_indexes() {
+    let dl = two_calendars().await;
+
+    let union = PanelBuilder::new(&dl).build();
+    let ts: Vec<i64> = union
+        .index()
+        .iter()
+        .map(chrono::DateTime::timestamp)
+        .collect();
+    assert_eq!(ts, [1000, 2000, 3000, 4000, 5000]);
+    assert_eq!(union.symbols(), ["AAA", "BBB"]);
+    assert_eq!(closes(&union, "BBB"), [None, d(20), None, None, d(50)]);
+    assert_eq!(
+        union.volumes("AAA").unwrap(),
+        [Some(100), Some(100), Some(100), Some(100), None]
+    );
+
+    let inter = PanelBuilder::new(&dl)
+        .index(PanelIndex::Intersection)
+        .build();
+    assert_eq!(inter.len(), 1);
+    assert_eq!(inter.index()[0].timestamp(), 2000);
+    assert_eq!(closes(&inter, "AAA"), [d(2)]);
+    assert!(inter.candles("ZZZ").is_none());
+}
+
+#[tokio::test]
+async fn forward_fill_with_and_without_limit() {
+    let dl = two_calendars().await;
+
+    let full = PanelBuilder::new(&dl).fill(FillPolicy::ForwardFill).build();
+    // Leading gaps stay empty.
+    assert_eq!(closes(&full, "BBB"), [None, d(20), d(20), d(20), d(50)]);
+    assert_eq!(closes(&full, "AAA")[4], d(4));
+    assert!(full.is_filled("BBB", 2));
+    assert!(!full.is_filled("BBB", 1));
+    assert!(!full.is_filled("BBB", 0));
+    assert_eq!(full.volumes("BBB").unwrap()[2], Some(0));
+
+    let limited = PanelBuilder::new(&dl)
+        .fill(FillPolicy::ForwardFillLimit(1))
+        .build();
+    assert_eq!(closes(&limited, "BBB"), [None, d(20), d(20), None, d(50)]);
+}
+
+#[tokio::test]
+async fn align_on_dates_matches_bars_across_timezones() {
+    // Same UTC date, stamped at 13:30 and 07:00.
+    let dl = download(&[
+        ("USA", chart(&[48_600, 135_000], &[1.0, 2.0])),
+        ("EUR", chart(&[25_200], &[10.0])),
+    ])
+    .await;
+
+    assert_eq!(PanelBuilder::new(&dl).build().len(), 3);
+
+    let panel = PanelBuilder::new(&dl).align_on_dates(true).build();
+    let ts: Vec<i64> = panel
+        .index()
+        .iter()
+        .map(chrono::DateTime::timestamp)
+        .collect();
+    assert_eq!(ts, [0, 86_400]);
+    assert_eq!(closes(&panel, "EUR"), [d(10), None]);
+    assert_eq!(
+        panel.candles("EUR").unwrap()[0]
+            .as_ref()
+            .unwrap()
+            .ts
+            .timestamp(),
+        25_200
+    );
+}
@@ -0,0 +1,75 @@
+use crate::common::{client_builder, mock_chart_body, setup_server};
+use httpmock::Method::GET;
+use httpmock::MockServer;
+use yfinance_rs::{DownloadBuilder, YfClient, YfError};
+
+const OK_BODY: &str = r#"{"chart":{"result":[{
+    "meta":{"currency":"USD"},
+    "timestamp":[1000,2000],
+    "indicators":{"quote":[{"open":[1.0,2.0],"high":[1.5,2.5],"low":[0.5,1.5],"close":[1.0,2.0],"volume":[10,20]}]}
+}],"error":null}}"#;
+
+fn no_retry_client(server: &MockServer) -> YfClient {
+    client_builder(server).retry_enabled(false).build().unwrap()
+}
+
+#[tokio::test]
+async fn run_partial_keeps_successes_and_reports_failures() {
+    let server = setup_server();
+    let _mock = mock_chart_body(&server, "GOOD", OK_BODY);
+    server.mock(|when, then| {
+        when.method(GET).path("/v8/finance/chart/GONE");
+        then.status(404);
+    });
+    let client = no_retry_client(&server);
+
+    let out = DownloadBuilder::new(&client)
+        .symbols(["GOOD", "GONE"])
+        .run_partial()
+        .await
+        .unwrap();
+
+    assert!(!out.is_complete());
+    assert_eq!(out.response.entries.len(), 1);
+    assert_eq!(out.response.entries[0].history.candles.len(), 2);
+    assert!(out.repairs.contains_key("GOOD"));
+    assert!(matches!(
+        out.failures.get("GONE"),
+        Some(YfError::NotFound { .. })
+    ));
+
+    // The strict path still fails as a whole.
+    let strict = DownloadBuilder::new(&client)
+        .symbols(["GOOD", "GONE"])
+        .run()
+        .await;
+    assert!(matches!(strict, Err(YfError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn retry_failed_reattempts_only_transient_failures() {
+    let server = setup_server();
+    let flaky = server.mock(|when, then| {
+        when.method(GET).path("/v8/finance/chart/FLAKY");
+        then.status(503);
+    });
+    let gone = server.mock(|when, then| {
+        when.method(GET).path("/v8/finance/chart/GONE");
+        then.status(404);
+    });
+    let client = no_retry_client(&server);
+
+    let out = DownloadBuilder::new(&client)
+        .symbols(["FLAKY", "GONE"])
+        .retry_failed(true)
+        .run_partial()
+        .await
+        .unwrap();
+
+    flaky.assert_calls(2);
+    gone.assert_calls(1);
+    assert!(out.response.entries.is_empty());
+    assert_eq!(out.failures.len(), 2);
+    assert!(out.failures["FLAKY"].is_transient());
+    assert!(!out.failures["GONE"].is_transient());
+}
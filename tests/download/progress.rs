@@ -0,0 +1,87 @@
+use crate::common::{client_builder, mock_chart_body, setup_server};
+use futures::StreamExt;
+use httpmock::Method::GET;
+use httpmock::MockServer;
+use std::sync::{Arc, Mutex};
+use yfinance_rs::{DownloadBuilder, DownloadProgress, YfClient};
+
+const OK_BODY: &str = r#"{"chart":{"result":[{
+    "meta":{"currency":"USD"},
+    "timestamp":[1000],
+    "indicators":{"quote":[{"open":[1.0],"high":[1.5],"low":[0.5],"close":[1.0],"volume":[10]}]}
+}],"error":null}}"#;
+
+fn setup(server: &MockServer) -> YfClient {
+    for sym in ["AAA", "BBB", "CCC"] {
+        let _mock = mock_chart_body(server, sym, OK_BODY);
+    }
+    server.mock(|when, then| {
+        when.method(GET).path("/v8/finance/chart/BAD");
+        then.status(404);
+    });
+    client_builder(server).retry_enabled(false).build().unwrap()
+}
+
+#[tokio::test]
+async fn progress_reports_running_counts() {
+    let server = setup_server();
+    let client = setup(&server);
+
+    let seen: Arc<Mutex<Vec<DownloadProgress>>> = Arc::default();
+    let sink = Arc::clone(&seen);
+    let out = DownloadBuilder::new(&client)
+        .symbols(["AAA", "BAD", "BBB", "CCC"])
+        .concurrency(2)
+        .on_progress(move |p| sink.lock().unwrap().push(p.clone()))
+        .run_partial()
+        .await
+        .unwrap();
+
+    // Entries keep request order regardless of completion order.
+    assert_eq!(out.response.entries.len(), 3);
+    assert_eq!(out.failures.len(), 1);
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 4);
+    for (i, p) in seen.iter().enumerate() {
+        assert_eq!(p.completed, i + 1);
+        assert_eq!(p.remaining, 4 - (i + 1));
+        assert_eq!(p.total, 4);
+    }
+    let last = seen.last().unwrap();
+    assert_eq!(last.failed, 1);
+    let bad = seen.iter().find(|p| p.symbol == "BAD").unwrap();
+    assert!(!bad.succeeded);
+}
+
+#[tokio::test]
+async fn stream_yields_each_symbol_once() {
+    let server = setup_server();
+    let client = setup(&server);
+
+    let results: Vec<_> = DownloadBuilder::new(&client)
+        .symbols(["AAA", "BBB", "BAD"])
+        .concurrency(1)
+        .stream()
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert_eq!(results.len(), 3);
+    let mut ok: Vec<&str> = results
+        .iter()
+        .filter(|(_, r)| r.is_ok())
+        .map(|(s, _)| s.as_str())
+        .collect();
+    ok.sort_unstable();
+    assert_eq!(ok, ["AAA", "BBB"]);
+    let (_, bad) = results.iter().find(|(s, _)| s == "BAD").unwrap();
+    assert!(bad.is_err());
+}
+
+#[tokio::test]
+async fn stream_rejects_empty_symbol_list() {
+    let client = YfClient::default();
+    assert!(DownloadBuilder::new(&client).stream().await.is_err());
+}
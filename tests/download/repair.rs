@@ -70,3 +70,58 @@ async fn download_repair_simple_100x_fix() {
     assert!((money_to_f64(&v[1].high) - 11.0).abs() < 1e-9);
     assert!((money_to_f64(&v[1].low) - 9.0).abs() < 1e-9);
 }
+
+#[tokio::test]
+async fn download_repair_reports_log_per_symbol() {
+    let body = r#"{
+      "chart": {
+        "result": [{
+          "timestamp": [1, 2, 3],
+          "indicators": {
+            "quote": [{
+              "open":  [ 10.0, 1000.0, 10.5],
+              "high":  [ 11.0, 1100.0, 11.0],
+              "low":   [  9.0,  900.0, 10.0],
+              "close": [ 10.5, 1050.0, 10.8],
+              "volume":[ 100,    100,   100]
+            }],
+            "adjclose": [{
+              "adjclose": [10.5, 1050.0, 10.8]
+            }]
+          }
+        }],
+        "error": null
+      }
+    }"#;
+
+    let server = MockServer::start();
+    let sym = "FIX";
+
+    server.mock(|when, then| {
+        when.method(GET).path(format!("/v8/finance/chart/{sym}"));
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(body);
+    });
+
+    let client = YfClient::builder()
+        .base_chart(Url::parse(&format!("{}/v8/finance/chart/", server.base_url())).unwrap())
+        .build()
+        .unwrap();
+
+    let (res, logs) = yfinance_rs::DownloadBuilder::new(&client)
+        .symbols([sym])
+        .repair(true)
+        .run_with_repairs()
+        .await
+        .unwrap();
+
+    assert_eq!(res.entries.len(), 1);
+    let log = logs.get(sym).expect("log for symbol");
+    assert_eq!(log.len(), 1);
+    assert_eq!(log.entries[0].ts.timestamp(), 2);
+    assert_eq!(
+        log.entries[0].kind,
+        yfinance_rs::history::RepairKind::UnitScaled { factor: 0.01 }
+    );
+}
@@ -0,0 +1,82 @@
+use crate::common::{client_for, dec, mock_chart_body, setup_server};
+use httpmock::Mock;
+use httpmock::MockServer;
+use rust_decimal::RoundingStrategy;
+use yfinance_rs::DownloadBuilder;
+
+fn mock_chart<'a>(server: &'a MockServer, sym: &str) -> Mock<'a> {
+    let body = r#"{
+      "chart": {
+        "result": [{
+          "timestamp": [10, 20],
+          "indicators": {
+            "quote": [{
+              "open":  [2.125, 2.135],
+              "high":  [2.1255, 2.2],
+              "low":   [2.115, 2.1],
+              "close": [2.125, 2.1349],
+              "volume":[1000, 2000]
+            }]
+          }
+        }],
+        "error": null
+      }
+    }"#;
+    mock_chart_body(server, sym, body)
+}
+
+#[tokio::test]
+async fn download_rounding_defaults_to_two_places_half_away_from_zero() {
+    let server = setup_server();
+    let mock = mock_chart(&server, "RND");
+
+    let res = DownloadBuilder::new(&client_for(&server))
+        .symbols(["RND"])
+        .auto_adjust(false)
+        .rounding(true)
+        .run()
+        .await
+        .unwrap();
+    mock.assert();
+
+    let c = &res.entries[0].history.candles;
+    assert_eq!(c[0].open.amount(), dec("2.13"));
+    assert_eq!(c[0].low.amount(), dec("2.12"));
+    assert_eq!(c[0].close.amount(), dec("2.13"));
+    assert_eq!(c[1].close.amount(), dec("2.13"));
+}
+
+#[tokio::test]
+async fn download_rounding_honours_places_and_strategy() {
+    let server = setup_server();
+    let mock = mock_chart(&server, "RND");
+
+    let res = DownloadBuilder::new(&client_for(&server))
+        .symbols(["RND"])
+        .auto_adjust(false)
+        .rounding(true)
+        .rounding_places(3)
+        .rounding_strategy(RoundingStrategy::MidpointNearestEven)
+        .run()
+        .await
+        .unwrap();
+    mock.assert();
+
+    let c = &res.entries[0].history.candles;
+    assert_eq!(c[0].open.amount(), dec("2.125"));
+    assert_eq!(c[0].high.amount(), dec("2.126"));
+    assert_eq!(c[1].close.amount(), dec("2.135"));
+
+    let res = DownloadBuilder::new(&client_for(&server))
+        .symbols(["RND"])
+        .auto_adjust(false)
+        .rounding(true)
+        .rounding_strategy(RoundingStrategy::MidpointNearestEven)
+        .run()
+        .await
+        .unwrap();
+
+    let c = &res.entries[0].history.candles;
+    assert_eq!(c[0].open.amount(), dec("2.12"));
+    assert_eq!(c[1].open.amount(), dec("2.14"));
+}
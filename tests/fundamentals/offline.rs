@@ -197,3 +197,65 @@ async fn offline_calendar_uses_recorded_fixture() {
         "record with YF_RECORD=1 first"
     );
 }
+
+/* ---------- out-of-range timeseries values ---------- */
+
+#[tokio::test]
+async fn offline_income_drops_only_the_unreadable_period() {
+    let sym = "AAPL";
+    let server = MockServer::start();
+
+    let body = r#"{
+      "timeseries": {
+        "result": [
+          {
+            "meta": { "type": ["annualTotalRevenue"] },
+            "timestamp": [1672444800, 1703980800],
+            "annualTotalRevenue": [
+              { "asOfDate": "2022-12-31", "periodType": "12M", "currencyCode": "USD", "reportedValue": {"raw": 1e300} },
+              { "asOfDate": "2023-12-31", "periodType": "12M", "currencyCode": "USD", "reportedValue": {"raw": 200.5} }
+            ]
+          }
+        ],
+        "error": null
+      }
+    }"#;
+
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path(format!(
+                "/ws/fundamentals-timeseries/v1/finance/timeseries/{sym}"
+            ))
+            .query_param_exists("type")
+            .query_param("crumb", "crumb");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(body);
+    });
+
+    let client = YfClient::builder()
+        .base_timeseries(
+            Url::parse(&format!(
+                "{}/ws/fundamentals-timeseries/v1/finance/timeseries/",
+                server.base_url()
+            ))
+            .unwrap(),
+        )
+        ._preauth("cookie", "crumb")
+        .build()
+        .unwrap();
+
+    let rows = Ticker::new(&client, sym).income_stmt(None).await.unwrap();
+
+    mock.assert();
+    assert_eq!(rows.len(), 2);
+    // Newest period first; the other period keeps its row but loses the value.
+    assert_eq!(
+        rows[0]
+            .total_revenue
+            .as_ref()
+            .map(paft::money::Money::amount),
+        Some(crate::common::dec("200.5"))
+    );
+    assert!(rows[1].total_revenue.is_none());
+}
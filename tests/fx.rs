@@ -0,0 +1,4 @@
+mod common;
+
+#[path = "fx/convert.rs"]
+mod fx_convert;
@@ -0,0 +1,127 @@
+use crate::common::{client_for, dec, mock_chart_body, setup_server};
+use chrono::NaiveDate;
+use httpmock::Method::GET;
+use paft::money::{Currency, IsoCurrency};
+use yfinance_rs::core::conversions::decimal_to_money_with_currency;
+use yfinance_rs::{Action, FxConverter, HistoryBuilder};
+
+// 2024-01-02 and 2024-01-03, with a dividend on the 3rd.
+const SAP_CHART: &str = r#"{"chart":{"result":[{
+    "meta":{"currency":"EUR","symbol":"SAP.DE"},
+    "timestamp":[1704153600,1704240000],
+    "indicators":{"quote":[{"open":[99.0,105.0],"high":[101.0,111.0],"low":[98.0,104.0],"close":[100.0,110.0],"volume":[10,20]}]},
+    "events":{"dividends":{"1704240000":{"date":1704240000,"amount":1.0}}}
+}],"error":null}}"#;
+
+// Rates for 2024-01-01 and 2024-01-02 only: the 3rd falls back to the 2nd.
+const EURUSD_CHART: &str = r#"{"chart":{"result":[{
+    "meta":{"currency":"USD","symbol":"EURUSD=X"},
+    "timestamp":[1704067200,1704153600],
+    "indicators":{"quote":[{"open":[1.1,1.2],"high":[1.1,1.2],"low":[1.1,1.2],"close":[1.1,1.2],"volume":[0,0]}]}
+}],"error":null}}"#;
+
+fn usd() -> Currency {
+    Currency::Iso(IsoCurrency::USD)
+}
+
+fn eur(amount: &str) -> paft::money::Money {
+    decimal_to_money_with_currency(dec(amount), Currency::Iso(IsoCurrency::EUR))
+}
+
+#[tokio::test]
+async fn spot_conversion_uses_the_pair_quote_and_caches_it() {
+    let server = setup_server();
+    let quote = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v7/finance/quote")
+            .query_param("symbols", "EURUSD=X");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(r#"{"quoteResponse":{"result":[{"symbol":"EURUSD=X","quoteType":"CURRENCY","currency":"USD","regularMarketPrice":1.1}],"error":null}}"#);
+    });
+
+    let fx = FxConverter::new(&client_for(&server)).cache_mode(yfinance_rs::CacheMode::Bypass);
+    let a = fx.convert(&eur("10"), &usd()).await.unwrap();
+    let b = fx.convert(&eur("20"), &usd()).await.unwrap();
+
+    assert_eq!(a.amount(), dec("11"));
+    assert_eq!(a.currency(), &usd());
+    assert_eq!(b.amount(), dec("22"));
+    quote.assert_hits(1);
+
+    // Same currency needs no request.
+    let same = fx.convert(&a, &usd()).await.unwrap();
+    assert_eq!(same.amount(), dec("11"));
+}
+
+#[tokio::test]
+async fn history_is_converted_at_each_bars_date() {
+    let server = setup_server();
+    let _mock = mock_chart_body(&server, "SAP.DE", SAP_CHART);
+    let rates = mock_chart_body(&server, "EURUSD=X", EURUSD_CHART);
+
+    let client = client_for(&server);
+    let history = HistoryBuilder::new(&client, "SAP.DE")
+        .auto_adjust(false)
+        .fetch_full()
+        .await
+        .unwrap();
+
+    let fx = FxConverter::new(&client).cache_mode(yfinance_rs::CacheMode::Bypass);
+    let converted = fx.convert_history(history, &usd()).await.unwrap();
+
+    assert_eq!(converted.candles[0].close.amount(), dec("120"));
+    assert_eq!(converted.candles[1].close.amount(), dec("132"));
+    assert_eq!(converted.candles[1].high.amount(), dec("133.2"));
+    assert_eq!(converted.candles[1].close.currency(), &usd());
+    let dividend = converted
+        .actions
+        .iter()
+        .find_map(|a| match a {
+            Action::Dividend { amount, .. } => Some(amount.clone()),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(dividend.amount(), dec("1.2"));
+    assert_eq!(dividend.currency(), &usd());
+
+    // The span was fetched once; single-date lookups inside it are served from the cache.
+    let d = NaiveDate::from_ymd_opt(2024, 1, 3).unwrap();
+    let rate = fx
+        .rate_on(&Currency::Iso(IsoCurrency::EUR), &usd(), d)
+        .await
+        .unwrap();
+    assert_eq!(rate, dec("1.2"));
+    rates.assert_hits(1);
+}
+
+#[tokio::test]
+async fn closes_reaching_today_are_refetched_after_the_daily_ttl() {
+    let today = chrono::Utc::now().date_naive();
+    let ts = today.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp();
+    let body = format!(
+        r#"{{"chart":{{"result":[{{
+            "meta":{{"currency":"USD","symbol":"EURUSD=X"}},
+            "timestamp":[{ts}],
+            "indicators":{{"quote":[{{"open":[1.1],"high":[1.1],"low":[1.1],"close":[1.1],"volume":[0]}}]}}
+        }}],"error":null}}}}"#
+    );
+    let server = setup_server();
+    let rates = mock_chart_body(&server, "EURUSD=X", body);
+
+    let eur = Currency::Iso(IsoCurrency::EUR);
+    let fx = FxConverter::new(&client_for(&server))
+        .cache_mode(yfinance_rs::CacheMode::Bypass)
+        .daily_ttl(std::time::Duration::ZERO);
+    for _ in 0..2 {
+        let rate = fx.rate_on(&eur, &usd(), today).await.unwrap();
+        assert_eq!(rate, dec("1.1"));
+    }
+    rates.assert_hits(2);
+
+    // The default TTL keeps the same span cached.
+    let fx = FxConverter::new(&client_for(&server)).cache_mode(yfinance_rs::CacheMode::Bypass);
+    fx.rate_on(&eur, &usd(), today).await.unwrap();
+    fx.rate_on(&eur, &usd(), today).await.unwrap();
+    rates.assert_hits(3);
+}
@@ -1,5 +1,9 @@
 mod common;
 
+#[path = "history/adjust_capital_gains.rs"]
+mod adjust_capital_gains;
+#[path = "history/adjust_dividends_without_adjclose.rs"]
+mod adjust_dividends_without_adjclose;
 #[path = "history/adjust_from_splits_only.rs"]
 mod adjust_from_splits_only;
 #[path = "history/adjust.rs"]
@@ -32,3 +36,33 @@ mod retry_synthetic;
 
 #[path = "history/caching_synthetic.rs"]
 mod caching_synthetic;
+
+#[path = "history/update_incremental.rs"]
+mod update_incremental;
+
+#[path = "history/repair.rs"]
+mod history_repair;
+
+#[path = "history/decimal_exact.rs"]
+mod decimal_exact;
+
+#[path = "history/validate.rs"]
+mod history_validate;
+
+#[path = "history/total_return.rs"]
+mod history_total_return;
+
+#[path = "history/dividends.rs"]
+mod history_dividends;
+
+#[path = "history/earnings.rs"]
+mod history_earnings;
+
+#[path = "history/as_of.rs"]
+mod history_as_of;
+
+#[path = "history/diff.rs"]
+mod history_diff;
+
+#[path = "history/transform.rs"]
+mod history_transform;
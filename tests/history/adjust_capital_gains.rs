@@ -0,0 +1,43 @@
+use crate::common::{client_for, mock_history_chart, setup_server};
+use yfinance_rs::HistoryBuilder;
+use yfinance_rs::core::Interval;
+use yfinance_rs::core::conversions::money_to_f64;
+
+async fn closes(adjust_capital_gains: bool) -> Vec<f64> {
+    let server = setup_server();
+    let mock = mock_history_chart(&server, "CGFUND");
+
+    let client = client_for(&server);
+
+    let resp = HistoryBuilder::new(&client, "CGFUND")
+        .interval(Interval::D1)
+        .auto_adjust(true)
+        .adjust_capital_gains(adjust_capital_gains)
+        .fetch_full()
+        .await
+        .unwrap();
+
+    mock.assert();
+    assert!(resp.adjusted);
+    resp.candles
+        .iter()
+        .map(|c| money_to_f64(&c.close))
+        .collect()
+}
+
+#[tokio::test]
+async fn capital_gains_are_folded_into_adjustment_when_enabled() {
+    // The fixture has no adjclose and a 5.00 capital gain going ex at the second bar.
+    let adjusted = closes(true).await;
+    assert_eq!(adjusted.len(), 3);
+    // Bar before the ex-date is scaled by 1 - 5/100 = 0.95; the ex-date bar and later are untouched.
+    assert!((adjusted[0] - 95.0).abs() < 1e-9, "got {}", adjusted[0]);
+    assert!((adjusted[1] - 100.0).abs() < 1e-9);
+    assert!((adjusted[2] - 100.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn capital_gains_are_ignored_by_default() {
+    let adjusted = closes(false).await;
+    assert_eq!(adjusted, vec![100.0, 100.0, 100.0]);
+}
@@ -0,0 +1,57 @@
+use crate::common::{client_for, mock_chart_body, setup_server};
+use yfinance_rs::HistoryBuilder;
+use yfinance_rs::core::Interval;
+use yfinance_rs::core::conversions::*;
+
+#[tokio::test]
+async fn history_auto_adjust_uses_dividends_when_adjclose_missing() {
+    let server = setup_server();
+
+    // No "adjclose" block; a 2.00 dividend goes ex at the second timestamp.
+    let body = r#"{
+      "chart":{
+        "result":[
+          {
+            "timestamp":[1000,2000,3000],
+            "indicators":{
+              "quote":[{
+                "open":[100.0,100.0,100.0],
+                "high":[101.0,101.0,101.0],
+                "low":[ 99.0, 99.0, 99.0],
+                "close":[100.0,100.0,100.0],
+                "volume":[10,10,10]
+              }]
+            },
+            "events": {
+              "dividends": {
+                "2000": { "date": 2000, "amount": 2.0 }
+              }
+            }
+          }
+        ],
+        "error": null
+      }
+    }"#;
+
+    let mock = mock_chart_body(&server, "TEST", body);
+
+    let client = client_for(&server);
+
+    let resp = HistoryBuilder::new(&client, "TEST")
+        .interval(Interval::D1)
+        .auto_adjust(true)
+        .fetch_full()
+        .await
+        .unwrap();
+
+    mock.assert();
+
+    assert!(resp.adjusted);
+    assert_eq!(resp.candles.len(), 3);
+    // Bar before the ex-date is scaled by 1 - 2/100 = 0.98; later bars are untouched.
+    assert!((money_to_f64(&resp.candles[0].close) - 98.0).abs() < 1e-9);
+    assert!((money_to_f64(&resp.candles[1].close) - 100.0).abs() < 1e-9);
+    assert!((money_to_f64(&resp.candles[2].close) - 100.0).abs() < 1e-9);
+    // Dividend adjustment does not touch volume.
+    assert_eq!(resp.candles[0].volume, Some(10));
+}
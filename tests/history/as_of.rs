@@ -0,0 +1,103 @@
+use crate::common::{client_for, dec, mock_chart_body, setup_server};
+use chrono::NaiveDate;
+use httpmock::Method::GET;
+use yfinance_rs::AsOfBuilder;
+use yfinance_rs::history::AsOfMiss;
+
+fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+// Tue 26, Wed 27 and Thu 28 March 2024 (Good Friday closed), then Mon 1 April.
+const BODY: &str = r#"{"chart":{"result":[{
+    "meta":{"currency":"USD","timezone":"America/New_York","gmtoffset":-14400},
+    "timestamp":[1711459800,1711546200,1711632600,1711978200],
+    "indicators":{
+      "quote":[{"open":[10.0,11.0,12.0,13.0],"high":[10.5,11.5,12.5,13.5],"low":[9.5,10.5,11.5,12.5],"close":[10.1,11.1,12.1,13.1],"volume":[100,100,100,100]}],
+      "adjclose":[{"adjclose":[5.05,5.55,6.05,6.55]}]
+    }
+}],"error":null}}"#;
+
+#[tokio::test]
+async fn as_of_groups_requests_and_reports_misses() {
+    let server = setup_server();
+    let aaa = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/chart/AAA")
+            .query_param("interval", "1d")
+            .query_param("period1", "1708300800")
+            .query_param("period2", "1711929600");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(BODY);
+    });
+    let unknown = server.mock(|when, then| {
+        when.method(GET).path("/v8/finance/chart/NOPE");
+        then.status(404);
+    });
+
+    let client = client_for(&server);
+
+    let lookups = AsOfBuilder::new(&client)
+        .add("AAA", day(2024, 3, 30))
+        .add("NOPE", day(2024, 3, 28))
+        .add("AAA", day(2024, 3, 27))
+        .add("AAA", day(2024, 3, 1))
+        .concurrency(1)
+        .fetch()
+        .await
+        .unwrap();
+
+    aaa.assert_calls(1);
+    unknown.assert_calls(1);
+
+    let got: Vec<(&str, NaiveDate)> = lookups
+        .iter()
+        .map(|l| (l.symbol.as_str(), l.date))
+        .collect();
+    assert_eq!(
+        got,
+        vec![
+            ("AAA", day(2024, 3, 30)),
+            ("NOPE", day(2024, 3, 28)),
+            ("AAA", day(2024, 3, 27)),
+            ("AAA", day(2024, 3, 1)),
+        ],
+        "results keep the order of the lookups"
+    );
+
+    let weekend = lookups[0].result.as_ref().unwrap();
+    assert_eq!(
+        weekend.date,
+        day(2024, 3, 28),
+        "Saturday falls back to Thursday"
+    );
+    assert_eq!(weekend.ts.timestamp(), 1_711_632_600);
+    assert_eq!(weekend.close.amount(), dec("12.1"));
+
+    assert_eq!(lookups[1].result, Err(AsOfMiss::NoData));
+
+    let exact = lookups[2].result.as_ref().unwrap();
+    assert_eq!(exact.date, day(2024, 3, 27));
+    assert_eq!(exact.close.amount(), dec("11.1"));
+
+    assert_eq!(lookups[3].result, Err(AsOfMiss::NoBarInWindow));
+}
+
+#[tokio::test]
+async fn as_of_adjusted_uses_adjclose() {
+    let server = setup_server();
+    let _mock = mock_chart_body(&server, "AAA", BODY);
+    let client = client_for(&server);
+
+    let lookups = AsOfBuilder::new(&client)
+        .adjusted(true)
+        .lookups([("AAA", day(2024, 4, 1))])
+        .fetch()
+        .await
+        .unwrap();
+
+    let bar = lookups[0].result.as_ref().unwrap();
+    assert_eq!(bar.date, day(2024, 4, 1));
+    assert_eq!(bar.close.amount(), dec("6.55"));
+}
@@ -0,0 +1,116 @@
+use crate::common::{
+    client_for, dec, mock_chart_body, mock_history_chart, mock_quote_v7, setup_server,
+};
+use yfinance_rs::core::{Action, Interval};
+use yfinance_rs::{HistoryBuilder, QuotesBuilder};
+
+#[tokio::test]
+async fn history_prices_parse_without_float_artifacts() {
+    let server = setup_server();
+
+    let body = r#"{
+      "chart":{
+        "result":[
+          {
+            "meta":{"currency":"USD"},
+            "timestamp":[1000,2000],
+            "indicators":{
+              "quote":[{
+                "open":[187.15,0.3],
+                "high":[187.45,0.3],
+                "low":[186.9,0.1],
+                "close":[187.2,0.3],
+                "volume":[10,10]
+              }],
+              "adjclose":[{"adjclose":[183.4567891234,0.3]}]
+            },
+            "events": {
+              "dividends": { "2000": { "date": 2000, "amount": 0.24 } }
+            }
+          }
+        ],
+        "error": null
+      }
+    }"#;
+
+    let mock = mock_chart_body(&server, "EXACT", body);
+
+    let client = client_for(&server);
+
+    let raw = HistoryBuilder::new(&client, "EXACT")
+        .interval(Interval::D1)
+        .auto_adjust(false)
+        .fetch_full()
+        .await
+        .unwrap();
+
+    assert_eq!(raw.candles[0].open.amount(), dec("187.15"));
+    assert_eq!(raw.candles[0].close.amount(), dec("187.2"));
+    assert_eq!(raw.candles[1].low.amount(), dec("0.1"));
+    assert_eq!(raw.candles[1].close.amount(), dec("0.3"));
+    match &raw.actions[0] {
+        Action::Dividend { amount, .. } => assert_eq!(amount.amount(), dec("0.24")),
+        other => panic!("expected dividend, got {other:?}"),
+    }
+
+    let adjusted = HistoryBuilder::new(&client, "EXACT")
+        .interval(Interval::D1)
+        .auto_adjust(true)
+        .fetch_full()
+        .await
+        .unwrap();
+
+    mock.assert_calls(2);
+
+    // The adjusted close is Yahoo's adjclose verbatim; the raw close is kept alongside it.
+    assert_eq!(adjusted.candles[0].close.amount(), dec("183.4567891234"));
+    assert_eq!(
+        adjusted.candles[0].close_unadj.as_ref().unwrap().amount(),
+        dec("187.2")
+    );
+    assert_eq!(adjusted.candles[1].close.amount(), dec("0.3"));
+}
+
+#[tokio::test]
+async fn history_out_of_range_numbers_are_treated_as_missing() {
+    let server = setup_server();
+    let mock = mock_history_chart(&server, "OUTLIER");
+
+    let client = client_for(&server);
+
+    let history = HistoryBuilder::new(&client, "OUTLIER")
+        .interval(Interval::D1)
+        .auto_adjust(false)
+        .fetch_full()
+        .await
+        .unwrap();
+
+    mock.assert();
+
+    // The bar with the unrepresentable high is incomplete and dropped; the rest parse.
+    let ts: Vec<i64> = history.candles.iter().map(|c| c.ts.timestamp()).collect();
+    assert_eq!(ts, vec![1000, 3000]);
+    assert_eq!(history.candles[1].close.amount(), dec("12"));
+    assert!(history.actions.is_empty());
+}
+
+#[tokio::test]
+async fn quote_out_of_range_numbers_are_treated_as_missing() {
+    let server = setup_server();
+    let mock = mock_quote_v7(&server, "OUTLIER");
+
+    let client = client_for(&server);
+
+    let quotes = QuotesBuilder::new(client)
+        .symbols(["OUTLIER"])
+        .fetch_detailed()
+        .await
+        .unwrap();
+
+    mock.assert();
+
+    let q = &quotes[0];
+    assert_eq!(q.quote.price.as_ref().unwrap().amount(), dec("12"));
+    assert!(q.market_cap.is_none());
+    assert!(q.trailing_pe.is_none());
+}
@@ -0,0 +1,101 @@
+use crate::common::{client_for, dec, mock_chart_body, setup_server};
+use yfinance_rs::history::{ActionChange, FieldDelta, PriceField, diff_history};
+use yfinance_rs::{HistoryBuilder, HistoryResponse};
+
+const OLD: &str = r#"{"chart":{"result":[{
+    "meta":{"currency":"USD"},
+    "timestamp":[1000,2000,3000],
+    "indicators":{"quote":[{"open":[10.0,11.0,12.0],"high":[10.5,11.5,12.5],"low":[9.5,10.5,11.5],"close":[10.1,11.1,12.1],"volume":[100,200,300]}]},
+    "events":{
+      "dividends":{"2000":{"date":2000,"amount":0.5}},
+      "splits":{"3000":{"date":3000,"numerator":2,"denominator":1,"splitRatio":"2:1"}}
+    }
+}],"error":null}}"#;
+
+// Bar 2000 has a corrected close and volume, bar 3000 is gone, bar 4000 is new, the dividend
+// was revised, the split dropped and a capital gain added.
+const NEW: &str = r#"{"chart":{"result":[{
+    "meta":{"currency":"USD"},
+    "timestamp":[1000,2000,4000],
+    "indicators":{"quote":[{"open":[10.0,11.0,13.0],"high":[10.5,11.5,13.5],"low":[9.5,10.5,12.5],"close":[10.1,11.2,13.1],"volume":[100,250,400]}]},
+    "events":{
+      "dividends":{"2000":{"date":2000,"amount":0.55}},
+      "capitalGains":{"4000":{"date":4000,"amount":0.1}}
+    }
+}],"error":null}}"#;
+
+async fn fetch(body: &str) -> HistoryResponse {
+    let server = setup_server();
+    let _mock = mock_chart_body(&server, "REV", body);
+    let client = client_for(&server);
+    HistoryBuilder::new(&client, "REV")
+        .auto_adjust(false)
+        .fetch_full()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn diff_reports_bar_and_action_revisions() {
+    let old = fetch(OLD).await;
+    let new = fetch(NEW).await;
+
+    let diff = diff_history(&old, &new);
+    assert!(diff.has_revisions());
+
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].ts.timestamp(), 4000);
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0].ts.timestamp(), 3000);
+
+    assert_eq!(diff.changed.len(), 1);
+    let change = &diff.changed[0];
+    assert_eq!(change.ts.timestamp(), 2000);
+    assert_eq!(change.deltas.len(), 3, "{:?}", change.deltas);
+    match &change.deltas[0] {
+        FieldDelta::Price { field, old, new } => {
+            assert_eq!(*field, PriceField::Close);
+            assert_eq!(old.amount(), dec("11.1"));
+            assert_eq!(new.amount(), dec("11.2"));
+        }
+        other => panic!("expected close delta, got {other:?}"),
+    }
+    assert!(matches!(
+        change.deltas[1],
+        FieldDelta::UnadjustedClose { .. }
+    ));
+    assert_eq!(
+        change.deltas[2],
+        FieldDelta::Volume {
+            old: Some(200),
+            new: Some(250)
+        }
+    );
+
+    assert_eq!(diff.actions.len(), 3, "{:?}", diff.actions);
+    assert!(matches!(diff.actions[0], ActionChange::Changed { .. }));
+    assert!(matches!(diff.actions[1], ActionChange::Removed(_)));
+    assert!(matches!(diff.actions[2], ActionChange::Added(_)));
+
+    let text = diff.to_string();
+    assert!(text.starts_with("1 bars added, 1 removed, 1 changed; 3 action changes"));
+}
+
+#[tokio::test]
+async fn diff_of_identical_fetches_is_empty() {
+    let a = fetch(OLD).await;
+    let b = fetch(OLD).await;
+
+    let diff = diff_history(&a, &b);
+    assert!(diff.is_empty());
+    assert!(!diff.has_revisions());
+    assert_eq!(diff.to_string(), "no differences");
+
+    // Appending bars is not a revision.
+    let mut shorter = a.clone();
+    shorter.candles.pop();
+    shorter.actions.clear();
+    let grown = diff_history(&shorter, &a);
+    assert!(!grown.is_empty());
+    assert!(!grown.has_revisions());
+}
@@ -0,0 +1,115 @@
+use crate::common::{client_for, dec, mock_chart_body, setup_server};
+use rust_decimal::Decimal;
+use yfinance_rs::HistoryBuilder;
+use yfinance_rs::history::{DividendAnalytics, DividendFrequency};
+
+const T0: i64 = 1_600_000_000;
+const QUARTER: i64 = 92 * 86_400;
+
+// Twelve quarterly dividends growing 10% a year, a 2.00 special a month after the sixth,
+// and a 2:1 split between the tenth and eleventh. Closes sit at 50 on every ex-date.
+async fn analytics() -> DividendAnalytics {
+    let mut ts = vec![T0];
+    let mut divs = Vec::new();
+    for k in 1..=12 {
+        let t = T0 + k * QUARTER;
+        ts.push(t);
+        let amount = match k {
+            1..=4 => "0.5",
+            5..=8 => "0.55",
+            _ => "0.605",
+        };
+        divs.push(format!(r#""{t}":{{"date":{t},"amount":{amount}}}"#));
+    }
+    let special = T0 + 6 * QUARTER + 30 * 86_400;
+    divs.push(format!(r#""{special}":{{"date":{special},"amount":2.0}}"#));
+    let split = T0 + 10 * QUARTER + QUARTER / 2;
+
+    let n = ts.len();
+    let prices = vec!["50.0"; n].join(",");
+    let volumes = vec!["1"; n].join(",");
+    let ts_json = ts
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    let body = format!(
+        r#"{{"chart":{{"result":[{{
+            "meta":{{"currency":"USD"}},
+            "timestamp":[{ts_json}],
+            "indicators":{{"quote":[{{"open":[{prices}],"high":[{prices}],"low":[{prices}],"close":[{prices}],"volume":[{volumes}]}}]}},
+            "events":{{
+              "dividends":{{{}}},
+              "splits":{{"{split}":{{"date":{split},"numerator":2,"denominator":1,"splitRatio":"2:1"}}}}
+            }}
+        }}],"error":null}}}}"#,
+        divs.join(",")
+    );
+
+    let server = setup_server();
+    let _mock = mock_chart_body(&server, "DIV", body);
+    let client = client_for(&server);
+    let history = HistoryBuilder::new(&client, "DIV")
+        .auto_adjust(false)
+        .fetch_full()
+        .await
+        .unwrap();
+    DividendAnalytics::from_history(&history)
+}
+
+fn at(t: i64) -> chrono::DateTime<chrono::Utc> {
+    chrono::DateTime::from_timestamp(t, 0).unwrap()
+}
+
+#[tokio::test]
+async fn dividend_analytics_detects_frequency_and_specials() {
+    let a = analytics().await;
+
+    assert_eq!(a.frequency(), DividendFrequency::Quarterly);
+    assert_eq!(a.frequency().payments_per_year(), Some(4));
+
+    let records = a.records();
+    assert_eq!(records.len(), 13);
+    let specials: Vec<Decimal> = records
+        .iter()
+        .filter(|r| r.special)
+        .map(|r| r.amount.amount())
+        .collect();
+    assert_eq!(specials, vec![dec("2.0")]);
+}
+
+#[tokio::test]
+async fn dividend_analytics_ttm_yield_and_cagr() {
+    let a = analytics().await;
+    let eighth = at(T0 + 8 * QUARTER);
+
+    // Quarters five to eight plus the special.
+    assert_eq!(a.ttm_dividends(eighth).unwrap().amount(), dec("4.2"));
+    assert_eq!(a.ttm_yield(eighth).unwrap(), dec("0.084"));
+
+    let regular_only = a.clone().include_special(false);
+    assert_eq!(
+        regular_only.ttm_dividends(eighth).unwrap().amount(),
+        dec("2.2")
+    );
+    assert_eq!(regular_only.ttm_yield(eighth).unwrap(), dec("0.044"));
+
+    let series = a.ttm_yield_series();
+    assert_eq!(series.len(), 13);
+    assert_eq!(series[8], (eighth, dec("0.084")));
+
+    let cagr = a.cagr(2).unwrap();
+    assert!((cagr - 0.10).abs() < 1e-9, "cagr = {cagr}");
+    assert!(a.cagr(5).is_none(), "not enough history");
+}
+
+#[tokio::test]
+async fn dividend_analytics_split_adjusts_earlier_amounts() {
+    let a = analytics().await.split_adjust(true);
+    let records = a.records();
+
+    assert_eq!(records[0].amount.amount(), dec("0.5"));
+    assert_eq!(records[0].adjusted_amount.amount(), dec("0.25"));
+    // The last dividends are after the split and unchanged.
+    assert_eq!(records[12].adjusted_amount.amount(), dec("0.605"));
+}
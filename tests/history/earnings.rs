@@ -0,0 +1,80 @@
+use crate::common::{client_for, dec, setup_server};
+use httpmock::Method::GET;
+use yfinance_rs::HistoryBuilder;
+
+const BODY: &str = r#"{"chart":{"result":[{
+    "meta":{"currency":"USD"},
+    "timestamp":[1700000000,1700086400,1700172800],
+    "indicators":{"quote":[{"open":[10.0,11.0,12.0],"high":[10.5,11.5,12.5],"low":[9.5,10.5,11.5],"close":[10.0,11.0,12.0],"volume":[100,100,100]}]},
+    "events":{
+      "dividends":{"1700086400":{"date":1700086400,"amount":0.24}},
+      "earnings":{
+        "1700172800":{"date":1700172800,"epsActual":1.46,"epsEstimate":1.39},
+        "1700000000":{"date":1700000000,"epsEstimate":1.2}
+      }
+    }
+}],"error":null}}"#;
+
+#[tokio::test]
+async fn history_returns_earnings_with_eps() {
+    let server = setup_server();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/chart/AAPL")
+            .query_param("events", "div|split|capitalGains|earn");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(BODY);
+    });
+
+    let (history, earnings) = HistoryBuilder::new(&client_for(&server), "AAPL")
+        .fetch_full_with_earnings()
+        .await
+        .unwrap();
+    mock.assert();
+
+    assert_eq!(history.candles.len(), 3);
+    assert_eq!(
+        history.actions.len(),
+        1,
+        "earnings are not corporate actions"
+    );
+
+    assert_eq!(earnings.len(), 2);
+    assert_eq!(earnings[0].ts.timestamp(), 1_700_000_000);
+    assert!(earnings[0].eps_actual.is_none());
+    assert_eq!(
+        earnings[0].eps_estimate.as_ref().unwrap().amount(),
+        dec("1.2")
+    );
+    assert_eq!(earnings[1].ts.timestamp(), 1_700_172_800);
+    assert_eq!(
+        earnings[1].eps_actual.as_ref().unwrap().amount(),
+        dec("1.46")
+    );
+    assert_eq!(
+        earnings[1].eps_estimate.as_ref().unwrap().amount(),
+        dec("1.39")
+    );
+}
+
+#[tokio::test]
+async fn earnings_requested_without_actions() {
+    let server = setup_server();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/chart/AAPL")
+            .query_param("events", "earn");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(BODY);
+    });
+
+    let (_history, earnings) = HistoryBuilder::new(&client_for(&server), "AAPL")
+        .actions(false)
+        .fetch_full_with_earnings()
+        .await
+        .unwrap();
+    mock.assert();
+    assert_eq!(earnings.len(), 2);
+}
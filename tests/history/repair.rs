@@ -0,0 +1,209 @@
+use crate::common::{client_for, mock_chart_body, setup_server};
+use yfinance_rs::HistoryBuilder;
+use yfinance_rs::core::Interval;
+use yfinance_rs::core::conversions::*;
+use yfinance_rs::history::{PriceField, RepairConfig, RepairKind};
+
+#[tokio::test]
+async fn history_repair_fixes_defects_and_logs_them() {
+    let server = setup_server();
+
+    // - 1000/2000 are missing the 2:1 split adjustment at 3000
+    // - 4000 has zero prices
+    // - 5000 is duplicated (the second bar wins)
+    // - 6000 is a zero-volume print far from the previous close
+    let body = r#"{
+      "chart":{
+        "result":[
+          {
+            "timestamp":[1000,2000,3000,4000,5000,5000,6000,7000],
+            "indicators":{
+              "quote":[{
+                "open": [200.0,200.0,100.0,0.0,101.0,102.0,150.0,101.0],
+                "high": [200.0,200.0,100.0,0.0,101.0,102.0,150.0,101.0],
+                "low":  [200.0,200.0,100.0,0.0,101.0,102.0,150.0,101.0],
+                "close":[200.0,200.0,100.0,0.0,101.0,102.0,150.0,101.0],
+                "volume":[10,10,20,20,20,20,0,20]
+              }]
+            },
+            "events": {
+              "splits": {
+                "3000": { "date": 3000, "numerator": 2, "denominator": 1 }
+              }
+            }
+          }
+        ],
+        "error": null
+      }
+    }"#;
+
+    let mock = mock_chart_body(&server, "TEST", body);
+
+    let client = client_for(&server);
+
+    let (resp, log) = HistoryBuilder::new(&client, "TEST")
+        .interval(Interval::D1)
+        .auto_adjust(false)
+        .repair(true)
+        .repair_config(RepairConfig {
+            remove_stray_prints: true,
+            ..RepairConfig::default()
+        })
+        .fetch_full_with_repairs()
+        .await
+        .unwrap();
+
+    mock.assert();
+
+    let ts: Vec<i64> = resp.candles.iter().map(|c| c.ts.timestamp()).collect();
+    assert_eq!(ts, vec![1000, 2000, 3000, 4000, 5000, 7000]);
+
+    let closes: Vec<f64> = resp
+        .candles
+        .iter()
+        .map(|c| money_to_f64(&c.close))
+        .collect();
+    assert_eq!(closes, vec![100.0, 100.0, 100.0, 100.0, 102.0, 101.0]);
+    assert!((money_to_f64(&resp.candles[3].open) - 100.0).abs() < 1e-9);
+    assert_eq!(resp.candles[0].volume, Some(20));
+
+    let count = |pred: fn(&RepairKind) -> bool| log.iter().filter(|e| pred(&e.kind)).count();
+    assert_eq!(count(|k| matches!(k, RepairKind::DuplicateRemoved)), 1);
+    assert_eq!(
+        count(|k| matches!(k, RepairKind::ZeroPriceFilled { .. })),
+        4
+    );
+    assert_eq!(count(|k| matches!(k, RepairKind::SplitAdjusted { .. })), 2);
+    assert_eq!(count(|k| matches!(k, RepairKind::StrayPrintRemoved)), 1);
+    assert_eq!(log.len(), 8);
+    assert!(log.iter().any(|e| e.ts.timestamp() == 4000
+        && e.kind
+            == RepairKind::ZeroPriceFilled {
+                field: PriceField::Close,
+                value: 100.0
+            }));
+}
+
+#[tokio::test]
+async fn history_without_repair_returns_empty_log() {
+    let server = setup_server();
+
+    let body = r#"{
+      "chart":{
+        "result":[
+          {
+            "timestamp":[1000,2000,3000],
+            "indicators":{
+              "quote":[{
+                "open": [10.0,1000.0,10.0],
+                "high": [10.0,1000.0,10.0],
+                "low":  [10.0,1000.0,10.0],
+                "close":[10.0,1000.0,10.0],
+                "volume":[10,10,10]
+              }]
+            }
+          }
+        ],
+        "error": null
+      }
+    }"#;
+
+    let _mock = mock_chart_body(&server, "TEST", body);
+
+    let client = client_for(&server);
+
+    let (resp, log) = HistoryBuilder::new(&client, "TEST")
+        .auto_adjust(false)
+        .fetch_full_with_repairs()
+        .await
+        .unwrap();
+
+    assert!(log.is_empty());
+    assert!((money_to_f64(&resp.candles[1].close) - 1000.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn history_repair_flags_stray_prints_without_removing_quiet_bars() {
+    let server = setup_server();
+
+    // Zero-volume bars: 3000 drifts a little, 4000 sits inside the recent range, and
+    // 5000 jumps far outside it.
+    let body = r#"{
+      "chart":{
+        "result":[
+          {
+            "timestamp":[1000,2000,3000,4000,5000,6000],
+            "indicators":{
+              "quote":[{
+                "open": [100.0,101.0,103.0,100.5,150.0,101.0],
+                "high": [100.0,101.0,103.0,100.5,150.0,101.0],
+                "low":  [100.0,101.0,103.0,100.5,150.0,101.0],
+                "close":[100.0,101.0,103.0,100.5,150.0,101.0],
+                "volume":[10,10,0,0,0,10]
+              }]
+            }
+          }
+        ],
+        "error": null
+      }
+    }"#;
+
+    let _mock = mock_chart_body(&server, "TEST", body);
+
+    let client = client_for(&server);
+
+    let (resp, log) = HistoryBuilder::new(&client, "TEST")
+        .auto_adjust(false)
+        .repair(true)
+        .fetch_full_with_repairs()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.candles.len(), 6, "flagged bars are kept by default");
+    assert_eq!(log.len(), 1);
+    let entry = log.iter().next().unwrap();
+    assert_eq!(entry.ts.timestamp(), 5000);
+    assert_eq!(entry.kind, RepairKind::StrayPrintFlagged);
+}
+
+#[tokio::test]
+async fn history_repair_leaves_keepna_rows_untouched() {
+    let server = setup_server();
+
+    let body = r#"{
+      "chart":{
+        "result":[
+          {
+            "timestamp":[1000,2000,3000],
+            "indicators":{
+              "quote":[{
+                "open": [100.0,null,101.0],
+                "high": [100.0,null,101.0],
+                "low":  [100.0,null,101.0],
+                "close":[100.0,null,101.0],
+                "volume":[10,null,10]
+              }]
+            }
+          }
+        ],
+        "error": null
+      }
+    }"#;
+
+    let _mock = mock_chart_body(&server, "TEST", body);
+
+    let client = client_for(&server);
+
+    let (resp, log) = HistoryBuilder::new(&client, "TEST")
+        .auto_adjust(false)
+        .keepna(true)
+        .repair(true)
+        .fetch_full_with_repairs()
+        .await
+        .unwrap();
+
+    assert!(log.is_empty(), "missing rows are not zero-filled: {log:?}");
+    let ts: Vec<i64> = resp.candles.iter().map(|c| c.ts.timestamp()).collect();
+    assert_eq!(ts, vec![1000, 2000, 3000]);
+    assert!(money_to_f64(&resp.candles[1].close).abs() < 1e-9);
+}
@@ -0,0 +1,119 @@
+use crate::common::{client_for, dec, mock_chart_body, setup_server};
+use chrono::{DateTime, Duration};
+use rust_decimal::Decimal;
+use yfinance_rs::HistoryBuilder;
+use yfinance_rs::core::HistoryResponse;
+use yfinance_rs::history::{ReinvestAt, TotalReturnBuilder};
+
+fn round(d: Decimal) -> Decimal {
+    d.round_dp(10)
+}
+
+// A 2.00 dividend goes ex on the third bar (close 98).
+async fn fetch(auto_adjust: bool) -> HistoryResponse {
+    let server = setup_server();
+    let body = r#"{
+      "chart":{
+        "result":[
+          {
+            "meta":{"currency":"USD"},
+            "timestamp":[1000,2000,3000,4000,5000],
+            "indicators":{
+              "quote":[{
+                "open":[100.0,100.0,98.0,99.0,100.0],
+                "high":[100.0,100.0,98.0,99.0,100.0],
+                "low":[100.0,100.0,98.0,99.0,100.0],
+                "close":[100.0,100.0,98.0,99.0,100.0],
+                "volume":[10,10,10,10,10]
+              }]
+            },
+            "events": {
+              "dividends": { "3000": { "date": 3000, "amount": 2.0 } }
+            }
+          }
+        ],
+        "error": null
+      }
+    }"#;
+    let _mock = mock_chart_body(&server, "TR", body);
+    let client = client_for(&server);
+    HistoryBuilder::new(&client, "TR")
+        .auto_adjust(auto_adjust)
+        .fetch_full()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn total_return_reinvests_on_ex_date() {
+    let history = fetch(false).await;
+    let tr = TotalReturnBuilder::new(&history).build().unwrap();
+
+    let idx: Vec<Decimal> = tr.points.iter().map(|p| round(p.index)).collect();
+    assert_eq!(idx[0], dec("100"));
+    assert_eq!(idx[1], dec("100"));
+    // Price drops by the dividend, which is reinvested at 98: no change in value.
+    assert_eq!(idx[2], dec("100"));
+    assert_eq!(
+        idx[4],
+        round(dec("100") * (Decimal::ONE + dec("2") / dec("98")))
+    );
+
+    assert_eq!(tr.reinvestments.len(), 1);
+    let r = &tr.reinvestments[0];
+    assert_eq!(r.ex_date.timestamp(), 3000);
+    assert_eq!(r.reinvested_at.timestamp(), 3000);
+    assert_eq!(r.gross, dec("2"));
+
+    // Adjusted histories fall back to close_unadj and give the same index.
+    let adjusted = fetch(true).await;
+    let tr2 = TotalReturnBuilder::new(&adjusted).build().unwrap();
+    assert_eq!(tr2.points, tr.points);
+}
+
+#[tokio::test]
+async fn total_return_applies_withholding_tax() {
+    let history = fetch(false).await;
+    let tr = TotalReturnBuilder::new(&history)
+        .withholding_tax(dec("0.5"))
+        .build()
+        .unwrap();
+
+    assert_eq!(tr.reinvestments[0].net, dec("1.0"));
+    assert_eq!(round(tr.points[2].index), dec("99"));
+
+    assert!(
+        TotalReturnBuilder::new(&history)
+            .withholding_tax(dec("1.5"))
+            .build()
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn total_return_holds_cash_until_pay_date() {
+    let history = fetch(false).await;
+    let ex = DateTime::from_timestamp(3000, 0).unwrap();
+    let tr = TotalReturnBuilder::new(&history)
+        .reinvest_at(ReinvestAt::PayDate)
+        .pay_date(ex, DateTime::from_timestamp(4000, 0).unwrap())
+        .build()
+        .unwrap();
+
+    let p = &tr.points;
+    assert_eq!(p[2].cash, dec("2"));
+    assert_eq!(round(p[2].index), dec("100"));
+    assert_eq!(p[3].cash, Decimal::ZERO);
+    assert_eq!(round(p[3].index), dec("101"));
+    assert_eq!(tr.reinvestments[0].reinvested_at.timestamp(), 4000);
+
+    // With only a lag, a pay date beyond the history leaves the dividend as cash.
+    let tr = TotalReturnBuilder::new(&history)
+        .reinvest_at(ReinvestAt::PayDate)
+        .pay_lag(Duration::seconds(10_000))
+        .build()
+        .unwrap();
+    assert!(tr.reinvestments.is_empty());
+    assert_eq!(tr.points[4].cash, dec("2"));
+    assert_eq!(round(tr.points[4].index), dec("102"));
+}
@@ -0,0 +1,80 @@
+use crate::common::{client_for, dec, mock_chart_body, setup_server};
+use httpmock::MockServer;
+use rust_decimal::RoundingStrategy;
+use yfinance_rs::history::Rounding;
+use yfinance_rs::{DownloadBuilder, HistoryBuilder, HistoryResponse, YfClient};
+
+// The first bar carries a 50% adjustment (adjclose 50.125 vs close 100.25).
+const BODY: &str = r#"{"chart":{"result":[{
+    "meta":{"currency":"USD"},
+    "timestamp":[1000,2000],
+    "indicators":{
+      "quote":[{"open":[100.25,101.0],"high":[105.25,106.0],"low":[95.25,96.0],"close":[100.25,101.125],"volume":[1000,2000]}],
+      "adjclose":[{"adjclose":[50.125,101.125]}]
+    }
+}],"error":null}}"#;
+
+fn mocked_client(server: &MockServer) -> YfClient {
+    let _mock = mock_chart_body(server, "XFM", BODY);
+    client_for(server)
+}
+
+#[tokio::test]
+async fn history_back_adjust_and_rounding() {
+    let server = setup_server();
+    let client = mocked_client(&server);
+
+    let resp = HistoryBuilder::new(&client, "XFM")
+        .auto_adjust(false)
+        .back_adjust(true)
+        .rounding(true)
+        .rounding_places(1)
+        .rounding_strategy(RoundingStrategy::MidpointNearestEven)
+        .fetch_full()
+        .await
+        .unwrap();
+
+    // back_adjust forces adjustment even with auto_adjust off
+    assert!(resp.adjusted);
+    let c = &resp.candles;
+    // open 100.25 * 0.5 = 50.125 -> 50.1 (half to even)
+    assert_eq!(c[0].open.amount(), dec("50.1"));
+    // close is the raw close, rounded: 100.25 -> 100.2
+    assert_eq!(c[0].close.amount(), dec("100.2"));
+    assert_eq!(c[1].close.amount(), dec("101.1"));
+}
+
+#[tokio::test]
+async fn custom_transforms_run_in_order_after_builtins() {
+    let server = setup_server();
+    let client = mocked_client(&server);
+
+    let resp = HistoryBuilder::new(&client, "XFM")
+        .auto_adjust(false)
+        .transform(|h: &mut HistoryResponse| h.candles.retain(|c| c.ts.timestamp() >= 2000))
+        .transform(Rounding::new(0, RoundingStrategy::ToZero))
+        .fetch_full()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.candles.len(), 1);
+    assert_eq!(resp.candles[0].close.amount(), dec("101"));
+}
+
+#[tokio::test]
+async fn download_shares_the_transform_pipeline() {
+    let server = setup_server();
+    let client = mocked_client(&server);
+
+    let res = DownloadBuilder::new(&client)
+        .symbols(["XFM"])
+        .auto_adjust(false)
+        .transform(|h: &mut HistoryResponse| h.candles.truncate(1))
+        .run()
+        .await
+        .unwrap();
+
+    let candles = &res.entries[0].history.candles;
+    assert_eq!(candles.len(), 1);
+    assert_eq!(candles[0].close.amount(), dec("100.25"));
+}
@@ -0,0 +1,155 @@
+use crate::common::{chart_body, client_for, setup_server};
+use httpmock::Method::GET;
+use yfinance_rs::core::conversions::*;
+use yfinance_rs::{HistoryBuilder, HistoryUpdate, Restatement};
+
+#[tokio::test]
+async fn update_appends_bars_after_overlap() {
+    let server = setup_server();
+
+    let initial = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/chart/TEST")
+            .query_param("range", "6mo");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(chart_body(&[1000, 2000, 3000], &[10.0, 11.0, 12.0], ""));
+    });
+    // Overlap of one bar before the last stored one; the last stored bar is replaced.
+    let incremental = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/chart/TEST")
+            .query_param("period1", "2000");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(chart_body(&[2000, 3000, 4000], &[11.0, 12.5, 13.0], ""));
+    });
+
+    let client = client_for(&server);
+    let existing = HistoryBuilder::new(&client, "TEST")
+        .fetch_full()
+        .await
+        .unwrap();
+    initial.assert();
+
+    let out = HistoryBuilder::new(&client, "TEST")
+        .update_overlap(1)
+        .update(&existing)
+        .await
+        .unwrap();
+    incremental.assert();
+
+    let HistoryUpdate::Appended { history, new_bars } = out else {
+        panic!("expected Appended, got {out:?}");
+    };
+    assert_eq!(new_bars, 1);
+    let ts: Vec<i64> = history.candles.iter().map(|c| c.ts.timestamp()).collect();
+    assert_eq!(ts, vec![1000, 2000, 3000, 4000]);
+    let closes: Vec<f64> = history
+        .candles
+        .iter()
+        .map(|c| money_to_f64(&c.close))
+        .collect();
+    assert_eq!(closes, vec![10.0, 11.0, 12.5, 13.0]);
+}
+
+#[tokio::test]
+async fn update_signals_restatement_on_new_split() {
+    let server = setup_server();
+
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/chart/TEST")
+            .query_param("range", "6mo");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(chart_body(&[1000, 2000, 3000], &[10.0, 11.0, 12.0], ""));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/chart/TEST")
+            .query_param("period1", "2000");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(chart_body(
+                &[2000, 3000, 4000],
+                &[5.5, 6.0, 6.5],
+                r#""splits": { "4000": { "date": 4000, "numerator": 2, "denominator": 1 } }"#,
+            ));
+    });
+
+    let client = client_for(&server);
+    let existing = HistoryBuilder::new(&client, "TEST")
+        .fetch_full()
+        .await
+        .unwrap();
+
+    let out = HistoryBuilder::new(&client, "TEST")
+        .update_overlap(1)
+        .restate_on_update(false)
+        .update(&existing)
+        .await
+        .unwrap();
+
+    match out {
+        HistoryUpdate::RestatementRequired(Restatement::NewActions(actions)) => {
+            assert_eq!(actions.len(), 1);
+        }
+        other => panic!("expected RestatementRequired(NewActions), got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn update_refetches_full_span_on_overlap_mismatch() {
+    let server = setup_server();
+
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/chart/TEST")
+            .query_param("range", "6mo");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(chart_body(&[1000, 2000, 3000], &[10.0, 11.0, 12.0], ""));
+    });
+    // The overlapping bar at 2000 comes back re-adjusted (e.g. a dividend was restated).
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/chart/TEST")
+            .query_param("period1", "2000");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(chart_body(&[2000, 3000, 4000], &[10.8, 12.0, 13.0], ""));
+    });
+    let full = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/chart/TEST")
+            .query_param("period1", "1000");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(chart_body(
+                &[1000, 2000, 3000, 4000],
+                &[9.8, 10.8, 12.0, 13.0],
+                "",
+            ));
+    });
+
+    let client = client_for(&server);
+    let existing = HistoryBuilder::new(&client, "TEST")
+        .fetch_full()
+        .await
+        .unwrap();
+
+    let out = HistoryBuilder::new(&client, "TEST")
+        .update_overlap(1)
+        .update(&existing)
+        .await
+        .unwrap();
+    full.assert();
+
+    let HistoryUpdate::Restated { history, reason } = out else {
+        panic!("expected Restated, got {out:?}");
+    };
+    assert!(matches!(reason, Restatement::OverlapMismatch { .. }));
+    assert_eq!(history.candles.len(), 4);
+    assert!((money_to_f64(&history.candles[0].close) - 9.8).abs() < 1e-9);
+}
@@ -0,0 +1,114 @@
+use crate::common::{client_for, mock_chart_body, setup_server};
+use httpmock::MockServer;
+use yfinance_rs::history::{PriceField, ValidationMode, ValidationRule};
+use yfinance_rs::{HistoryBuilder, YfClient, YfError};
+
+// Bars: 0 ok, 1 high < low, 2 close above high, 3 duplicate of 2, 4 goes back in time,
+// 5 all-null (kept by keepna), 6 negative open.
+const BODY: &str = r#"{
+  "chart":{
+    "result":[
+      {
+        "timestamp":[1000,2000,3000,3000,2500,4000,5000],
+        "indicators":{
+          "quote":[{
+            "open":[10.0, 10.0, 10.0, 10.0, 10.0, null, -1.0],
+            "high":[11.0,  9.0, 11.0, 11.0, 11.0, null, 11.0],
+            "low": [ 9.0, 10.5,  9.0,  9.0,  9.0, null,  9.0],
+            "close":[10.5, 10.0, 12.0, 10.0, 10.0, null, 10.0],
+            "volume":[10,10,10,10,10,10,10]
+          }]
+        }
+      }
+    ],
+    "error": null
+  }
+}"#;
+
+fn setup(server: &MockServer) -> YfClient {
+    let _mock = mock_chart_body(server, "BAD", BODY);
+    client_for(server)
+}
+
+#[tokio::test]
+async fn validation_warn_reports_every_anomaly_by_bar_and_rule() {
+    let server = setup_server();
+    let client = setup(&server);
+
+    let (resp, report) = HistoryBuilder::new(&client, "BAD")
+        .auto_adjust(false)
+        .keepna(true)
+        .validation(ValidationMode::Warn)
+        .fetch_full_validated()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.candles.len(), 7, "warn leaves the series untouched");
+
+    let found: Vec<(usize, ValidationRule)> = report.iter().map(|i| (i.index, i.rule)).collect();
+    assert_eq!(
+        found,
+        vec![
+            (1, ValidationRule::HighBelowLow),
+            (2, ValidationRule::CloseOutsideRange),
+            (3, ValidationRule::DuplicateTimestamp),
+            (4, ValidationRule::NonMonotonicTimestamp),
+            (5, ValidationRule::MissingPrice(PriceField::Open)),
+            (5, ValidationRule::MissingPrice(PriceField::High)),
+            (5, ValidationRule::MissingPrice(PriceField::Low)),
+            (5, ValidationRule::MissingPrice(PriceField::Close)),
+            (6, ValidationRule::NegativePrice(PriceField::Open)),
+        ]
+    );
+    assert_eq!(report.bars(), vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[tokio::test]
+async fn validation_drop_removes_offending_bars() {
+    let server = setup_server();
+    let client = setup(&server);
+
+    let (resp, report) = HistoryBuilder::new(&client, "BAD")
+        .auto_adjust(false)
+        .keepna(true)
+        .validation(ValidationMode::Drop)
+        .fetch_full_validated()
+        .await
+        .unwrap();
+
+    assert_eq!(report.bars().len(), 6);
+    assert_eq!(resp.candles.len(), 1);
+    assert_eq!(resp.candles[0].ts.timestamp(), 1000);
+}
+
+#[tokio::test]
+async fn validation_error_fails_the_request() {
+    let server = setup_server();
+    let client = setup(&server);
+
+    let err = HistoryBuilder::new(&client, "BAD")
+        .auto_adjust(false)
+        .validation(ValidationMode::Error)
+        .fetch_full()
+        .await
+        .unwrap_err();
+
+    match err {
+        YfError::Validation(report) => {
+            assert!(
+                report
+                    .iter()
+                    .any(|i| i.rule == ValidationRule::HighBelowLow)
+            );
+        }
+        other => panic!("expected validation error, got {other:?}"),
+    }
+
+    // Off (the default) passes the bars through as before.
+    let resp = HistoryBuilder::new(&client, "BAD")
+        .auto_adjust(false)
+        .fetch_full()
+        .await
+        .unwrap();
+    assert_eq!(resp.candles.len(), 6);
+}
@@ -7,3 +7,15 @@ mod quotes_retry_synth;
 
 #[path = "quotes/live.rs"]
 mod live;
+
+#[path = "quotes/detail.rs"]
+mod quotes_detail;
+
+#[path = "quotes/batching.rs"]
+mod quotes_batching;
+
+#[path = "quotes/fields.rs"]
+mod quotes_fields;
+
+#[path = "quotes/asset_kind.rs"]
+mod quotes_asset_kind;
@@ -0,0 +1,70 @@
+use crate::common::{client_for, fixture, mock_chart_body, setup_server};
+use paft::domain::AssetKind;
+use yfinance_rs::DownloadBuilder;
+use yfinance_rs::core::conversions::{string_to_asset_kind, symbol_to_asset_kind};
+use yfinance_rs::stream::ws_quote_type_to_asset_kind;
+
+#[test]
+fn symbol_syntax_identifies_non_equities() {
+    assert_eq!(symbol_to_asset_kind("^GSPC"), AssetKind::Index);
+    assert_eq!(symbol_to_asset_kind("EURUSD=X"), AssetKind::Forex);
+    assert_eq!(symbol_to_asset_kind("ES=F"), AssetKind::Future);
+    assert_eq!(symbol_to_asset_kind("BTC-USD"), AssetKind::Crypto);
+    assert_eq!(symbol_to_asset_kind("ETH-BTC"), AssetKind::Crypto);
+    assert_eq!(
+        symbol_to_asset_kind("AAPL240119C00150000"),
+        AssetKind::Option
+    );
+
+    // Share classes and exchange suffixes stay equities.
+    assert_eq!(symbol_to_asset_kind("AAPL"), AssetKind::Equity);
+    assert_eq!(symbol_to_asset_kind("BRK-B"), AssetKind::Equity);
+    assert_eq!(symbol_to_asset_kind("TSCO.L"), AssetKind::Equity);
+}
+
+#[test]
+fn yahoo_quote_types_map_to_asset_kinds() {
+    assert_eq!(string_to_asset_kind("EQUITY"), Some(AssetKind::Equity));
+    assert_eq!(string_to_asset_kind("ETF"), Some(AssetKind::Fund));
+    assert_eq!(string_to_asset_kind("MUTUALFUND"), Some(AssetKind::Fund));
+    assert_eq!(string_to_asset_kind("INDEX"), Some(AssetKind::Index));
+    assert_eq!(string_to_asset_kind("CURRENCY"), Some(AssetKind::Forex));
+    assert_eq!(
+        string_to_asset_kind("CRYPTOCURRENCY"),
+        Some(AssetKind::Crypto)
+    );
+    assert_eq!(string_to_asset_kind("FUTURE"), Some(AssetKind::Future));
+
+    assert_eq!(ws_quote_type_to_asset_kind(8), Some(AssetKind::Equity));
+    assert_eq!(ws_quote_type_to_asset_kind(20), Some(AssetKind::Fund));
+    assert_eq!(ws_quote_type_to_asset_kind(41), Some(AssetKind::Crypto));
+    assert_eq!(ws_quote_type_to_asset_kind(0), None);
+}
+
+#[tokio::test]
+async fn chart_meta_corrects_a_kind_guessed_from_the_symbol() {
+    let server = setup_server();
+    let client = client_for(&server);
+    let with_type = fixture("history_chart", "CGFUND", "json");
+    let without_type = with_type.replace(r#""instrumentType": "MUTUALFUND","#, "");
+    assert_ne!(with_type, without_type);
+
+    // Without a type in the chart meta the kind is guessed from the symbol syntax.
+    let mut untyped = mock_chart_body(&server, "CGFUND", without_type);
+    let res = DownloadBuilder::new(&client)
+        .symbols(["CGFUND"])
+        .run()
+        .await
+        .unwrap();
+    assert_eq!(*res.entries[0].instrument.kind(), AssetKind::Equity);
+    untyped.delete();
+
+    // A later chart naming the type replaces the cached guess.
+    let _typed = mock_chart_body(&server, "CGFUND", with_type);
+    let res = DownloadBuilder::new(&client)
+        .symbols(["CGFUND"])
+        .run()
+        .await
+        .unwrap();
+    assert_eq!(*res.entries[0].instrument.kind(), AssetKind::Fund);
+}
@@ -0,0 +1,115 @@
+use crate::common::{client_for, setup_server};
+use httpmock::Method::GET;
+use yfinance_rs::{QuotesBuilder, YfError};
+
+fn node(sym: &str) -> String {
+    format!(r#"{{"symbol":"{sym}","regularMarketPrice":1.0,"currency":"USD"}}"#)
+}
+
+fn body(nodes: &[&str]) -> String {
+    let result = nodes.iter().map(|s| node(s)).collect::<Vec<_>>().join(",");
+    format!(r#"{{"quoteResponse":{{"result":[{result}],"error":null}}}}"#)
+}
+
+fn symbol_of(q: &yfinance_rs::Quote) -> String {
+    match q.instrument.id() {
+        paft::domain::IdentifierScheme::Security(s) => s.symbol.as_str().to_string(),
+        paft::domain::IdentifierScheme::Prediction(_) => String::new(),
+    }
+}
+
+#[tokio::test]
+async fn large_lists_are_chunked_and_merged_in_request_order() {
+    let server = setup_server();
+    // Yahoo may answer a chunk out of order.
+    let first = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v7/finance/quote")
+            .query_param("symbols", "AAA,BBB");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(body(&["BBB", "AAA"]));
+    });
+    let second = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v7/finance/quote")
+            .query_param("symbols", "CCC,DDD");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(body(&["CCC"]));
+    });
+    let third = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v7/finance/quote")
+            .query_param("symbols", "EEE");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(body(&["EEE"]));
+    });
+
+    let client = client_for(&server);
+
+    let batch = QuotesBuilder::new(client)
+        .symbols(["AAA", "BBB", "CCC", "aaa", "DDD", "EEE"])
+        .chunk_size(2)
+        .concurrency(2)
+        .fetch_batch()
+        .await
+        .unwrap();
+
+    first.assert();
+    second.assert();
+    third.assert();
+
+    let syms: Vec<String> = batch.quotes.iter().map(symbol_of).collect();
+    assert_eq!(syms, ["AAA", "BBB", "CCC", "EEE"]);
+    assert_eq!(batch.missing, ["DDD"]);
+}
+
+#[tokio::test]
+async fn a_failed_chunk_does_not_fail_the_others() {
+    let server = setup_server();
+    let ok = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v7/finance/quote")
+            .query_param("symbols", "AAA,BBB");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(body(&["AAA", "BBB"]));
+    });
+    let bad = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v7/finance/quote")
+            .query_param("symbols", "CCC,DDD");
+        then.status(400);
+    });
+
+    let client = client_for(&server);
+
+    let batch = QuotesBuilder::new(client.clone())
+        .symbols(["AAA", "BBB", "CCC", "DDD"])
+        .chunk_size(2)
+        .fetch_batch()
+        .await
+        .unwrap();
+
+    ok.assert();
+    bad.assert();
+
+    let syms: Vec<String> = batch.quotes.iter().map(symbol_of).collect();
+    assert_eq!(syms, ["AAA", "BBB"]);
+    assert!(batch.missing.is_empty());
+    assert_eq!(batch.failed.len(), 1);
+    let (failed_syms, err) = &batch.failed[0];
+    assert_eq!(failed_syms, &["CCC", "DDD"]);
+    assert!(matches!(err, YfError::Status { status: 400, .. }));
+
+    // With every chunk failing there is nothing to return.
+    let err = QuotesBuilder::new(client)
+        .symbols(["CCC", "DDD"])
+        .chunk_size(2)
+        .fetch_batch()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, YfError::Status { status: 400, .. }));
+}
@@ -0,0 +1,90 @@
+use crate::common::{client_for, dec, setup_server};
+use httpmock::Method::GET;
+use httpmock::MockServer;
+use yfinance_rs::{QuotesBuilder, Ticker, YfClient};
+
+const BODY: &str = r#"{
+  "quoteResponse": {
+    "result": [{
+      "symbol": "AAPL", "quoteType": "EQUITY", "currency": "USD", "fullExchangeName": "NasdaqGS",
+      "shortName": "Apple Inc.", "longName": "Apple Inc.", "exchangeTimezoneName": "America/New_York",
+      "regularMarketPrice": 190.5, "regularMarketPreviousClose": 188.0, "regularMarketVolume": 5000000,
+      "regularMarketOpen": 189.0, "regularMarketDayHigh": 191.25, "regularMarketDayLow": 188.75,
+      "regularMarketChange": 2.5, "regularMarketChangePercent": 1.3298, "regularMarketTime": 1700000000,
+      "bid": 190.49, "ask": 190.52, "bidSize": 8, "askSize": 1.2e1,
+      "fiftyTwoWeekHigh": 199.62, "fiftyTwoWeekLow": 124.17,
+      "fiftyDayAverage": 178.3, "twoHundredDayAverage": 172.1,
+      "averageDailyVolume3Month": 55000000, "averageDailyVolume10Day": 48000000,
+      "marketCap": 2950000000000, "sharesOutstanding": 15500000000,
+      "trailingPE": 31.2, "forwardPE": 28.4, "epsTrailingTwelveMonths": 6.1, "epsForward": 6.7,
+      "priceToBook": 47.9, "bookValue": 3.98,
+      "dividendRate": 0.96, "dividendYield": 0.51,
+      "trailingAnnualDividendRate": 0.94, "trailingAnnualDividendYield": 0.005,
+      "postMarketPrice": 190.8, "postMarketChange": 0.3, "postMarketChangePercent": 0.157, "postMarketTime": 1700010000
+    }],
+    "error": null
+  }
+}"#;
+
+fn mocked_client(server: &MockServer) -> YfClient {
+    server.mock(|when, then| {
+        when.method(GET).path("/v7/finance/quote");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(BODY);
+    });
+    client_for(server)
+}
+
+#[tokio::test]
+async fn detailed_quote_exposes_full_v7_payload() {
+    let server = setup_server();
+    let client = mocked_client(&server);
+
+    let quotes = QuotesBuilder::new(client)
+        .symbols(["AAPL"])
+        .fetch_detailed()
+        .await
+        .unwrap();
+    assert_eq!(quotes.len(), 1);
+    let q = &quotes[0];
+
+    assert_eq!(q.quote.price.as_ref().unwrap().amount(), dec("190.5"));
+    assert_eq!(q.long_name.as_deref(), Some("Apple Inc."));
+    assert_eq!(q.exchange_timezone.as_deref(), Some("America/New_York"));
+    assert_eq!(q.bid.as_ref().unwrap().amount(), dec("190.49"));
+    assert_eq!(q.ask.as_ref().unwrap().amount(), dec("190.52"));
+    assert_eq!(q.spread(), Some(dec("0.03")));
+    assert_eq!(q.bid_size, Some(8));
+    assert_eq!(q.ask_size, Some(12));
+    assert_eq!(q.day_high.as_ref().unwrap().amount(), dec("191.25"));
+    assert_eq!(
+        q.fifty_two_week_low.as_ref().unwrap().amount(),
+        dec("124.17")
+    );
+    assert_eq!(q.average_volume_3_month, Some(55_000_000));
+    assert_eq!(q.shares_outstanding, Some(15_500_000_000));
+    assert_eq!(q.market_cap, Some(dec("2950000000000")));
+    assert_eq!(q.trailing_pe, Some(dec("31.2")));
+    assert_eq!(q.trailing_annual_dividend_yield, Some(dec("0.005")));
+
+    assert_eq!(q.regular.change.as_ref().unwrap().amount(), dec("2.5"));
+    assert_eq!(q.regular.time.unwrap().timestamp(), 1_700_000_000);
+    assert_eq!(q.post_market.price.as_ref().unwrap().amount(), dec("190.8"));
+    assert_eq!(q.post_market.time.unwrap().timestamp(), 1_700_010_000);
+    assert!(q.pre_market.price.is_none());
+    assert!(q.pre_market.time.is_none());
+}
+
+#[tokio::test]
+async fn ticker_quote_detail_matches_quote() {
+    let server = setup_server();
+    let client = mocked_client(&server);
+    let ticker = Ticker::new(&client, "AAPL");
+
+    let detail = ticker.quote_detail().await.unwrap();
+    let plain = ticker.quote().await.unwrap();
+    assert_eq!(detail.quote.price, plain.price);
+    assert_eq!(detail.quote.day_volume, Some(5_000_000));
+    assert_eq!(detail.open.unwrap().amount(), dec("189.0"));
+}
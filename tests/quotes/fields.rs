@@ -0,0 +1,49 @@
+use crate::common::{client_for, setup_server};
+use httpmock::Method::GET;
+use yfinance_rs::{QuoteField, QuotesBuilder};
+
+#[tokio::test]
+async fn field_selection_is_sent_and_unrequested_fields_are_dropped() {
+    let server = setup_server();
+    // Yahoo sends a few unrequested fields regardless of `fields=`.
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v7/finance/quote")
+            .query_param("symbols", "AAPL")
+            .query_param(
+                "fields",
+                "symbol,quoteType,currency,exchange,fullExchangeName,market,\
+                 regularMarketPrice,regularMarketVolume",
+            );
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(
+                r#"{"quoteResponse":{"result":[{
+                    "symbol":"AAPL","quoteType":"EQUITY","currency":"USD",
+                    "regularMarketPrice":190.5,"regularMarketVolume":1000,
+                    "shortName":"Apple Inc.","regularMarketPreviousClose":189.0,
+                    "bid":190.4,"ask":190.6
+                }],"error":null}}"#,
+            );
+    });
+
+    let client = client_for(&server);
+
+    let details = QuotesBuilder::new(client)
+        .symbols(["AAPL"])
+        .fields([QuoteField::Volume, QuoteField::Price])
+        .fetch_detailed()
+        .await
+        .unwrap();
+    mock.assert();
+
+    let d = &details[0];
+    assert_eq!(
+        d.quote.price.as_ref().unwrap().amount(),
+        rust_decimal::Decimal::new(1905, 1)
+    );
+    assert_eq!(d.quote.day_volume, Some(1000));
+    assert!(d.quote.shortname.is_none());
+    assert!(d.quote.previous_close.is_none());
+    assert!(d.bid.is_none() && d.ask.is_none());
+}
@@ -0,0 +1,4 @@
+mod common;
+
+#[path = "spark/offline.rs"]
+mod spark_offline;
@@ -0,0 +1,200 @@
+use crate::common::{client_for, dec, setup_server};
+use httpmock::Method::GET;
+use paft::money::Currency;
+use std::str::FromStr;
+use yfinance_rs::core::{Interval, Range};
+use yfinance_rs::{DownloadBuilder, SparkBuilder, YfError};
+
+fn wrapped(symbol: &str, currency: &str, closes: &str) -> String {
+    format!(
+        r#"{{"symbol":"{symbol}","response":[{{
+            "meta":{{"currency":"{currency}","chartPreviousClose":99.5}},
+            "timestamp":[1000,2000,3000],
+            "indicators":{{"quote":[{{"close":{closes}}}]}}
+        }}]}}"#
+    )
+}
+
+#[tokio::test]
+async fn spark_batches_symbols_and_maps_close_series() {
+    let server = setup_server();
+
+    let first = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/spark")
+            .query_param("symbols", "AAA,BBB")
+            .query_param("range", "5d")
+            .query_param("interval", "1d");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(format!(
+                r#"{{"spark":{{"result":[{},{}],"error":null}}}}"#,
+                wrapped("AAA", "USD", "[100.1,null,101.25]"),
+                wrapped("BBB", "EUR", "[10.0,10.5,11.0]")
+            ));
+    });
+    // Second batch comes back in the flat, symbol-keyed shape.
+    let second = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/spark")
+            .query_param("symbols", "CCC,MISSING");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(
+                r#"{"CCC":{"symbol":"CCC","timestamp":[1000,2000],"close":[5.5,5.75],"chartPreviousClose":5.0}}"#,
+            );
+    });
+
+    let series = SparkBuilder::new(&client_for(&server))
+        .symbols(["AAA", "BBB", "CCC", "MISSING"])
+        .range(Range::D5)
+        .interval(Interval::D1)
+        .batch_size(2)
+        .fetch()
+        .await
+        .unwrap();
+
+    first.assert();
+    second.assert();
+
+    let syms: Vec<&str> = series.iter().map(|s| s.symbol.as_str()).collect();
+    assert_eq!(syms, ["AAA", "BBB", "CCC"]);
+
+    let aaa = &series[0];
+    assert_eq!(aaa.currency, Some(Currency::from_str("USD").unwrap()));
+    assert_eq!(aaa.closes.len(), 2, "null close skipped");
+    assert_eq!(aaa.closes[0].ts.timestamp(), 1000);
+    assert_eq!(aaa.closes[0].close.amount(), dec("100.1"));
+    assert_eq!(aaa.closes[1].close.amount(), dec("101.25"));
+    assert_eq!(aaa.previous_close.as_ref().unwrap().amount(), dec("99.5"));
+
+    assert_eq!(
+        series[1].closes[2].close.currency(),
+        &Currency::from_str("EUR").unwrap()
+    );
+    assert_eq!(series[2].closes[1].close.amount(), dec("5.75"));
+    assert_eq!(series[2].currency, None);
+}
+
+#[tokio::test]
+async fn download_closes_only_uses_spark() {
+    let server = setup_server();
+
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/spark")
+            .query_param("symbols", "AAA,BBB");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(format!(
+                r#"{{"spark":{{"result":[{},{}],"error":null}}}}"#,
+                wrapped("AAA", "USD", "[100.1,null,101.25]"),
+                wrapped("BBB", "USD", "[10.0,10.5,11.0]")
+            ));
+    });
+
+    let resp = DownloadBuilder::new(&client_for(&server))
+        .symbols(["AAA", "BBB"])
+        .closes_only(true)
+        .run()
+        .await
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(resp.entries.len(), 2);
+
+    let aaa = &resp.entries[0].history;
+    assert!(!aaa.adjusted);
+    assert_eq!(aaa.candles.len(), 2);
+    let c = &aaa.candles[1];
+    assert_eq!(c.close.amount(), dec("101.25"));
+    assert_eq!(c.open, c.close);
+    assert_eq!(c.high, c.close);
+    assert_eq!(c.low, c.close);
+    assert_eq!(c.volume, None);
+    assert_eq!(resp.entries[1].history.candles.len(), 3);
+}
+
+#[tokio::test]
+async fn spark_matches_symbols_case_insensitively() {
+    let server = setup_server();
+
+    let _mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/spark")
+            .query_param("symbols", "aaa");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(format!(
+                r#"{{"spark":{{"result":[{}],"error":null}}}}"#,
+                wrapped("AAA", "USD", "[100.1,101.25]")
+            ));
+    });
+
+    let series = SparkBuilder::new(&client_for(&server))
+        .symbols(["aaa"])
+        .fetch()
+        .await
+        .unwrap();
+
+    assert_eq!(series.len(), 1);
+    assert_eq!(series[0].symbol, "aaa", "the requested spelling is kept");
+    assert_eq!(series[0].closes.len(), 2);
+}
+
+#[tokio::test]
+async fn download_closes_only_reports_missing_symbols_as_not_found() {
+    let server = setup_server();
+
+    let _mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/spark")
+            .query_param("symbols", "AAA,GONE");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(format!(
+                r#"{{"spark":{{"result":[{}],"error":null}}}}"#,
+                wrapped("AAA", "USD", "[100.1,101.25]")
+            ));
+    });
+
+    let partial = DownloadBuilder::new(&client_for(&server))
+        .symbols(["AAA", "GONE"])
+        .closes_only(true)
+        .run_partial()
+        .await
+        .unwrap();
+
+    assert_eq!(partial.response.entries.len(), 1);
+    assert!(matches!(
+        partial.failures.get("GONE"),
+        Some(YfError::NotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn spark_reads_out_of_range_closes_as_missing() {
+    let server = setup_server();
+    let _mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v8/finance/spark")
+            .query_param("symbols", "CCC");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(
+                r#"{"CCC":{"symbol":"CCC","timestamp":[1000,2000,3000],"close":[5.5,1e300,5.75],"chartPreviousClose":1e300}}"#,
+            );
+    });
+
+    let series = SparkBuilder::new(&client_for(&server))
+        .symbols(["CCC"])
+        .fetch()
+        .await
+        .unwrap();
+
+    let ccc = &series[0];
+    let ts: Vec<i64> = ccc.closes.iter().map(|c| c.ts.timestamp()).collect();
+    assert_eq!(ts, [1000, 3000], "the unreadable close is skipped");
+    assert_eq!(ccc.closes[1].close.amount(), dec("5.75"));
+    assert!(ccc.previous_close.is_none());
+}
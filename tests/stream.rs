@@ -1,8 +1,16 @@
 mod common;
+#[path = "stream/common.rs"]
+mod stream_common;
 
+#[path = "stream/detailed.rs"]
+mod stream_detailed;
 #[path = "stream/live.rs"]
 mod stream_live;
 #[path = "stream/offline.rs"]
 mod stream_offline;
+#[path = "stream/reconnect.rs"]
+mod stream_reconnect;
+#[path = "stream/subscribe.rs"]
+mod stream_subscribe;
 #[path = "stream/websocket_decoder.rs"]
 mod websocket_decoder;
@@ -0,0 +1,91 @@
+#![allow(dead_code)]
+
+use base64::{Engine as _, engine::general_purpose};
+use futures_util::StreamExt;
+use prost::Message;
+use std::net::SocketAddr;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{WebSocketStream, accept_async, tungstenite};
+use url::Url;
+use yfinance_rs::YfClient;
+
+/// The subset of `yaticker.proto` the stream tests exercise; tags match the real schema.
+#[derive(Clone, PartialEq, Message)]
+pub struct PricingData {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(float, tag = "2")]
+    pub price: f32,
+    #[prost(sint64, tag = "3")]
+    pub time: i64,
+    #[prost(string, tag = "4")]
+    pub currency: String,
+    #[prost(int32, tag = "6")]
+    pub quote_type: i32,
+    #[prost(int32, tag = "7")]
+    pub market_hours: i32,
+    #[prost(float, tag = "8")]
+    pub change_percent: f32,
+    #[prost(sint64, tag = "9")]
+    pub day_volume: i64,
+    #[prost(float, tag = "10")]
+    pub day_high: f32,
+    #[prost(float, tag = "11")]
+    pub day_low: f32,
+    #[prost(string, tag = "13")]
+    pub short_name: String,
+    #[prost(sint64, tag = "14")]
+    pub expire_date: i64,
+    #[prost(float, tag = "16")]
+    pub previous_close: f32,
+    #[prost(float, tag = "17")]
+    pub strike_price: f32,
+    #[prost(string, tag = "18")]
+    pub underlying_symbol: String,
+    #[prost(sint64, tag = "19")]
+    pub open_interest: i64,
+    #[prost(sint64, tag = "20")]
+    pub options_type: i64,
+    #[prost(float, tag = "23")]
+    pub bid: f32,
+    #[prost(sint64, tag = "24")]
+    pub bid_size: i64,
+    #[prost(float, tag = "25")]
+    pub ask: f32,
+    #[prost(sint64, tag = "26")]
+    pub ask_size: i64,
+}
+
+/// Wraps an encoded `PricingData` in the JSON text frame Yahoo sends.
+pub fn encode(msg: &PricingData) -> String {
+    let b64 = general_purpose::STANDARD.encode(msg.encode_to_vec());
+    format!(r#"{{"type":"pricing","message":"{b64}"}}"#)
+}
+
+/// Builds the websocket text frame carrying `msg`.
+pub fn frame(msg: &PricingData) -> tungstenite::Message {
+    tungstenite::Message::Text(encode(msg).into())
+}
+
+/// Builds a client whose stream endpoint points at a local websocket server.
+pub fn ws_client(addr: SocketAddr) -> YfClient {
+    YfClient::builder()
+        .base_stream(Url::parse(&format!("ws://{addr}/")).unwrap())
+        .build()
+        .unwrap()
+}
+
+/// Accepts the next connection on `listener` and completes the websocket handshake.
+pub async fn accept(listener: &TcpListener) -> WebSocketStream<TcpStream> {
+    let (tcp, _) = listener.accept().await.unwrap();
+    accept_async(tcp).await.unwrap()
+}
+
+/// Reads frames until the client sends a text message, returning it.
+pub async fn next_text(ws: &mut WebSocketStream<TcpStream>) -> String {
+    loop {
+        if let tungstenite::Message::Text(t) = ws.next().await.unwrap().unwrap() {
+            return t.to_string();
+        }
+    }
+}
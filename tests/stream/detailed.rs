@@ -0,0 +1,76 @@
+use crate::common::dec;
+use crate::stream_common::{PricingData, encode};
+use paft::domain::AssetKind;
+use yfinance_rs::stream::{MarketHours, OptionSide, decode_and_map_detailed_message};
+
+#[test]
+fn detailed_decoder_keeps_option_fields() {
+    let msg = PricingData {
+        id: "AAPL240119C00150000".into(),
+        price: 12.5,
+        time: 1_700_000_000_000,
+        currency: "USD".into(),
+        quote_type: 13,
+        market_hours: 1,
+        change_percent: -1.25,
+        day_volume: 420,
+        day_high: 13.1,
+        day_low: 11.9,
+        short_name: "AAPL Jan 2024 150 call".into(),
+        expire_date: 1_705_622_400,
+        previous_close: 12.0,
+        strike_price: 150.0,
+        underlying_symbol: "AAPL".into(),
+        open_interest: 1_000,
+        options_type: 0,
+        bid: 12.4,
+        bid_size: 3,
+        ask: 12.6,
+        ask_size: 7,
+    };
+
+    let d = decode_and_map_detailed_message(&encode(&msg)).unwrap();
+
+    assert_eq!(d.update.price.as_ref().unwrap().amount(), dec("12.5"));
+    assert_eq!(d.quote_type, Some(AssetKind::Option));
+    assert_eq!(d.market_hours, Some(MarketHours::Regular));
+    assert_eq!(d.change_percent, Some(dec("-1.25")));
+    assert_eq!(d.day_volume, Some(420));
+    assert_eq!(d.day_high.as_ref().unwrap().amount(), dec("13.1"));
+    assert_eq!(d.bid.as_ref().unwrap().amount(), dec("12.4"));
+    assert_eq!(d.ask.as_ref().unwrap().amount(), dec("12.6"));
+    assert_eq!((d.bid_size, d.ask_size), (Some(3), Some(7)));
+    assert_eq!(d.strike.as_ref().unwrap().amount(), dec("150"));
+    assert_eq!(d.option_side, Some(OptionSide::Call));
+    assert_eq!(d.underlying_symbol.as_deref(), Some("AAPL"));
+    assert_eq!(d.open_interest, Some(1_000));
+    assert_eq!(d.expiry.unwrap().timestamp(), 1_705_622_400);
+
+    // Fields Yahoo left at zero are absent rather than zero.
+    assert!(d.open.is_none());
+    assert!(d.last_size.is_none());
+    assert!(d.market_cap.is_none());
+}
+
+#[test]
+fn plain_decoder_matches_the_detailed_update() {
+    let msg = PricingData {
+        id: "BTC-USD".into(),
+        price: 43_000.5,
+        time: 1_700_000_000_000,
+        currency: "USD".into(),
+        quote_type: 41,
+        previous_close: 42_000.0,
+        ..Default::default()
+    };
+    let text = encode(&msg);
+
+    let plain = yfinance_rs::stream::decode_and_map_message(&text).unwrap();
+    let detail = decode_and_map_detailed_message(&text).unwrap();
+    assert_eq!(plain.price, detail.update.price);
+    assert_eq!(plain.ts, detail.update.ts);
+    assert_eq!(detail.quote_type, Some(AssetKind::Crypto));
+    assert!(detail.option_side.is_none());
+    // Crypto has no session, so the unset market hours are not read as pre-market.
+    assert!(detail.market_hours.is_none());
+}
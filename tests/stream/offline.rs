@@ -188,3 +188,38 @@ async fn stream_polling_emits_on_volume_only_change_with_diff_only() {
         "second update should carry positive volume delta when price is unchanged"
     );
 }
+
+#[tokio::test]
+async fn stream_polling_keeps_the_quote_exchange_across_polls() {
+    let server = crate::common::setup_server();
+    let _mock = crate::common::mock_quote_v7(&server, "MSFT");
+    let client = crate::common::client_for(&server);
+
+    let (handle, mut rx) = yfinance_rs::StreamBuilder::new(&client)
+        .symbols(["MSFT"])
+        .method(StreamMethod::Polling)
+        .interval(Duration::from_millis(40))
+        .diff_only(false)
+        .cache_mode(CacheMode::Bypass)
+        .start()
+        .unwrap();
+
+    let mut exchanges = Vec::new();
+    for _ in 0..2 {
+        let update = timeout(Duration::from_secs(3), rx.recv())
+            .await
+            .expect("timed out waiting for a polled update")
+            .expect("stream closed before two updates");
+        match update.instrument.id() {
+            IdentifierScheme::Security(s) => exchanges.push(s.exchange.clone()),
+            IdentifierScheme::Prediction(_) => panic!("unexpected instrument identifier scheme"),
+        }
+    }
+    handle.abort();
+
+    assert!(
+        exchanges[0].is_some(),
+        "the quote's exchange should be kept"
+    );
+    assert_eq!(exchanges[0], exchanges[1]);
+}
@@ -0,0 +1,150 @@
+use crate::stream_common::{PricingData, accept, frame, next_text, ws_client};
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{Duration, timeout};
+use tokio_tungstenite::tungstenite::Message;
+use yfinance_rs::core::client::Backoff;
+use yfinance_rs::{StreamBuilder, StreamMethod};
+
+fn tick(price: f32, time: i64, day_volume: i64) -> Message {
+    frame(&PricingData {
+        id: "AAPL".into(),
+        price,
+        time,
+        currency: "USD".into(),
+        day_volume,
+        previous_close: 100.0,
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn websocket_reconnects_resubscribes_and_keeps_volume_state() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (subs_tx, mut subs_rx) = mpsc::unbounded_channel::<String>();
+
+    let server = tokio::spawn(async move {
+        // First connection: one tick, then the server hangs up.
+        let mut ws = accept(&listener).await;
+        subs_tx.send(next_text(&mut ws).await).unwrap();
+        ws.send(tick(190.0, 1_700_000_000_000, 1_000))
+            .await
+            .unwrap();
+        ws.close(None).await.unwrap();
+        while let Some(Ok(_)) = ws.next().await {}
+
+        // Second connection: wait for a keep-alive ping before sending the next tick.
+        let mut ws = accept(&listener).await;
+        subs_tx.send(next_text(&mut ws).await).unwrap();
+        loop {
+            if let Message::Ping(_) = ws.next().await.unwrap().unwrap() {
+                break;
+            }
+        }
+        ws.send(tick(191.0, 1_700_000_001_000, 1_500))
+            .await
+            .unwrap();
+        while let Some(Ok(_)) = ws.next().await {}
+    });
+
+    let client = ws_client(addr);
+
+    let (handle, mut rx) = StreamBuilder::new(&client)
+        .symbols(["AAPL", "MSFT"])
+        .method(StreamMethod::Websocket)
+        .reconnect_backoff(Backoff::Fixed(Duration::from_millis(10)))
+        .ping_interval(Some(Duration::from_millis(20)))
+        .start()
+        .unwrap();
+
+    let first = timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("timed out waiting for the first update")
+        .expect("stream ended before the first update");
+    let second = timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("timed out waiting for an update after reconnecting")
+        .expect("stream ended instead of reconnecting");
+    handle.stop().await;
+    server.await.unwrap();
+
+    let expected = r#"{"subscribe":["AAPL","MSFT"]}"#;
+    assert_eq!(subs_rx.recv().await.unwrap(), expected);
+    assert_eq!(
+        subs_rx.recv().await.unwrap(),
+        expected,
+        "symbols should be resubscribed on the new connection"
+    );
+
+    assert_eq!(first.volume, None, "first tick has no delta");
+    assert_eq!(
+        second.volume,
+        Some(500),
+        "delta should carry over from the previous connection"
+    );
+}
+
+#[tokio::test]
+async fn websocket_without_reconnect_ends_when_socket_closes() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = tokio::spawn(async move {
+        let mut ws = accept(&listener).await;
+        next_text(&mut ws).await;
+        ws.close(None).await.unwrap();
+        while let Some(Ok(_)) = ws.next().await {}
+    });
+
+    let client = ws_client(addr);
+
+    let (handle, mut rx) = StreamBuilder::new(&client)
+        .symbols(["AAPL"])
+        .method(StreamMethod::Websocket)
+        .reconnect(false)
+        .start()
+        .unwrap();
+
+    let end = timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("stream should end once the socket closes");
+    assert!(end.is_none());
+    handle.stop().await;
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn websocket_session_ends_when_pings_go_unanswered() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (done_tx, done_rx) = oneshot::channel::<()>();
+
+    let server = tokio::spawn(async move {
+        let mut ws = accept(&listener).await;
+        next_text(&mut ws).await;
+        // Stop reading, so the client's pings are never answered.
+        done_rx.await.ok();
+        drop(ws);
+    });
+
+    let client = ws_client(addr);
+
+    let (handle, mut rx) = StreamBuilder::new(&client)
+        .symbols(["AAPL"])
+        .method(StreamMethod::Websocket)
+        .reconnect(false)
+        .ping_interval(Some(Duration::from_millis(20)))
+        .max_missed_pings(Some(2))
+        .start()
+        .unwrap();
+
+    let end = timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("stream should end once the server stops answering");
+    assert!(end.is_none());
+    handle.stop().await;
+    done_tx.send(()).unwrap();
+    server.await.unwrap();
+}
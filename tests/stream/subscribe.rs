@@ -0,0 +1,173 @@
+use crate::common::{client_for, fixture, setup_server};
+use crate::stream_common::{PricingData, accept, frame, ws_client};
+use GET;
+use futures_util::{SinkExt, StreamExt};
+use paft::domain::IdentifierScheme;
+use paft::market::quote::QuoteUpdate;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio::time::{Duration, timeout};
+use tokio_tungstenite::tungstenite::Message;
+use yfinance_rs::{StreamBuilder, StreamMethod};
+
+fn tick(id: &str, day_volume: i64) -> Message {
+    frame(&PricingData {
+        id: id.into(),
+        price: 100.0,
+        time: 1_700_000_000_000,
+        currency: "USD".into(),
+        day_volume,
+        ..Default::default()
+    })
+}
+
+async fn next_request(rx: &mut mpsc::UnboundedReceiver<String>) -> String {
+    timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("timed out waiting for a websocket request")
+        .unwrap()
+}
+
+fn symbol_of(u: &QuoteUpdate) -> String {
+    match u.instrument.id() {
+        IdentifierScheme::Security(s) => s.symbol.as_str().to_string(),
+        IdentifierScheme::Prediction(_) => String::new(),
+    }
+}
+
+#[tokio::test]
+async fn websocket_subscribe_and_unsubscribe_while_running() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (requests_tx, mut requests_rx) = mpsc::unbounded_channel::<String>();
+    let (ticks_tx, mut ticks_rx) = mpsc::unbounded_channel::<Message>();
+
+    let server = tokio::spawn(async move {
+        let mut ws = accept(&listener).await;
+        loop {
+            tokio::select! {
+                msg = ws.next() => match msg {
+                    Some(Ok(Message::Text(t))) => requests_tx.send(t.to_string()).unwrap(),
+                    Some(Ok(_)) => {}
+                    _ => break,
+                },
+                Some(tick) = ticks_rx.recv() => ws.send(tick).await.unwrap(),
+            }
+        }
+    });
+
+    let client = ws_client(addr);
+
+    let (handle, mut rx) = StreamBuilder::new(&client)
+        .symbols(["AAPL"])
+        .method(StreamMethod::Websocket)
+        .start()
+        .unwrap();
+
+    assert_eq!(
+        next_request(&mut requests_rx).await,
+        r#"{"subscribe":["AAPL"]}"#
+    );
+
+    handle.subscribe(["MSFT", "aapl"]).unwrap();
+    assert_eq!(
+        next_request(&mut requests_rx).await,
+        r#"{"subscribe":["MSFT"]}"#,
+        "symbols already streamed are not resubscribed"
+    );
+
+    ticks_tx.send(tick("AAPL", 1_000)).unwrap();
+    let first = timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(first.volume, None);
+
+    handle.unsubscribe(["AAPL"]).unwrap();
+    assert_eq!(
+        next_request(&mut requests_rx).await,
+        r#"{"unsubscribe":["AAPL"]}"#
+    );
+
+    // A late tick for the removed symbol is dropped; MSFT still flows.
+    ticks_tx.send(tick("AAPL", 1_200)).unwrap();
+    ticks_tx.send(tick("MSFT", 500)).unwrap();
+    let next = timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(symbol_of(&next), "MSFT");
+
+    // Resubscribing starts AAPL afresh instead of diffing against the old volume.
+    handle.subscribe(["AAPL"]).unwrap();
+    assert_eq!(
+        next_request(&mut requests_rx).await,
+        r#"{"subscribe":["AAPL"]}"#
+    );
+    ticks_tx.send(tick("AAPL", 1_500)).unwrap();
+    let again = timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(symbol_of(&again), "AAPL");
+    assert_eq!(again.volume, None, "volume state is dropped on unsubscribe");
+
+    handle.stop().await;
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn polling_picks_up_subscribed_symbols() {
+    let server = setup_server();
+
+    let aapl = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v7/finance/quote")
+            .query_param("symbols", "AAPL");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(fixture("quote_v7", "AAPL", "json"));
+    });
+    let msft = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v7/finance/quote")
+            .query_param("symbols", "MSFT");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(fixture("quote_v7", "MSFT", "json"));
+    });
+
+    let client = client_for(&server);
+
+    let (handle, mut rx) = StreamBuilder::new(&client)
+        .symbols(["AAPL"])
+        .method(StreamMethod::Polling)
+        .interval(Duration::from_millis(40))
+        .start()
+        .unwrap();
+
+    let first = timeout(Duration::from_secs(3), rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(symbol_of(&first), "AAPL");
+
+    handle.unsubscribe(["AAPL"]).unwrap();
+    handle.subscribe(["MSFT"]).unwrap();
+
+    let next = timeout(Duration::from_secs(3), rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(symbol_of(&next), "MSFT");
+
+    let aapl_calls = aapl.calls();
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    handle.stop().await;
+    assert_eq!(
+        aapl.calls(),
+        aapl_calls,
+        "unsubscribed symbols are no longer polled"
+    );
+    assert!(msft.calls() > 0);
+}